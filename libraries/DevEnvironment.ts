@@ -28,6 +28,7 @@ export class DevEnvironment {
   tokens: { [name: string]: TokenMetadata };
   nftCollections: { [name: string]: NftWithToken };
   nfts: { [name: string]: NftWithToken | SftWithToken };
+  nftCreators: { [name: string]: Keypair };
   metaplex: Metaplex;
   justiesClients: { [name: string]: JustiesProgramClient };
   payers: { [name: string]: PublicKey };
@@ -42,6 +43,7 @@ export class DevEnvironment {
     this.tokens = {};
     this.nftCollections = {};
     this.nfts = {};
+    this.nftCreators = {};
     this.metaplex = Metaplex.make(this.connection)
       .use(keypairIdentity(this.majorSigner)).use(mockStorage());
     this.justiesClients = {
@@ -70,6 +72,10 @@ export class DevEnvironment {
     return this.nfts[name];
   }
 
+  public getNftCreator(name: string): Keypair {
+    return this.nftCreators[name];
+  }
+
   public getTokenAta(payerName: string, tokenName: string) {
     const payer = this.payers[payerName];
     const tokenMint = this.getTokenMintAddress(tokenName);
@@ -123,6 +129,15 @@ export class DevEnvironment {
     return Number(tokenAccount.amount);
   }
 
+  public async createTokenAta(owner: PublicKey, tokenName: string) {
+    await splToken.getOrCreateAssociatedTokenAccount(
+      this.connection,
+      this.majorSigner,
+      this.getTokenMintAddress(tokenName),
+      owner
+    );
+  }
+
   public async airdrop(recipientName: string, amount: number) {
     console.log(`Airdropping ${amount} SOL to: "${recipientName}"...`);
     const recipient = this.payers[recipientName];
@@ -224,6 +239,11 @@ export class DevEnvironment {
     // Creates standalone NFTs that doesn't belong to any collections.
     await this.createNft("Standalone #1");
     await this.createNft("Standalone #2");
+    await this.createNft("Artwork #1");
+
+    // Creates legacy NFTs that are identified by their verified creator.
+    this.nftCreators["Legacy"] = this.generateKeypair();
+    await this.createNft("Legacy #1", undefined, "Legacy");
   }
 
   public createJustiesClient(payerName: string) {
@@ -303,7 +323,11 @@ export class DevEnvironment {
     })).sft as SftWithToken;
   }
 
-  async createNft(name: string, collectionName?: string) {
+  async createNft(
+    name: string,
+    collectionName?: string,
+    creatorName?: string
+  ) {
     if (collectionName !== undefined) {
       console.log(`Creating NFT "${name}" in collection "${collectionName}"`);
     } else {
@@ -317,6 +341,12 @@ export class DevEnvironment {
       useNewMint: this.generateKeypair(),
       sellerFeeBasisPoints: 200,
       collection: collectionAddress,
+      // The creator is verified as it signs the transaction.
+      creators: creatorName !== undefined ? [{
+        address: this.nftCreators[creatorName].publicKey,
+        share: 100,
+        authority: this.nftCreators[creatorName],
+      }] : undefined,
       mintAuthority: this.majorSigner,
       updateAuthority: this.majorSigner,
       collectionAuthority: this.majorSigner,
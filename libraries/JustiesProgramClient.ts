@@ -1,9 +1,21 @@
 import {Justies, IDL} from "../target/types/justies";
 import * as anchor from "@project-serum/anchor";
-import {AnchorProvider, BN, IdlTypes, Program} from "@project-serum/anchor";
+import {
+  AnchorProvider,
+  BN,
+  IdlAccounts,
+  IdlTypes,
+  Program
+} from "@project-serum/anchor";
 import * as splToken from "@solana/spl-token";
 import {ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID} from "@solana/spl-token";
-import {AccountMeta, Connection, PublicKey} from "@solana/web3.js";
+import {
+  AccountMeta,
+  Connection,
+  PublicKey,
+  Transaction,
+  TransactionInstruction
+} from "@solana/web3.js";
 import {BUILTIN_PROGRAMS, findPda, findPdaTokenMetadata} from "./ProgramUtils";
 import {Metaplex, mockStorage} from "@metaplex-foundation/js";
 import {SYSVAR_SLOT_HASHES_PUBKEY} from "@solana/web3.js";
@@ -12,6 +24,24 @@ export type GroupConfig = IdlTypes<Justies>["GroupConfig"];
 export type RevenueShareConfig = IdlTypes<Justies>["RevenueShareConfig"];
 export type EligibilityCheckInput = IdlTypes<Justies>["EligibilityCheckInput"];
 export type UpdateConfigsInput = IdlTypes<Justies>["UpdateConfigsInput"];
+export type TimelockedConfigsInput =
+  IdlTypes<Justies>["TimelockedConfigsInput"];
+export type AdminRole = IdlTypes<Justies>["AdminRole"];
+export type AllowlistType = IdlTypes<Justies>["AllowlistType"];
+export type ProposalAccountMeta = IdlTypes<Justies>["ProposalAccountMeta"];
+export type CurrencyListingLimits =
+  IdlTypes<Justies>["CurrencyListingLimits"];
+export type ListingRuleOverrides = IdlTypes<Justies>["ListingRuleOverrides"];
+export type Feature = IdlTypes<Justies>["Feature"];
+export type FeeShareConfig = IdlTypes<Justies>["FeeShareConfig"];
+export type FeeTierConfig = IdlTypes<Justies>["FeeTierConfig"];
+export type FeeDiscountConfig = IdlTypes<Justies>["FeeDiscountConfig"];
+export type GlobalStates = IdlAccounts<Justies>["globalStates"];
+
+// The major version of the global states layout supported by this client. The
+// accounts of any minor version can be deserialized, as the new fields are only
+// appended.
+export const GLOBAL_STATES_MAJOR_VERSION = 1;
 
 // A client interacts with the justies program.
 export class JustiesProgramClient {
@@ -36,6 +66,26 @@ export class JustiesProgramClient {
     return this.justiesProgram.provider.publicKey;
   }
 
+  static getDefaultCurrencyListingLimits(): CurrencyListingLimits {
+    return {
+      minStartBid: new BN(0),
+      minTicketPrice: new BN(0),
+      maxTicketPrice: null,
+      minOutbidIncrement: new BN(0),
+    };
+  }
+
+  static getDefaultListingRuleOverrides(): ListingRuleOverrides {
+    return {
+      marketFeeRateBps: null,
+      minAuctionDuration: null,
+      maxAuctionDuration: null,
+      minRaffleDuration: null,
+      maxRaffleDuration: null,
+      maxRaffledNfts: null,
+    };
+  }
+
   static getDefaultUpdateConfigsInput(): UpdateConfigsInput {
     return {
       marketFeeRateBps: null,
@@ -50,12 +100,30 @@ export class JustiesProgramClient {
       maxRaffledNfts: null,
       minRaffleDuration: null,
       maxRaffleDuration: null,
-      auctionCreationEnabled: null,
-      raffleCreationEnabled: null,
+      biddingEnabled: null,
+      ticketSalesEnabled: null,
+      claimsEnabled: null,
+      quarantineWalletAddress: null,
+      curatedCreatorsOnly: null,
+      maxLiveListingsPerCreator: null,
+      listingFee: null,
+      listingFeeTokenMint: null,
+      listingFeeRefundable: null,
       numKeysPerIndexPage: null,
     };
   }
 
+  static getDefaultTimelockedConfigsInput(): TimelockedConfigsInput {
+    return {
+      marketFeeRateBps: null,
+      feeTreasuryAddress: null,
+      configTimelockDelay: null,
+      feeShares: null,
+      listingFee: null,
+      listingFeeTokenMint: null,
+    };
+  }
+
   static getPubkeyIndexPageId(totalKeys: BN, pageSize: number): number {
     return Math.trunc(totalKeys.toNumber() / pageSize);
   }
@@ -107,6 +175,87 @@ export class JustiesProgramClient {
     return recipients.concat(recipientTokenAccounts);
   }
 
+  private createRemainingAccountsForFeeDistribution(
+    tokenMintAddress: PublicKey,
+    feeShareConfigs: FeeShareConfig[],
+  ): AccountMeta[] {
+    return feeShareConfigs.map((config: FeeShareConfig) => ({
+      pubkey: splToken.getAssociatedTokenAddressSync(
+        tokenMintAddress,
+        config.treasury
+      ),
+      isWritable: true,
+      isSigner: false,
+    }));
+  }
+
+  // The listing fee token accounts are only used for the listing fee in tokens,
+  // so they are derived from the default pubkey for the listing fee in SOL.
+  private createListingFeeAccounts(
+    feeTreasury: PublicKey,
+    listingFeeTokenMint: PublicKey,
+  ) {
+    return {
+      feeTreasury: feeTreasury,
+      creatorListingFeeTokenAccount: splToken.getAssociatedTokenAddressSync(
+        listingFeeTokenMint,
+        this.providerAddress
+      ),
+      feeTreasuryListingFeeTokenAccount: splToken.getAssociatedTokenAddressSync(
+        listingFeeTokenMint,
+        feeTreasury,
+        true
+      ),
+    };
+  }
+
+  private createRemainingAccountsForRevenueReceiverBlocklist(
+    revenueShareConfigs: RevenueShareConfig[],
+  ): AccountMeta[] {
+    return revenueShareConfigs.map((config: RevenueShareConfig) => ({
+      pubkey: this.findPdaWalletBlocklistStates(config.revenueReceiver),
+      isWritable: false,
+      isSigner: false,
+    }));
+  }
+
+  // The creator states is only passed for the listings still counted as live,
+  // which are to be removed from the creator's live listings.
+  private createRemainingAccountsForLiveListing(
+    creator: PublicKey,
+    countedAsLive: boolean,
+  ): AccountMeta[] {
+    return countedAsLive ? [{
+      pubkey: this.findPdaCreatorStates(creator),
+      isWritable: true,
+      isSigner: false,
+    }] : [];
+  }
+
+  // Sends a testing-only instruction, which is dispatched via the fallback of
+  // the program (only in the builds with the "testing" feature), so it's
+  // neither in the IDL nor translated into an AnchorError by Anchor.
+  private async sendTestingInstruction(
+    instructionName: string,
+    args: Buffer,
+    keys: AccountMeta[],
+  ) {
+    const discriminator = Buffer.from(
+      anchor.utils.sha256.hash(`global:${instructionName}`),
+      "hex"
+    ).subarray(0, 8);
+    const transaction = new Transaction().add(new TransactionInstruction({
+      programId: this.programId,
+      keys,
+      data: Buffer.concat([discriminator, args]),
+    }));
+    try {
+      await this.justiesProgram.provider.sendAndConfirm(transaction);
+    } catch (err) {
+      throw (err.logs && anchor.AnchorError.parse(err.logs)) || err;
+    }
+  }
+
   private async* getIndexedKeys(
     totalKeys: BN,
     pageSize: number,
@@ -128,6 +277,27 @@ export class JustiesProgramClient {
     }
   }
 
+  // Iterates the allowlist states. The removed entries are no longer indexed,
+  // while the entries that can't be fetched are skipped.
+  private async* getAllowlistStates(
+    totalKeys: BN,
+    pageSize: number,
+    pdaGetter: (pageId: number) => PublicKey
+  ) {
+    for await (const pdaKey of this.getIndexedKeys(
+      totalKeys,
+      pageSize,
+      pdaGetter,
+    )) {
+      const allowlistStates =
+        await this.justiesProgram.account.tokenAllowlistStates.fetchNullable(
+          pdaKey);
+      if (allowlistStates !== null) {
+        yield allowlistStates;
+      }
+    }
+  }
+
   //////////////////////////////////////////////////////////////////////////////
   // PDA getters;
   //////////////////////////////////////////////////////////////////////////////
@@ -135,10 +305,45 @@ export class JustiesProgramClient {
     return this.findPda("global_states");
   }
 
+  public findPdaAuditLog() {
+    return this.findPda("audit_log");
+  }
+
+  public findPdaPendingConfigs() {
+    return this.findPda("pending_configs");
+  }
+
+  public findPdaMultisigAuthority() {
+    return this.findPda("multisig_authority");
+  }
+
+  public findPdaAdminProposal(id: anchor.BN) {
+    return this.findPda("admin_proposal", id);
+  }
+
   public findPdaTokenAllowlistStates(tokenMintAddress: PublicKey) {
     return this.findPda("token_allowlist_states", tokenMintAddress);
   }
 
+  public findPdaWalletBlocklistStates(walletAddress: PublicKey) {
+    return this.findPda("wallet_blocklist_states", walletAddress);
+  }
+
+  public findPdaCreatorStates(creatorAddress: PublicKey) {
+    return this.findPda("creator_states", creatorAddress);
+  }
+
+  public findPdaCurrencyStats(tokenMintAddress: PublicKey) {
+    return this.findPda("currency_stats", tokenMintAddress);
+  }
+
+  public findPdaCreatorVolume(
+    creatorAddress: PublicKey,
+    tokenMintAddress: PublicKey
+  ) {
+    return this.findPda("creator_volume", creatorAddress, tokenMintAddress);
+  }
+
   public findPdaTokenAllowlistIndex(pageId: number) {
     return this.findPda("token_allowlist_index", new BN(pageId));
   }
@@ -151,6 +356,22 @@ export class JustiesProgramClient {
     return this.findPda("nft_allowlist_index", new BN(pageId));
   }
 
+  public findPdaNftMintAllowlistStates(nftMintAddress: PublicKey) {
+    return this.findPda("nft_mint_allowlist_states", nftMintAddress);
+  }
+
+  public findPdaNftMintAllowlistIndex(pageId: number) {
+    return this.findPda("nft_mint_allowlist_index", new BN(pageId));
+  }
+
+  public findPdaNftCreatorAllowlistStates(creatorAddress: PublicKey) {
+    return this.findPda("nft_creator_allowlist_states", creatorAddress);
+  }
+
+  public findPdaNftCreatorAllowlistIndex(pageId: number) {
+    return this.findPda("nft_creator_allowlist_index", new BN(pageId));
+  }
+
   public findPdaAuction(id: anchor.BN) {
     return this.findPda("auction", id);
   }
@@ -232,8 +453,47 @@ export class JustiesProgramClient {
   // Account states fetchers;
   //////////////////////////////////////////////////////////////////////////////
   public async fetchGlobalStates() {
-    return await this.justiesProgram.account.globalStates.fetch(
+    const globalStates = await this.justiesProgram.account.globalStates.fetch(
       this.findPdaGlobalStates());
+    // Version 0 is the layout before the versioning, which is compatible.
+    if (globalStates.majorVersion > GLOBAL_STATES_MAJOR_VERSION) {
+      throw new Error(
+        `Unsupported global states version: ${globalStates.majorVersion}`);
+    }
+    return globalStates;
+  }
+
+  public async fetchCreatorStates(creatorAddress: PublicKey) {
+    return await this.justiesProgram.account.creatorStates.fetch(
+      this.findPdaCreatorStates(creatorAddress));
+  }
+
+  public async fetchCurrencyStats(tokenMintAddress: PublicKey) {
+    return await this.justiesProgram.account.currencyStats.fetchNullable(
+      this.findPdaCurrencyStats(tokenMintAddress));
+  }
+
+  public async fetchCreatorVolume(
+    creatorAddress: PublicKey,
+    tokenMintAddress: PublicKey
+  ) {
+    return await this.justiesProgram.account.creatorVolume.fetchNullable(
+      this.findPdaCreatorVolume(creatorAddress, tokenMintAddress));
+  }
+
+  public async fetchAuditLog() {
+    return await this.justiesProgram.account.auditLog.fetch(
+      this.findPdaAuditLog());
+  }
+
+  public async fetchPendingConfigs() {
+    return await this.justiesProgram.account.pendingConfigs.fetchNullable(
+      this.findPdaPendingConfigs());
+  }
+
+  public async fetchAdminProposal(id: anchor.BN) {
+    return await this.justiesProgram.account.adminProposal.fetch(
+      this.findPdaAdminProposal(id));
   }
 
   public async fetchTokenAllowlistStates(tokenMintAddress: PublicKey) {
@@ -256,6 +516,16 @@ export class JustiesProgramClient {
       this.findPdaNftAllowlistIndex(pageId));
   }
 
+  public async fetchNftMintAllowlistStates(nftMintAddress: PublicKey) {
+    return await this.justiesProgram.account.tokenAllowlistStates.fetchNullable(
+      this.findPdaNftMintAllowlistStates(nftMintAddress));
+  }
+
+  public async fetchNftCreatorAllowlistStates(creatorAddress: PublicKey) {
+    return await this.justiesProgram.account.tokenAllowlistStates.fetchNullable(
+      this.findPdaNftCreatorAllowlistStates(creatorAddress));
+  }
+
   public async fetchLatestAuction() {
     const auctionId = await this.latestAuctionId();
     return await this.fetchAuction(auctionId);
@@ -312,26 +582,38 @@ export class JustiesProgramClient {
   //////////////////////////////////////////////////////////////////////////////
   public async* getTokenAllowlistStates() {
     const globalStates = await this.fetchGlobalStates();
-    for await (const pdaKey of this.getIndexedKeys(
+    yield* this.getAllowlistStates(
       globalStates.totalAllowedCurrencyTokens,
       globalStates.numKeysPerIndexPage,
       this.findPdaTokenAllowlistIndex.bind(this),
-    )) {
-      yield await
-        this.justiesProgram.account.tokenAllowlistStates.fetch(pdaKey);
-    }
+    );
   }
 
   public async* getNftAllowlistStates() {
     const globalStates = await this.fetchGlobalStates();
-    for await (const pdaKey of this.getIndexedKeys(
+    yield* this.getAllowlistStates(
       globalStates.totalAllowedNftCollections,
       globalStates.numKeysPerIndexPage,
       this.findPdaNftAllowlistIndex.bind(this),
-    )) {
-      yield await
-        this.justiesProgram.account.tokenAllowlistStates.fetch(pdaKey);
-    }
+    );
+  }
+
+  public async* getNftMintAllowlistStates() {
+    const globalStates = await this.fetchGlobalStates();
+    yield* this.getAllowlistStates(
+      globalStates.totalAllowedNftMints,
+      globalStates.numKeysPerIndexPage,
+      this.findPdaNftMintAllowlistIndex.bind(this),
+    );
+  }
+
+  public async* getNftCreatorAllowlistStates() {
+    const globalStates = await this.fetchGlobalStates();
+    yield* this.getAllowlistStates(
+      globalStates.totalAllowedNftCreators,
+      globalStates.numKeysPerIndexPage,
+      this.findPdaNftCreatorAllowlistIndex.bind(this),
+    );
   }
 
   public async* getAuctionBids(auctionId: BN) {
@@ -382,6 +664,7 @@ export class JustiesProgramClient {
     ).accounts(
       {
         globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
         authority: this.providerAddress,
         systemProgram: BUILTIN_PROGRAMS.SYSTEM,
       }
@@ -394,21 +677,260 @@ export class JustiesProgramClient {
     await this.justiesProgram.methods.updateConfigs(input).accounts(
       {
         globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
         authority: this.providerAddress,
         systemProgram: BUILTIN_PROGRAMS.SYSTEM,
       }
     ).rpc();
   }
 
-  public async setMockTimestamp(timestamp: anchor.BN | null) {
-    await this.justiesProgram.methods.setMockTimestamp(timestamp)
+  public async migrateGlobalStates() {
+    await this.justiesProgram.methods.migrateGlobalStates().accounts(
+      {
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        authority: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+  }
+
+  // Testing-only.
+  public async downgradeGlobalStates() {
+    await this.sendTestingInstruction(
+      "downgrade_global_states",
+      Buffer.alloc(0),
+      [
+        {pubkey: this.findPdaGlobalStates(), isWritable: true, isSigner: false},
+        {pubkey: this.providerAddress, isWritable: true, isSigner: true},
+        {pubkey: BUILTIN_PROGRAMS.SYSTEM, isWritable: false, isSigner: false},
+      ]
+    );
+  }
+
+  public async migrateAllowlistStates(
+    allowlistType: AllowlistType,
+    tokenMintAddress: PublicKey,
+  ) {
+    await this.justiesProgram.methods.migrateAllowlistStates(
+      allowlistType,
+      tokenMintAddress,
+    ).accounts(
+      {
+        allowlistStates: this.findPdaAllowlistStates(
+          allowlistType, tokenMintAddress),
+        payer: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+  }
+
+  public async setFeeDiscounts(feeDiscounts: FeeDiscountConfig[]) {
+    await this.justiesProgram.methods.setFeeDiscounts(feeDiscounts).accounts({
+      globalStates: this.findPdaGlobalStates(),
+      auditLog: this.findPdaAuditLog(),
+      authority: this.providerAddress,
+    }).rpc();
+  }
+
+  public async setFeeTiers(feeTiers: FeeTierConfig[]) {
+    await this.justiesProgram.methods.setFeeTiers(feeTiers).accounts({
+      globalStates: this.findPdaGlobalStates(),
+      auditLog: this.findPdaAuditLog(),
+      authority: this.providerAddress,
+    }).rpc();
+  }
+
+  public async setFeatureEnabled(feature: Feature, enabled: boolean) {
+    await this.justiesProgram.methods.setFeatureEnabled(feature, enabled)
       .accounts({
         globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
         authority: this.providerAddress,
         systemProgram: BUILTIN_PROGRAMS.SYSTEM,
       }).rpc();
   }
 
+  public async queueConfigChanges(input: TimelockedConfigsInput) {
+    await this.justiesProgram.methods.queueConfigChanges(input).accounts(
+      {
+        pendingConfigs: this.findPdaPendingConfigs(),
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        authority: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+  }
+
+  public async executeConfigChanges() {
+    const pendingConfigs = await this.fetchPendingConfigs();
+    await this.justiesProgram.methods.executeConfigChanges().accounts(
+      {
+        pendingConfigs: this.findPdaPendingConfigs(),
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        proposer: pendingConfigs.proposer,
+        executor: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+  }
+
+  public async cancelConfigChanges() {
+    const pendingConfigs = await this.fetchPendingConfigs();
+    await this.justiesProgram.methods.cancelConfigChanges().accounts(
+      {
+        pendingConfigs: this.findPdaPendingConfigs(),
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        proposer: pendingConfigs.proposer,
+        authority: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+  }
+
+  public async proposeAuthority(newAuthority: PublicKey) {
+    await this.justiesProgram.methods.proposeAuthority(newAuthority).accounts(
+      {
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        authority: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+  }
+
+  public async acceptAuthority() {
+    await this.justiesProgram.methods.acceptAuthority().accounts(
+      {
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        pendingAuthority: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+  }
+
+  public async cancelAuthorityProposal() {
+    await this.justiesProgram.methods.cancelAuthorityProposal().accounts(
+      {
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        authority: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+  }
+
+  public async grantAdminRole(role: AdminRole, grantee: PublicKey) {
+    await this.justiesProgram.methods.grantAdminRole(role, grantee).accounts(
+      {
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        authority: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+  }
+
+  public async revokeAdminRole(role: AdminRole) {
+    await this.justiesProgram.methods.revokeAdminRole(role).accounts(
+      {
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        authority: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+  }
+
+  public async setMultisig(members: PublicKey[], threshold: number) {
+    await this.justiesProgram.methods.setMultisig(members, threshold).accounts(
+      {
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        authority: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+  }
+
+  // Proposes a justies instruction to be signed by the multisig authority pda.
+  // Returns the id of the created proposal.
+  public async createAdminProposal(instruction: TransactionInstruction) {
+    const proposalId = (await this.fetchGlobalStates()).totalAdminProposals;
+    const accounts: ProposalAccountMeta[] = instruction.keys.map(key => ({
+      pubkey: key.pubkey,
+      isSigner: key.isSigner,
+      isWritable: key.isWritable,
+    }));
+    await this.justiesProgram.methods.createAdminProposal(
+      accounts,
+      instruction.data,
+    ).accounts(
+      {
+        adminProposal: this.findPdaAdminProposal(proposalId),
+        globalStates: this.findPdaGlobalStates(),
+        proposer: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+    return proposalId;
+  }
+
+  public async approveAdminProposal(proposalId: anchor.BN) {
+    await this.justiesProgram.methods.approveAdminProposal(proposalId).accounts(
+      {
+        adminProposal: this.findPdaAdminProposal(proposalId),
+        globalStates: this.findPdaGlobalStates(),
+        approver: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+  }
+
+  public async executeAdminProposal(proposalId: anchor.BN) {
+    const proposal = await this.fetchAdminProposal(proposalId);
+    const multisigAuthority = this.findPdaMultisigAuthority();
+    // The multisig authority pda is signed by the program, so all the other
+    // accounts are passed as non-signers.
+    const remainingAccounts: AccountMeta[] = proposal.accounts
+      .filter(meta => !meta.pubkey.equals(multisigAuthority))
+      .map(meta => ({
+        pubkey: meta.pubkey,
+        isSigner: false,
+        isWritable: meta.isWritable,
+      }));
+    await this.justiesProgram.methods.executeAdminProposal(proposalId).accounts(
+      {
+        adminProposal: this.findPdaAdminProposal(proposalId),
+        globalStates: this.findPdaGlobalStates(),
+        multisigAuthority,
+        executor: this.providerAddress,
+        justiesProgram: this.programId,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).remainingAccounts(remainingAccounts).rpc();
+  }
+
+  // Testing-only.
+  public async setMockTimestamp(timestamp: anchor.BN | null) {
+    // Borsh-serialized Option<i64>.
+    const args = timestamp === null
+      ? Buffer.from([0])
+      : Buffer.concat([
+        Buffer.from([1]),
+        timestamp.toTwos(64).toArrayLike(Buffer, "le", 8),
+      ]);
+    await this.sendTestingInstruction("set_mock_timestamp", args, [
+      {pubkey: this.findPdaGlobalStates(), isWritable: true, isSigner: false},
+      {pubkey: this.providerAddress, isWritable: false, isSigner: true},
+      {pubkey: BUILTIN_PROGRAMS.SYSTEM, isWritable: false, isSigner: false},
+    ]);
+  }
+
   public async clearMockTimestamp() {
     await this.setMockTimestamp(null);
   }
@@ -435,6 +957,7 @@ export class JustiesProgramClient {
         authority: this.providerAddress,
         feeTreasury: globalStates.feeTreasuryAddress,
         globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
         currencyTokenMint: tokenMintAddress,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -460,6 +983,7 @@ export class JustiesProgramClient {
         nftAllowlistIndex: this.findPdaNftAllowlistIndex(indexPageId),
         authority: this.providerAddress,
         globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
         nftCollectionMint: collectionMintAddress,
         collectionMetadata: metadataPda,
         justiesProgram: this.programId,
@@ -467,21 +991,435 @@ export class JustiesProgramClient {
       }).rpc();
   }
 
-  // This method assumes the nft token account is an ATA.
-  public async createAuction(
-    nftMint: PublicKey,
-    currencyTokenMint: PublicKey,
-    duration: number,
-    startBid: number,
-    eligibleGroups: GroupConfig[],
-    revenueShares: RevenueShareConfig[],
-    creatorNftAccount?: PublicKey,
-  ) {
+  public async addNftMintToAllowlist(nftMintAddress: PublicKey, indexPageId?: number) {
     const globalStates = await this.fetchGlobalStates();
-    const auctionId = globalStates.totalAuctions;
-    const auctionAddress = this.findPdaAuction(auctionId);
+    if (indexPageId === undefined) {
+      indexPageId = JustiesProgramClient.getPubkeyIndexPageId(
+        globalStates.totalAllowedNftMints,
+        globalStates.numKeysPerIndexPage
+      );
+    }
+    await this.justiesProgram.methods.addNftMintToAllowlist(nftMintAddress)
+      .accounts({
+        nftMintAllowlistStates: this.findPdaNftMintAllowlistStates(
+          nftMintAddress),
+        nftMintAllowlistIndex: this.findPdaNftMintAllowlistIndex(indexPageId),
+        authority: this.providerAddress,
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        nftMint: nftMintAddress,
+        nftMetadata: findPdaTokenMetadata(nftMintAddress),
+        justiesProgram: this.programId,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }).rpc();
+  }
+
+  public async addNftCreatorToAllowlist(creatorAddress: PublicKey, indexPageId?: number) {
+    const globalStates = await this.fetchGlobalStates();
+    if (indexPageId === undefined) {
+      indexPageId = JustiesProgramClient.getPubkeyIndexPageId(
+        globalStates.totalAllowedNftCreators,
+        globalStates.numKeysPerIndexPage
+      );
+    }
+    await this.justiesProgram.methods.addNftCreatorToAllowlist(creatorAddress)
+      .accounts({
+        nftCreatorAllowlistStates: this.findPdaNftCreatorAllowlistStates(
+          creatorAddress),
+        nftCreatorAllowlistIndex: this.findPdaNftCreatorAllowlistIndex(
+          indexPageId),
+        authority: this.providerAddress,
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        justiesProgram: this.programId,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }).rpc();
+  }
+
+  // Returns the index pages that the next numNewKeys keys are indexed into.
+  private getNextIndexPages(
+    totalKeys: BN,
+    numNewKeys: number,
+    numKeysPerIndexPage: number,
+    findPdaIndexPage: (pageId: number) => PublicKey,
+  ) {
+    const firstPageId = JustiesProgramClient.getPubkeyIndexPageId(
+      totalKeys, numKeysPerIndexPage);
+    const lastPageId = JustiesProgramClient.getPubkeyIndexPageId(
+      totalKeys.addn(Math.max(numNewKeys - 1, 0)), numKeysPerIndexPage);
+    const indexPages = [];
+    for (let pageId = firstPageId; pageId <= lastPageId; pageId++) {
+      indexPages.push({
+        pubkey: findPdaIndexPage(pageId),
+        isWritable: true,
+        isSigner: false,
+      });
+    }
+    return indexPages;
+  }
+
+  public async batchAddCurrencyTokensToAllowlist(
+    tokenMintAddresses: PublicKey[]
+  ) {
+    const globalStates = await this.fetchGlobalStates();
+    const remainingAccounts = [];
+    for (const tokenMintAddress of tokenMintAddresses) {
+      remainingAccounts.push(
+        {
+          pubkey: this.findPdaTokenAllowlistStates(tokenMintAddress),
+          isWritable: true,
+          isSigner: false,
+        },
+        {pubkey: tokenMintAddress, isWritable: false, isSigner: false},
+        {
+          pubkey: splToken.getAssociatedTokenAddressSync(
+            tokenMintAddress,
+            globalStates.feeTreasuryAddress
+          ),
+          isWritable: true,
+          isSigner: false,
+        },
+      );
+    }
+    remainingAccounts.push(...this.getNextIndexPages(
+      globalStates.totalAllowedCurrencyTokens,
+      tokenMintAddresses.length,
+      globalStates.numKeysPerIndexPage,
+      this.findPdaTokenAllowlistIndex.bind(this),
+    ));
+    await this.justiesProgram.methods.batchAddCurrencyTokensToAllowlist(
+      tokenMintAddresses).accounts(
+      {
+        authority: this.providerAddress,
+        feeTreasury: globalStates.feeTreasuryAddress,
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        justiesProgram: this.programId,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }).remainingAccounts(remainingAccounts).rpc();
+  }
+
+  public async batchAddNftCollectionsToAllowlist(
+    collectionMintAddresses: PublicKey[]
+  ) {
+    const globalStates = await this.fetchGlobalStates();
+    const remainingAccounts = [];
+    for (const collectionMintAddress of collectionMintAddresses) {
+      remainingAccounts.push(
+        {
+          pubkey: this.findPdaNftAllowlistStates(collectionMintAddress),
+          isWritable: true,
+          isSigner: false,
+        },
+        {pubkey: collectionMintAddress, isWritable: false, isSigner: false},
+        {
+          pubkey: findPdaTokenMetadata(collectionMintAddress),
+          isWritable: false,
+          isSigner: false,
+        },
+      );
+    }
+    remainingAccounts.push(...this.getNextIndexPages(
+      globalStates.totalAllowedNftCollections,
+      collectionMintAddresses.length,
+      globalStates.numKeysPerIndexPage,
+      this.findPdaNftAllowlistIndex.bind(this),
+    ));
+    await this.justiesProgram.methods.batchAddNftCollectionsToAllowlist(
+      collectionMintAddresses).accounts(
+      {
+        authority: this.providerAddress,
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        justiesProgram: this.programId,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }).remainingAccounts(remainingAccounts).rpc();
+  }
+
+  private findPdaAllowlistStates(
+    allowlistType: AllowlistType,
+    tokenMintAddress: PublicKey
+  ) {
+    if ("currencyToken" in allowlistType) {
+      return this.findPdaTokenAllowlistStates(tokenMintAddress);
+    }
+    if ("nftMint" in allowlistType) {
+      return this.findPdaNftMintAllowlistStates(tokenMintAddress);
+    }
+    if ("nftCreator" in allowlistType) {
+      return this.findPdaNftCreatorAllowlistStates(tokenMintAddress);
+    }
+    return this.findPdaNftAllowlistStates(tokenMintAddress);
+  }
+
+  private findPdaAllowlistIndex(allowlistType: AllowlistType, pageId: number) {
+    if ("currencyToken" in allowlistType) {
+      return this.findPdaTokenAllowlistIndex(pageId);
+    }
+    if ("nftMint" in allowlistType) {
+      return this.findPdaNftMintAllowlistIndex(pageId);
+    }
+    if ("nftCreator" in allowlistType) {
+      return this.findPdaNftCreatorAllowlistIndex(pageId);
+    }
+    return this.findPdaNftAllowlistIndex(pageId);
+  }
+
+  private static getTotalAllowed(
+    globalStates: GlobalStates,
+    allowlistType: AllowlistType,
+  ): BN {
+    if ("currencyToken" in allowlistType) {
+      return globalStates.totalAllowedCurrencyTokens;
+    }
+    if ("nftMint" in allowlistType) {
+      return globalStates.totalAllowedNftMints;
+    }
+    if ("nftCreator" in allowlistType) {
+      return globalStates.totalAllowedNftCreators;
+    }
+    return globalStates.totalAllowedNftCollections;
+  }
+
+  // Finds the id of the index page holding the allowlist states key.
+  private async findAllowlistIndexPageId(
+    globalStates: GlobalStates,
+    allowlistType: AllowlistType,
+    allowlistStatesAddress: PublicKey,
+  ) {
+    const pageSize = globalStates.numKeysPerIndexPage;
+    let currentIdx = 0;
+    for await (const pdaKey of this.getIndexedKeys(
+      JustiesProgramClient.getTotalAllowed(globalStates, allowlistType),
+      pageSize,
+      (pageId: number) => this.findPdaAllowlistIndex(allowlistType, pageId),
+    )) {
+      if (pdaKey.equals(allowlistStatesAddress)) {
+        return JustiesProgramClient.getPubkeyIndexPageId(
+          new BN(currentIdx), pageSize);
+      }
+      ++currentIdx;
+    }
+    throw new Error("The allowlist states key is not indexed");
+  }
+
+  // Finds the allowlist states that the NFT can be listed under, in the order
+  // of: the verified collection, the NFT mint and the verified creators. Falls
+  // back to the collection (or the mint) pda when none exists.
+  private async findNftAllowlistStates(nftMint: PublicKey) {
     const nftMetadata = await this.metaplex.nfts()
       .findByMint({mintAddress: nftMint});
+    const collection = nftMetadata.collection?.verified ?
+      nftMetadata.collection.address : null;
+    if (collection !== null &&
+      await this.fetchNftAllowlistStates(collection) !== null) {
+      return this.findPdaNftAllowlistStates(collection);
+    }
+    if (await this.fetchNftMintAllowlistStates(nftMint) !== null) {
+      return this.findPdaNftMintAllowlistStates(nftMint);
+    }
+    for (const creator of nftMetadata.creators) {
+      if (creator.verified &&
+        await this.fetchNftCreatorAllowlistStates(creator.address) !== null) {
+        return this.findPdaNftCreatorAllowlistStates(creator.address);
+      }
+    }
+    return collection !== null ?
+      this.findPdaNftAllowlistStates(collection) :
+      this.findPdaNftMintAllowlistStates(nftMint);
+  }
+
+  public async setCurrencyListingLimits(
+    tokenMintAddress: PublicKey,
+    listingLimits: CurrencyListingLimits,
+  ) {
+    await this.justiesProgram.methods.setCurrencyListingLimits(
+      tokenMintAddress,
+      listingLimits,
+    ).accounts(
+      {
+        tokenAllowlistStates: this.findPdaTokenAllowlistStates(
+          tokenMintAddress),
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        authority: this.providerAddress,
+      }
+    ).rpc();
+  }
+
+  public async setCurrencyMarketFeeRate(
+    tokenMintAddress: PublicKey,
+    marketFeeRateBps: number | null,
+  ) {
+    await this.justiesProgram.methods.setCurrencyMarketFeeRate(
+      tokenMintAddress,
+      marketFeeRateBps,
+    ).accounts(
+      {
+        tokenAllowlistStates: this.findPdaTokenAllowlistStates(
+          tokenMintAddress),
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        authority: this.providerAddress,
+      }
+    ).rpc();
+  }
+
+  public async setNftCollectionListingRules(
+    collectionMintAddress: PublicKey,
+    listingRules: ListingRuleOverrides,
+  ) {
+    await this.justiesProgram.methods.setNftCollectionListingRules(
+      collectionMintAddress,
+      listingRules,
+    ).accounts(
+      {
+        nftAllowlistStates: this.findPdaNftAllowlistStates(
+          collectionMintAddress),
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        authority: this.providerAddress,
+      }
+    ).rpc();
+  }
+
+  public async suspendAllowlistEntry(
+    allowlistType: AllowlistType,
+    tokenMintAddress: PublicKey,
+    reasonCode: number | null,
+  ) {
+    await this.justiesProgram.methods.suspendAllowlistEntry(
+      allowlistType,
+      tokenMintAddress,
+      reasonCode,
+    ).accounts(
+      {
+        allowlistStates: this.findPdaAllowlistStates(
+          allowlistType, tokenMintAddress),
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        authority: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+  }
+
+  public async reinstateAllowlistEntry(
+    allowlistType: AllowlistType,
+    tokenMintAddress: PublicKey,
+  ) {
+    await this.justiesProgram.methods.reinstateAllowlistEntry(
+      allowlistType,
+      tokenMintAddress,
+    ).accounts(
+      {
+        allowlistStates: this.findPdaAllowlistStates(
+          allowlistType, tokenMintAddress),
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        authority: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+  }
+
+  public async removeAllowlistEntry(
+    allowlistType: AllowlistType,
+    tokenMintAddress: PublicKey,
+    reasonCode: number | null,
+  ) {
+    const globalStates = await this.fetchGlobalStates();
+    const allowlistStates = this.findPdaAllowlistStates(
+      allowlistType, tokenMintAddress);
+    const indexPageId = await this.findAllowlistIndexPageId(
+      globalStates, allowlistType, allowlistStates);
+    const lastIndexPageId = JustiesProgramClient.getPubkeyIndexPageId(
+      JustiesProgramClient.getTotalAllowed(globalStates, allowlistType).subn(1),
+      globalStates.numKeysPerIndexPage);
+    await this.justiesProgram.methods.removeAllowlistEntry(
+      allowlistType,
+      tokenMintAddress,
+      reasonCode,
+      new BN(indexPageId),
+    ).accounts(
+      {
+        allowlistStates,
+        indexPage: this.findPdaAllowlistIndex(allowlistType, indexPageId),
+        lastIndexPage: this.findPdaAllowlistIndex(
+          allowlistType, lastIndexPageId),
+        globalStates: this.findPdaGlobalStates(),
+        auditLog: this.findPdaAuditLog(),
+        authority: this.providerAddress,
+        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+      }
+    ).rpc();
+  }
+
+  public async blockWallet(
+    walletAddress: PublicKey,
+    reasonCode: number | null,
+  ) {
+    await this.justiesProgram.methods.blockWallet(
+      walletAddress,
+      reasonCode,
+    ).accounts({
+      walletBlocklistStates: this.findPdaWalletBlocklistStates(walletAddress),
+      globalStates: this.findPdaGlobalStates(),
+      auditLog: this.findPdaAuditLog(),
+      authority: this.providerAddress,
+      systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+    }).rpc();
+  }
+
+  public async setCuratedCreator(
+    creatorAddress: PublicKey,
+    curated: boolean,
+    maxLiveListings: number | null,
+  ) {
+    await this.justiesProgram.methods.setCuratedCreator(
+      creatorAddress,
+      curated,
+      maxLiveListings,
+    ).accounts({
+      creatorStates: this.findPdaCreatorStates(creatorAddress),
+      globalStates: this.findPdaGlobalStates(),
+      auditLog: this.findPdaAuditLog(),
+      authority: this.providerAddress,
+      systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+    }).rpc();
+  }
+
+  public async unblockWallet(
+    walletAddress: PublicKey,
+    reasonCode: number | null,
+  ) {
+    await this.justiesProgram.methods.unblockWallet(
+      walletAddress,
+      reasonCode,
+    ).accounts({
+      walletBlocklistStates: this.findPdaWalletBlocklistStates(walletAddress),
+      globalStates: this.findPdaGlobalStates(),
+      auditLog: this.findPdaAuditLog(),
+      authority: this.providerAddress,
+      systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+    }).rpc();
+  }
+
+  // This method assumes the nft token account is an ATA.
+  public async createAuction(
+    nftMint: PublicKey,
+    currencyTokenMint: PublicKey,
+    duration: number,
+    startBid: number,
+    eligibleGroups: GroupConfig[],
+    revenueShares: RevenueShareConfig[],
+    creatorNftAccount?: PublicKey,
+  ) {
+    const globalStates = await this.fetchGlobalStates();
+    const auctionId = globalStates.totalAuctions;
+    const auctionAddress = this.findPdaAuction(auctionId);
 
     if (creatorNftAccount === undefined) {
       creatorNftAccount = splToken.getAssociatedTokenAddressSync(
@@ -501,23 +1439,34 @@ export class JustiesProgramClient {
       nftMint: nftMint,
       currencyTokenMint: currencyTokenMint,
       creator: this.providerAddress,
+      creatorBlocklistStates: this.findPdaWalletBlocklistStates(
+        this.providerAddress),
+      creatorStates: this.findPdaCreatorStates(this.providerAddress),
       lotEscrowNftAccount: this.findPdaLotEscrow(auctionAddress),
       creatorNftAccount: creatorNftAccount,
       nftMetadata: findPdaTokenMetadata(nftMint),
       globalStates: this.findPdaGlobalStates(),
-      nftAllowlistStates: this.findPdaNftAllowlistStates(nftMetadata.collection.address),
+      nftAllowlistStates: await this.findNftAllowlistStates(nftMint),
       tokenAllowlistStates: this.findPdaTokenAllowlistStates(currencyTokenMint),
+      ...this.createListingFeeAccounts(
+        globalStates.feeTreasuryAddress,
+        globalStates.listingFeeTokenMint
+      ),
       tokenProgram: TOKEN_PROGRAM_ID,
       systemProgram: BUILTIN_PROGRAMS.SYSTEM,
-    }).rpc();
+    }).remainingAccounts(
+      this.createRemainingAccountsForRevenueReceiverBlocklist(revenueShares)
+    ).rpc();
   }
 
   public async cancelAuction(auctionId: anchor.BN) {
+    const globalStates = await this.fetchGlobalStates();
     const auction = await this.fetchAuction(auctionId);
     const auctionAddress = await this.findPdaAuction(auctionId);
     await this.justiesProgram.methods.cancelAuction(auctionId).accounts({
       auction: auctionAddress,
       creator: this.providerAddress,
+      creatorStates: this.findPdaCreatorStates(this.providerAddress),
       nftMint: auction.nftMintAddress,
       lotEscrowNftAccount: this.findPdaLotEscrow(auctionAddress),
       creatorNftAccount: splToken.getAssociatedTokenAddressSync(
@@ -525,6 +1474,7 @@ export class JustiesProgramClient {
         this.providerAddress
       ),
       globalStates: this.findPdaGlobalStates(),
+      feeTreasury: globalStates.feeTreasuryAddress,
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: BUILTIN_PROGRAMS.SYSTEM,
@@ -532,6 +1482,45 @@ export class JustiesProgramClient {
       .rpc();
   }
 
+  public async forceCancelAuction(
+    auctionId: anchor.BN,
+    nftRecipient: PublicKey,
+    reasonCode: number | null
+  ) {
+    const globalStates = await this.fetchGlobalStates();
+    const auction = await this.fetchAuction(auctionId);
+    const auctionAddress = this.findPdaAuction(auctionId);
+    // The top bid escrow is only checked when there is a top bidder.
+    const topBidEscrowTokenAccount = auction.topBidder === null
+      ? BUILTIN_PROGRAMS.SYSTEM
+      : this.findPdaBidEscrow(auctionAddress, auction.topBidder);
+    await this.justiesProgram.methods.forceCancelAuction(
+      auctionId,
+      reasonCode
+    ).accounts({
+      auction: auctionAddress,
+      authority: this.providerAddress,
+      creator: auction.creator,
+      nftRecipient: nftRecipient,
+      nftMint: auction.nftMintAddress,
+      lotEscrowNftAccount: this.findPdaLotEscrow(auctionAddress),
+      nftRecipientNftAccount: splToken.getAssociatedTokenAddressSync(
+        auction.nftMintAddress,
+        nftRecipient
+      ),
+      topBidEscrowTokenAccount: topBidEscrowTokenAccount,
+      globalStates: this.findPdaGlobalStates(),
+      feeTreasury: globalStates.feeTreasuryAddress,
+      auditLog: this.findPdaAuditLog(),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+    }).remainingAccounts(this.createRemainingAccountsForLiveListing(
+      auction.creator,
+      auction.countedAsLive
+    )).rpc();
+  }
+
   public async makeBid(
     auctionId: anchor.BN,
     bidAmount: number,
@@ -574,6 +1563,7 @@ export class JustiesProgramClient {
       bidIndex: this.findPdaBidsIndexPage(auctionAddress, indexPageId),
       auction: auctionAddress,
       bidder: bidderAddress,
+      bidderBlocklistStates: this.findPdaWalletBlocklistStates(bidderAddress),
       bidEscrowTokenAccount: this.findPdaBidEscrow(
         auctionAddress,
         bidderAddress
@@ -581,6 +1571,10 @@ export class JustiesProgramClient {
       bidderTokenAccount: bidderTokenAccount,
       currencyTokenMint: currencyTokenMintAddress,
       globalStates: this.findPdaGlobalStates(),
+      tokenAllowlistStates: this.findPdaTokenAllowlistStates(
+        auction.currencyTokenMintAddress),
+      nftAllowlistStates: this.findPdaAllowlistStates(
+        auction.nftAllowlistType, auction.nftAllowlistKey),
       justiesProgram: this.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
       systemProgram: BUILTIN_PROGRAMS.SYSTEM,
@@ -607,7 +1601,10 @@ export class JustiesProgramClient {
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: BUILTIN_PROGRAMS.SYSTEM,
-    }).rpc();
+    }).remainingAccounts(this.createRemainingAccountsForLiveListing(
+      auction.creator,
+      auction.countedAsLive
+    )).rpc();
   }
 
   public async cancelAuctionBid(auctionId: anchor.BN) {
@@ -632,10 +1629,45 @@ export class JustiesProgramClient {
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+    }).remainingAccounts(this.createRemainingAccountsForLiveListing(
+      auction.creator,
+      auction.countedAsLive
+    )).rpc();
+  }
+
+  // The lot NFT mint is passed in, as an auction of an older layout may not be
+  // fetched before it's migrated.
+  public async migrateAuction(auctionId: BN, nftMintAddress: PublicKey) {
+    await this.justiesProgram.methods.migrateAuction(auctionId).accounts({
+      auction: this.findPdaAuction(auctionId),
+      nftMetadata: findPdaTokenMetadata(nftMintAddress),
+      payer: this.providerAddress,
+      systemProgram: BUILTIN_PROGRAMS.SYSTEM,
     }).rpc();
   }
 
-  public async claimAuctionRevenue(auctionId: anchor.BN) {
+  // Testing-only.
+  public async downgradeAuction(auctionId: BN) {
+    const args = auctionId.toArrayLike(Buffer, "le", 8);
+    await this.sendTestingInstruction("downgrade_auction", args, [
+      {
+        pubkey: this.findPdaAuction(auctionId),
+        isWritable: true,
+        isSigner: false,
+      },
+      {pubkey: this.findPdaGlobalStates(), isWritable: false, isSigner: false},
+      {pubkey: this.providerAddress, isWritable: true, isSigner: true},
+      {pubkey: BUILTIN_PROGRAMS.SYSTEM, isWritable: false, isSigner: false},
+    ]);
+  }
+
+  // The account payloads are only needed for the eligibility check of the fee
+  // discount.
+  public async claimAuctionRevenue(
+    auctionId: anchor.BN,
+    eligibilityCheckInput: EligibilityCheckInput | null = null,
+    accountPayloads: AccountMeta[] = [],
+  ) {
     const auctionAddress = this.findPdaAuction(auctionId);
     const auction = await this.fetchAuction(auctionId);
     const globalStates = await this.fetchGlobalStates();
@@ -643,11 +1675,18 @@ export class JustiesProgramClient {
     const remainingAccounts = this.createRemainingAccountsForRevenueDistribution(
       auction.currencyTokenMintAddress,
       auction.revenueShares,
-    );
-    await this.justiesProgram.methods.claimAuctionRevenue(auctionId).accounts({
+    ).concat(this.createRemainingAccountsForFeeDistribution(
+      auction.currencyTokenMintAddress,
+      globalStates.feeShares,
+    )).concat(accountPayloads);
+    await this.justiesProgram.methods.claimAuctionRevenue(
+      auctionId,
+      eligibilityCheckInput
+    ).accounts({
       auction: auctionAddress,
       topBid: this.findPdaAuctionBid(auctionAddress, topBidderAddress),
       creator: auction.creator,
+      creatorStates: this.findPdaCreatorStates(auction.creator),
       bidEscrowTokenAccount: this.findPdaBidEscrow(
         auctionAddress,
         topBidderAddress
@@ -657,9 +1696,19 @@ export class JustiesProgramClient {
         globalStates.feeTreasuryAddress
       ),
       tokenMint: auction.currencyTokenMintAddress,
+      currencyStats: this.findPdaCurrencyStats(
+        auction.currencyTokenMintAddress),
+      creatorVolume: this.findPdaCreatorVolume(
+        auction.creator,
+        auction.currencyTokenMintAddress
+      ),
       topBidder: topBidderAddress,
       feeTreasury: globalStates.feeTreasuryAddress,
       globalStates: this.findPdaGlobalStates(),
+      tokenAllowlistStates: this.findPdaTokenAllowlistStates(
+        auction.currencyTokenMintAddress),
+      nftAllowlistStates: this.findPdaAllowlistStates(
+        auction.nftAllowlistType, auction.nftAllowlistKey),
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: BUILTIN_PROGRAMS.SYSTEM,
@@ -706,24 +1755,35 @@ export class JustiesProgramClient {
       nftMint: nftMint,
       currencyTokenMint: currencyTokenMint,
       creator: this.providerAddress,
+      creatorBlocklistStates: this.findPdaWalletBlocklistStates(
+        this.providerAddress),
+      creatorStates: this.findPdaCreatorStates(this.providerAddress),
       creatorNftAccount: creatorNftAccount,
       rewardsEscrowNftAccount: this.findPdaRaffleRewardsEscrow(raffleAddress),
       revenueEscrowTokenAccount: this.findPdaRaffleRevenueEscrow(
         raffleAddress),
       nftMetadata: nftMetadata.metadataAddress,
-      nftAllowlistStates: this.findPdaNftAllowlistStates(nftMetadata.collection.address),
+      nftAllowlistStates: await this.findNftAllowlistStates(nftMint),
       tokenAllowlistStates: this.findPdaTokenAllowlistStates(currencyTokenMint),
+      ...this.createListingFeeAccounts(
+        globalStates.feeTreasuryAddress,
+        globalStates.listingFeeTokenMint
+      ),
       tokenProgram: TOKEN_PROGRAM_ID,
       systemProgram: BUILTIN_PROGRAMS.SYSTEM,
-    }).rpc();
+    }).remainingAccounts(
+      this.createRemainingAccountsForRevenueReceiverBlocklist(revenueShares)
+    ).rpc();
   }
 
   public async cancelRaffle(raffleId: BN) {
+    const globalStates = await this.fetchGlobalStates();
     const raffle = await this.fetchRaffle(raffleId);
     const raffleAddress = await this.findPdaRaffle(raffleId);
     await this.justiesProgram.methods.cancelRaffle(raffleId).accounts({
       raffle: raffleAddress,
       creator: this.providerAddress,
+      creatorStates: this.findPdaCreatorStates(this.providerAddress),
       nftMint: raffle.nftMintAddress,
       currencyTokenMint: raffle.currencyTokenMintAddress,
       rewardsEscrowNftAccount: this.findPdaRaffleRewardsEscrow(raffleAddress),
@@ -733,6 +1793,67 @@ export class JustiesProgramClient {
         this.providerAddress
       ),
       globalStates: this.findPdaGlobalStates(),
+      feeTreasury: globalStates.feeTreasuryAddress,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+    }).rpc();
+  }
+
+  public async forceCancelRaffle(
+    raffleId: BN,
+    nftRecipient: PublicKey,
+    reasonCode: number | null
+  ) {
+    const globalStates = await this.fetchGlobalStates();
+    const raffle = await this.fetchRaffle(raffleId);
+    const raffleAddress = this.findPdaRaffle(raffleId);
+    await this.justiesProgram.methods.forceCancelRaffle(
+      raffleId,
+      reasonCode
+    ).accounts({
+      raffle: raffleAddress,
+      authority: this.providerAddress,
+      creator: raffle.creator,
+      nftRecipient: nftRecipient,
+      nftMint: raffle.nftMintAddress,
+      currencyTokenMint: raffle.currencyTokenMintAddress,
+      rewardsEscrowNftAccount: this.findPdaRaffleRewardsEscrow(raffleAddress),
+      revenueEscrowTokenAccount: this.findPdaRaffleRevenueEscrow(raffleAddress),
+      nftRecipientNftAccount: splToken.getAssociatedTokenAddressSync(
+        raffle.nftMintAddress,
+        nftRecipient
+      ),
+      globalStates: this.findPdaGlobalStates(),
+      feeTreasury: globalStates.feeTreasuryAddress,
+      auditLog: this.findPdaAuditLog(),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+    }).remainingAccounts(this.createRemainingAccountsForLiveListing(
+      raffle.creator,
+      raffle.countedAsLive
+    )).rpc();
+  }
+
+  public async refundRaffleTickets(raffleId: BN) {
+    const raffle = await this.fetchRaffle(raffleId);
+    const raffleAddress = this.findPdaRaffle(raffleId);
+    await this.justiesProgram.methods.refundRaffleTickets(raffleId).accounts({
+      raffle: raffleAddress,
+      globalStates: this.findPdaGlobalStates(),
+      buyer: this.providerAddress,
+      ticketPosition: this.findPdaRaffleTicketPosition(
+        raffleAddress,
+        this.providerAddress
+      ),
+      revenueEscrowTokenAccount: this.findPdaRaffleRevenueEscrow(raffleAddress),
+      buyerTokenAccount: splToken.getAssociatedTokenAddressSync(
+        raffle.currencyTokenMintAddress,
+        this.providerAddress
+      ),
+      currencyTokenMint: raffle.currencyTokenMintAddress,
+      creator: raffle.creator,
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: BUILTIN_PROGRAMS.SYSTEM,
@@ -771,7 +1892,13 @@ export class JustiesProgramClient {
     ).accounts({
       raffle: raffleAddress,
       globalStates: this.findPdaGlobalStates(),
+      tokenAllowlistStates: this.findPdaTokenAllowlistStates(
+        raffle.currencyTokenMintAddress),
+      nftAllowlistStates: this.findPdaAllowlistStates(
+        raffle.nftAllowlistType, raffle.nftAllowlistKey),
       buyer: this.providerAddress,
+      buyerBlocklistStates: this.findPdaWalletBlocklistStates(
+        this.providerAddress),
       currencyTokenMint: raffle.currencyTokenMintAddress,
       revenueEscrowTokenAccount: this.findPdaRaffleRevenueEscrow(
         raffleAddress),
@@ -820,17 +1947,33 @@ export class JustiesProgramClient {
     }).rpc();
   }
 
-  public async claimRaffleRevenue(raffleId: BN) {
+  // The account payloads are only needed for the eligibility check of the fee
+  // discount.
+  public async claimRaffleRevenue(
+    raffleId: BN,
+    eligibilityCheckInput: EligibilityCheckInput | null = null,
+    accountPayloads: AccountMeta[] = [],
+  ) {
     const raffleAddress = this.findPdaRaffle(raffleId);
     const raffle = await this.fetchRaffle(raffleId);
     const globalStates = await this.fetchGlobalStates();
     const remainingAccounts = this.createRemainingAccountsForRevenueDistribution(
       raffle.currencyTokenMintAddress,
       raffle.revenueShares,
-    );
-    await this.justiesProgram.methods.claimRaffleRevenue(raffleId).accounts({
+    ).concat(this.createRemainingAccountsForFeeDistribution(
+      raffle.currencyTokenMintAddress,
+      globalStates.feeShares,
+    )).concat(accountPayloads);
+    await this.justiesProgram.methods.claimRaffleRevenue(
+      raffleId,
+      eligibilityCheckInput
+    ).accounts({
       raffle: raffleAddress,
       globalStates: this.findPdaGlobalStates(),
+      tokenAllowlistStates: this.findPdaTokenAllowlistStates(
+        raffle.currencyTokenMintAddress),
+      nftAllowlistStates: this.findPdaAllowlistStates(
+        raffle.nftAllowlistType, raffle.nftAllowlistKey),
       creator: this.providerAddress,
       revenueEscrowTokenAccount: this.findPdaRaffleRevenueEscrow(
         raffleAddress),
@@ -840,31 +1983,72 @@ export class JustiesProgramClient {
       ),
       feeTreasury: globalStates.feeTreasuryAddress,
       tokenMint: raffle.currencyTokenMintAddress,
+      currencyStats: this.findPdaCurrencyStats(raffle.currencyTokenMintAddress),
+      creatorVolume: this.findPdaCreatorVolume(
+        this.providerAddress,
+        raffle.currencyTokenMintAddress
+      ),
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: BUILTIN_PROGRAMS.SYSTEM,
     }).remainingAccounts(remainingAccounts).rpc();
   }
 
+  // The reward NFT mint is passed in, as a raffle of an older layout may not be
+  // fetched before it's migrated.
+  public async migrateRaffle(raffleId: BN, nftMintAddress: PublicKey) {
+    await this.justiesProgram.methods.migrateRaffle(raffleId).accounts({
+      raffle: this.findPdaRaffle(raffleId),
+      nftMetadata: findPdaTokenMetadata(nftMintAddress),
+      payer: this.providerAddress,
+      systemProgram: BUILTIN_PROGRAMS.SYSTEM,
+    }).rpc();
+  }
+
+  // Testing-only.
+  public async downgradeRaffle(raffleId: BN) {
+    const args = raffleId.toArrayLike(Buffer, "le", 8);
+    await this.sendTestingInstruction("downgrade_raffle", args, [
+      {pubkey: this.findPdaRaffle(raffleId), isWritable: true, isSigner: false},
+      {pubkey: this.findPdaGlobalStates(), isWritable: false, isSigner: false},
+      {pubkey: this.providerAddress, isWritable: true, isSigner: true},
+      {pubkey: BUILTIN_PROGRAMS.SYSTEM, isWritable: false, isSigner: false},
+    ]);
+  }
+
+  // Testing-only.
   public async setRaffleWinners(raffleId: BN, winners: number[]) {
-    await this.justiesProgram.methods.setRaffleWinners(raffleId, winners)
-      .accounts({
-        raffle: this.findPdaRaffle(raffleId),
-        globalStates: this.findPdaGlobalStates(),
-        authority: this.providerAddress,
-        systemProgram: BUILTIN_PROGRAMS.SYSTEM,
-      }).rpc();
+    const raffle = await this.fetchRaffle(raffleId);
+    // Borsh-serialized (u64, Vec<u16>).
+    const args = Buffer.alloc(8 + 4 + 2 * winners.length);
+    raffleId.toArrayLike(Buffer, "le", 8).copy(args);
+    args.writeUInt32LE(winners.length, 8);
+    winners.forEach((winnerId, i) => args.writeUInt16LE(winnerId, 12 + 2 * i));
+    await this.sendTestingInstruction("set_raffle_winners", args, [
+      {pubkey: this.findPdaRaffle(raffleId), isWritable: true, isSigner: false},
+      {pubkey: this.findPdaGlobalStates(), isWritable: false, isSigner: false},
+      {pubkey: this.providerAddress, isWritable: false, isSigner: true},
+      {pubkey: BUILTIN_PROGRAMS.SYSTEM, isWritable: false, isSigner: false},
+      ...this.createRemainingAccountsForLiveListing(
+        raffle.creator,
+        raffle.countedAsLive
+      ),
+    ]);
   }
 
   public async makeRaffle(raffleId: BN, rerun: boolean) {
     const raffleAddress = this.findPdaRaffle(raffleId);
+    const raffle = await this.fetchRaffle(raffleId);
     await this.justiesProgram.methods.makeRaffle(raffleId, rerun).accounts({
       raffle: this.findPdaRaffle(raffleId),
       ticketPositionStats: this.findPdaRaffleTicketPositionStats(raffleAddress),
       globalStates: this.findPdaGlobalStates(),
       authority: this.providerAddress,
       systemProgram: BUILTIN_PROGRAMS.SYSTEM,
-    }).rpc();
+    }).remainingAccounts(this.createRemainingAccountsForLiveListing(
+      raffle.creator,
+      raffle.countedAsLive
+    )).rpc();
   }
 
   public async claimRemainingRaffleRewards(raffleId: BN) {
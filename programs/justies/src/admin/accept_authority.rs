@@ -0,0 +1,54 @@
+use crate::admin::{AuditField, AuditLog, ConfigChange, GlobalStates};
+use crate::common::{get_current_timestamp, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Accepts the authority role.
+///
+/// This is the 2nd step of the two-step authority transfer. Once confirmed, the
+/// signer becomes the authority and all the admin instructions are only
+/// callable by it, while the previous authority loses its access immediately.
+///
+/// Only callable by the pending authority.
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.pending_authority == Some(pending_authority.key()) @JustiesErrorCode::NotThePendingAuthority,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub pending_authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+  let current_timestamp = get_current_timestamp(&ctx.accounts.global_states);
+  let global_states = &mut ctx.accounts.global_states;
+  let new_authority = ctx.accounts.pending_authority.key();
+  let changes = vec![
+    ConfigChange::new(
+      AuditField::Authority,
+      global_states.authority,
+      new_authority,
+    ),
+    ConfigChange::new(
+      AuditField::PendingAuthority,
+      global_states.pending_authority,
+      None,
+    ),
+  ];
+  global_states.authority = new_authority;
+  global_states.pending_authority = None;
+  ctx
+    .accounts
+    .audit_log
+    .record_all(changes, new_authority, current_timestamp);
+  Ok(())
+}
@@ -1,5 +1,10 @@
-use crate::admin::{GlobalStates, TokenAllowlistStates};
-use crate::common::{index_pubkey, JustiesErrorCode, PubkeyIndexPage};
+use crate::admin::{
+  AdminRole, AuditField, AuditLog, ConfigChange, GlobalStates,
+  TokenAllowlistStates,
+};
+use crate::common::{
+  get_current_timestamp, index_pubkey, JustiesErrorCode, PubkeyIndexPage,
+};
 use crate::program::Justies;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
@@ -11,7 +16,7 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 /// The allowlist states can be looked up via token mint address, or iterated
 /// via the token_allowlist_index pda.
 ///
-/// Only callable by the authority.
+/// Only callable by the allowlist curator.
 #[derive(Accounts)]
 #[instruction(token_mint_address: Pubkey)]
 pub struct AddCurrencyTokenToAllowList<'info> {
@@ -51,9 +56,16 @@ pub struct AddCurrencyTokenToAllowList<'info> {
       mut,
       seeds = [b"global_states"],
       bump = global_states.bump,
+      constraint = global_states.has_role(AdminRole::AllowlistCurator, authority.key()) @JustiesErrorCode::MissingAdminRole,
       constraint = global_states.fee_treasury_address == fee_treasury.key() @JustiesErrorCode::InvalidFeeTreasuryAddress,
   )]
   pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
   #[account(
       constraint = currency_token_mint.key() == token_mint_address.key() @JustiesErrorCode::InvalidCurrencyTokenMint,
   )]
@@ -100,5 +112,11 @@ pub fn handler(
   )?;
 
   global_states.total_allowed_currency_tokens += 1;
+  ctx.accounts.audit_log.record(
+    ConfigChange::new(AuditField::CurrencyTokenAllowlist, false, true)
+      .with_target(token_mint_address),
+    ctx.accounts.authority.key(),
+    get_current_timestamp(global_states),
+  );
   Ok(())
 }
@@ -1,5 +1,10 @@
-use crate::admin::{GlobalStates, TokenAllowlistStates};
-use crate::common::{index_pubkey, JustiesErrorCode, PubkeyIndexPage};
+use crate::admin::{
+  AdminRole, AuditField, AuditLog, ConfigChange, GlobalStates,
+  TokenAllowlistStates,
+};
+use crate::common::{
+  get_current_timestamp, index_pubkey, JustiesErrorCode, PubkeyIndexPage,
+};
 use crate::program::Justies;
 use anchor_lang::prelude::*;
 use anchor_spl::metadata::{Metadata, MetadataAccount};
@@ -11,7 +16,7 @@ use anchor_spl::token::Mint;
 /// The allowlist states can be looked up via collection mint address, or
 /// iterated via the nft_allowlist_index pda.
 ///
-/// Only callable by the authority.
+/// Only callable by the allowlist curator.
 #[derive(Accounts)]
 #[instruction(collection_mint_address: Pubkey)]
 pub struct AddNftCollectionToAllowList<'info> {
@@ -42,8 +47,15 @@ pub struct AddNftCollectionToAllowList<'info> {
       mut,
       seeds = [b"global_states"],
       bump = global_states.bump,
+      constraint = global_states.has_role(AdminRole::AllowlistCurator, authority.key()) @JustiesErrorCode::MissingAdminRole,
   )]
   pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
   #[account(
       constraint = nft_collection_mint.key() == collection_mint_address.key() @JustiesErrorCode::InvalidNftCollectionMint,
       constraint = nft_collection_mint.decimals == 0 @JustiesErrorCode::InvalidNftCollectionMint,
@@ -101,5 +113,11 @@ pub fn handler(
   )?;
 
   global_states.total_allowed_nft_collections += 1;
+  ctx.accounts.audit_log.record(
+    ConfigChange::new(AuditField::NftCollectionAllowlist, false, true)
+      .with_target(collection_mint_address),
+    ctx.accounts.authority.key(),
+    get_current_timestamp(global_states),
+  );
   Ok(())
 }
@@ -0,0 +1,111 @@
+use crate::admin::{
+  AdminRole, AuditField, AuditLog, ConfigChange, GlobalStates,
+  TokenAllowlistStates,
+};
+use crate::common::{
+  get_current_timestamp, index_pubkey, JustiesErrorCode, PubkeyIndexPage,
+};
+use crate::program::Justies;
+use anchor_lang::prelude::*;
+
+/// Adds an NFT creator into the allowlist so that the NFTs verified by the
+/// creator can be listed in auctions and raffles. This is for the legacy
+/// collections that are identified by their verified creator rather than a
+/// Metaplex collection.
+///
+/// The allowlist states can be looked up via creator address, or iterated via
+/// the nft_creator_allowlist_index pda.
+///
+/// Only callable by the allowlist curator.
+#[derive(Accounts)]
+#[instruction(creator_address: Pubkey)]
+pub struct AddNftCreatorToAllowList<'info> {
+  #[account(
+      init,
+      payer = authority,
+      space = 8 + TokenAllowlistStates::MAX_DATA_SIZE,
+      seeds = [b"nft_creator_allowlist_states", creator_address.as_ref()],
+      bump,
+  )]
+  pub nft_creator_allowlist_states: Account<'info, TokenAllowlistStates>,
+  /// CHECK: checked by the constraints.
+  #[account(
+      mut,
+      seeds = [
+        b"nft_creator_allowlist_index",
+        PubkeyIndexPage::page_id(
+          global_states.total_allowed_nft_creators,
+          global_states.num_keys_per_index_page,
+        ).to_le_bytes().as_ref(),
+      ],
+      bump,
+  )]
+  pub nft_creator_allowlist_index: UncheckedAccount<'info>,
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.has_role(AdminRole::AllowlistCurator, authority.key()) @JustiesErrorCode::MissingAdminRole,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub justies_program: Program<'info, Justies>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<AddNftCreatorToAllowList>,
+  creator_address: Pubkey,
+) -> Result<()> {
+  let global_states = &mut ctx.accounts.global_states;
+  let nft_creator_allowlist_states =
+    &mut ctx.accounts.nft_creator_allowlist_states;
+
+  nft_creator_allowlist_states.bump =
+    *ctx.bumps.get("nft_creator_allowlist_states").unwrap();
+  nft_creator_allowlist_states.allowed = true;
+  nft_creator_allowlist_states.token_mint_address = creator_address;
+
+  // Indexes the nft creator allowlist states key to make it iterable.
+  let page_id = PubkeyIndexPage::page_id(
+    global_states.total_allowed_nft_creators,
+    global_states.num_keys_per_index_page,
+  );
+  let page_id_bytes = page_id.to_le_bytes();
+  let index_page_bump = *ctx.bumps.get("nft_creator_allowlist_index").unwrap();
+  let index_page_bump_bytes = index_page_bump.to_le_bytes();
+  let signer_seed = vec![
+    b"nft_creator_allowlist_index".as_ref(),
+    page_id_bytes.as_ref(),
+    index_page_bump_bytes.as_ref(),
+  ];
+  index_pubkey(
+    ctx
+      .accounts
+      .nft_creator_allowlist_states
+      .to_account_info()
+      .key(),
+    index_page_bump,
+    signer_seed.as_slice(),
+    ctx.accounts.nft_creator_allowlist_index.to_account_info(),
+    ctx.accounts.authority.to_account_info(),
+    ctx.accounts.justies_program.to_account_info(),
+    ctx.accounts.system_program.to_account_info(),
+  )?;
+
+  global_states.total_allowed_nft_creators += 1;
+  ctx.accounts.audit_log.record(
+    ConfigChange::new(AuditField::NftCreatorAllowlist, false, true)
+      .with_target(creator_address),
+    ctx.accounts.authority.key(),
+    get_current_timestamp(global_states),
+  );
+  Ok(())
+}
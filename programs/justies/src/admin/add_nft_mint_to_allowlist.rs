@@ -0,0 +1,130 @@
+use crate::admin::{
+  AdminRole, AuditField, AuditLog, ConfigChange, GlobalStates,
+  TokenAllowlistStates,
+};
+use crate::common::{
+  get_current_timestamp, index_pubkey, JustiesErrorCode, PubkeyIndexPage,
+};
+use crate::program::Justies;
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{Metadata, MetadataAccount};
+use anchor_spl::token::Mint;
+
+/// Adds an individual NFT into the allowlist so that it can be listed in
+/// auctions and raffles. This is for the NFTs without a verified collection
+/// (e.g.: 1/1 arts and older mints), which can't be allow-listed via
+/// add_nft_collection_to_allowlist.
+///
+/// The allowlist states can be looked up via NFT mint address, or iterated via
+/// the nft_mint_allowlist_index pda.
+///
+/// Only callable by the allowlist curator.
+#[derive(Accounts)]
+#[instruction(nft_mint_address: Pubkey)]
+pub struct AddNftMintToAllowList<'info> {
+  #[account(
+      init,
+      payer = authority,
+      space = 8 + TokenAllowlistStates::MAX_DATA_SIZE,
+      seeds = [b"nft_mint_allowlist_states", nft_mint_address.as_ref()],
+      bump,
+  )]
+  pub nft_mint_allowlist_states: Account<'info, TokenAllowlistStates>,
+  /// CHECK: checked by the constraints.
+  #[account(
+      mut,
+      seeds = [
+        b"nft_mint_allowlist_index",
+        PubkeyIndexPage::page_id(
+          global_states.total_allowed_nft_mints,
+          global_states.num_keys_per_index_page,
+        ).to_le_bytes().as_ref(),
+      ],
+      bump,
+  )]
+  pub nft_mint_allowlist_index: UncheckedAccount<'info>,
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.has_role(AdminRole::AllowlistCurator, authority.key()) @JustiesErrorCode::MissingAdminRole,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  #[account(
+      constraint = nft_mint.key() == nft_mint_address.key() @JustiesErrorCode::InvalidNftMint,
+      constraint = nft_mint.decimals == 0 @JustiesErrorCode::InvalidNftMint,
+      constraint = nft_mint.supply == 1 @JustiesErrorCode::InvalidNftMint,
+  )]
+  pub nft_mint: Account<'info, Mint>,
+  #[account(
+      seeds = [
+        b"metadata",
+        Metadata::id().as_ref(),
+        nft_mint.key().as_ref(),
+      ],
+      seeds::program = Metadata::id(),
+      bump,
+      constraint = nft_metadata.key == mpl_token_metadata::state::Key::MetadataV1 @JustiesErrorCode::InvalidNftMetadata,
+      constraint = nft_metadata.collection.as_ref().map_or(true, |collection| !collection.verified) @JustiesErrorCode::InvalidNftMetadata,
+  )]
+  pub nft_metadata: Account<'info, MetadataAccount>,
+  pub justies_program: Program<'info, Justies>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<AddNftMintToAllowList>,
+  nft_mint_address: Pubkey,
+) -> Result<()> {
+  let global_states = &mut ctx.accounts.global_states;
+  let nft_mint_allowlist_states = &mut ctx.accounts.nft_mint_allowlist_states;
+
+  nft_mint_allowlist_states.bump =
+    *ctx.bumps.get("nft_mint_allowlist_states").unwrap();
+  nft_mint_allowlist_states.allowed = true;
+  nft_mint_allowlist_states.token_mint_address = nft_mint_address;
+
+  // Indexes the nft mint allowlist states key to make it iterable.
+  let page_id = PubkeyIndexPage::page_id(
+    global_states.total_allowed_nft_mints,
+    global_states.num_keys_per_index_page,
+  );
+  let page_id_bytes = page_id.to_le_bytes();
+  let index_page_bump = *ctx.bumps.get("nft_mint_allowlist_index").unwrap();
+  let index_page_bump_bytes = index_page_bump.to_le_bytes();
+  let signer_seed = vec![
+    b"nft_mint_allowlist_index".as_ref(),
+    page_id_bytes.as_ref(),
+    index_page_bump_bytes.as_ref(),
+  ];
+  index_pubkey(
+    ctx
+      .accounts
+      .nft_mint_allowlist_states
+      .to_account_info()
+      .key(),
+    index_page_bump,
+    signer_seed.as_slice(),
+    ctx.accounts.nft_mint_allowlist_index.to_account_info(),
+    ctx.accounts.authority.to_account_info(),
+    ctx.accounts.justies_program.to_account_info(),
+    ctx.accounts.system_program.to_account_info(),
+  )?;
+
+  global_states.total_allowed_nft_mints += 1;
+  ctx.accounts.audit_log.record(
+    ConfigChange::new(AuditField::NftMintAllowlist, false, true)
+      .with_target(nft_mint_address),
+    ctx.accounts.authority.key(),
+    get_current_timestamp(global_states),
+  );
+  Ok(())
+}
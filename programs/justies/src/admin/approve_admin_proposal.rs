@@ -0,0 +1,47 @@
+use crate::admin::{AdminProposal, GlobalStates};
+use crate::common::JustiesErrorCode;
+use anchor_lang::prelude::*;
+
+/// Approves an admin proposal.
+///
+/// Only callable by the multisig members that haven't approved the proposal
+/// since the last change of the members.
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ApproveAdminProposal<'info> {
+  #[account(
+      mut,
+      seeds = [b"admin_proposal", proposal_id.to_le_bytes().as_ref()],
+      bump = admin_proposal.bump,
+      constraint = !admin_proposal.executed @JustiesErrorCode::AdminProposalAlreadyExecuted,
+  )]
+  pub admin_proposal: Box<Account<'info, AdminProposal>>,
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.is_multisig_member(approver.key()) @JustiesErrorCode::NotAMultisigMember,
+  )]
+  pub global_states: Box<Account<'info, GlobalStates>>,
+  pub approver: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<ApproveAdminProposal>,
+  _proposal_id: u64,
+) -> Result<()> {
+  let approver = ctx.accounts.approver.key();
+  let admin_proposal = &mut ctx.accounts.admin_proposal;
+  // The approvals given before the multisig members changed are cleared, so
+  // that they have to be given again by the current members.
+  let global_states = &ctx.accounts.global_states;
+  if !admin_proposal.has_current_approvals(global_states) {
+    admin_proposal.approvers.clear();
+    admin_proposal.multisig_nonce = global_states.multisig_nonce;
+  }
+  if admin_proposal.approvers.contains(&approver) {
+    return err!(JustiesErrorCode::AdminProposalAlreadyApproved);
+  }
+  admin_proposal.approvers.push(approver);
+  Ok(())
+}
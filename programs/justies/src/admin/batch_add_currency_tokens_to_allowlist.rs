@@ -0,0 +1,136 @@
+use crate::admin::{
+  AdminRole, AllowlistType, AuditLog, ConfigChange, GlobalStates,
+};
+use crate::common::{
+  batch_entry_name, create_and_index_allowlist_states,
+  create_associated_token_account, get_current_timestamp, JustiesErrorCode,
+};
+use crate::program::Justies;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{
+  get_associated_token_address, AssociatedToken,
+};
+use anchor_spl::token::{Mint, Token};
+
+/// Adds currency tokens to the allowlist in batch. This behaves the same as
+/// calling add_currency_token_to_allowlist for each of the tokens.
+///
+/// For the i-th token, the following accounts need to be set as
+/// "remaining_accounts" in order:
+/// - remaining_accounts[3 * i]: the token_allowlist_states pda of the token.
+/// - remaining_accounts[3 * i + 1]: the currency token mint account.
+/// - remaining_accounts[3 * i + 2]: the fee treasury's associated token account
+///   of the currency token, which is created if needed.
+///
+/// They are followed by the token_allowlist_index pages that the new keys are
+/// indexed into (i.e.: the current page, plus the next pages if the current
+/// page gets full).
+///
+/// The errors are attached with the name of the failed entry, e.g.:
+/// "entries[2].currency_token_mint".
+///
+/// Only callable by the allowlist curator.
+#[derive(Accounts)]
+pub struct BatchAddCurrencyTokensToAllowList<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  /// CHECK: the address is verified in the constraint.
+  pub fee_treasury: UncheckedAccount<'info>,
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.has_role(AdminRole::AllowlistCurator, authority.key()) @JustiesErrorCode::MissingAdminRole,
+      constraint = global_states.fee_treasury_address == fee_treasury.key() @JustiesErrorCode::InvalidFeeTreasuryAddress,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub token_program: Program<'info, Token>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub justies_program: Program<'info, Justies>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+  ctx: Context<'_, '_, '_, 'info, BatchAddCurrencyTokensToAllowList<'info>>,
+  token_mint_addresses: Vec<Pubkey>,
+) -> Result<()> {
+  let num_entries = token_mint_addresses.len();
+  if ctx.remaining_accounts.len() < 3 * num_entries {
+    return err!(JustiesErrorCode::NotEnoughPayloadAccounts);
+  }
+  let (entry_accounts, index_pages) =
+    ctx.remaining_accounts.split_at(3 * num_entries);
+
+  let global_states = &mut ctx.accounts.global_states;
+  let current_timestamp = get_current_timestamp(global_states);
+  let mut changes = vec![];
+  for (idx, token_mint_address) in token_mint_addresses.into_iter().enumerate()
+  {
+    let currency_token_mint_info = &entry_accounts[3 * idx + 1];
+    if currency_token_mint_info.key() != token_mint_address
+      || Account::<Mint>::try_from(currency_token_mint_info).is_err()
+    {
+      return Err(
+        error!(JustiesErrorCode::InvalidCurrencyTokenMint)
+          .with_account_name(batch_entry_name(idx, "currency_token_mint")),
+      );
+    }
+    let fee_treasury_token_account_info = &entry_accounts[3 * idx + 2];
+    if fee_treasury_token_account_info.key()
+      != get_associated_token_address(
+        &ctx.accounts.fee_treasury.key(),
+        &token_mint_address,
+      )
+    {
+      return Err(
+        error!(JustiesErrorCode::InvalidFeeTreasuryTokenAccount)
+          .with_account_name(batch_entry_name(
+            idx,
+            "fee_treasury_token_account",
+          )),
+      );
+    }
+
+    create_and_index_allowlist_states(
+      AllowlistType::CurrencyToken,
+      idx,
+      token_mint_address,
+      &entry_accounts[3 * idx],
+      index_pages,
+      global_states,
+      ctx.accounts.authority.to_account_info(),
+      ctx.accounts.justies_program.to_account_info(),
+      ctx.accounts.system_program.to_account_info(),
+    )?;
+    create_associated_token_account(
+      fee_treasury_token_account_info.clone(),
+      ctx.accounts.authority.to_account_info(),
+      ctx.accounts.fee_treasury.to_account_info(),
+      currency_token_mint_info.clone(),
+      &ctx.accounts.associated_token_program,
+      &ctx.accounts.token_program,
+      &ctx.accounts.system_program,
+    )?;
+    changes.push(
+      ConfigChange::new(
+        AllowlistType::CurrencyToken.audit_field(),
+        false,
+        true,
+      )
+      .with_target(token_mint_address),
+    );
+  }
+
+  ctx.accounts.audit_log.record_all(
+    changes,
+    ctx.accounts.authority.key(),
+    current_timestamp,
+  );
+  Ok(())
+}
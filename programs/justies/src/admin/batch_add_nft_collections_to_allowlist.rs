@@ -0,0 +1,139 @@
+use crate::admin::{
+  AdminRole, AllowlistType, AuditLog, ConfigChange, GlobalStates,
+};
+use crate::common::{
+  batch_entry_name, create_and_index_allowlist_states, get_current_timestamp,
+  JustiesErrorCode,
+};
+use crate::program::Justies;
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token::Mint;
+
+/// Adds NFT collections to the allowlist in batch. This behaves the same as
+/// calling add_nft_collection_to_allowlist for each of the collections.
+///
+/// For the i-th collection, the following accounts need to be set as
+/// "remaining_accounts" in order:
+/// - remaining_accounts[3 * i]: the nft_allowlist_states pda of the collection.
+/// - remaining_accounts[3 * i + 1]: the collection mint account.
+/// - remaining_accounts[3 * i + 2]: the collection metadata account.
+///
+/// They are followed by the nft_allowlist_index pages that the new keys are
+/// indexed into (i.e.: the current page, plus the next pages if the current
+/// page gets full).
+///
+/// The errors are attached with the name of the failed entry, e.g.:
+/// "entries[2].collection_metadata".
+///
+/// Only callable by the allowlist curator.
+#[derive(Accounts)]
+pub struct BatchAddNftCollectionsToAllowList<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.has_role(AdminRole::AllowlistCurator, authority.key()) @JustiesErrorCode::MissingAdminRole,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub justies_program: Program<'info, Justies>,
+  pub system_program: Program<'info, System>,
+}
+
+/// Validates the collection mint & metadata accounts of the idx-th entry.
+fn validate_collection(
+  idx: usize,
+  collection_mint_address: Pubkey,
+  collection_mint_info: &AccountInfo,
+  collection_metadata_info: &AccountInfo,
+) -> Result<()> {
+  let invalid_collection_mint = || {
+    error!(JustiesErrorCode::InvalidNftCollectionMint)
+      .with_account_name(batch_entry_name(idx, "collection_mint"))
+  };
+  if collection_mint_info.key() != collection_mint_address {
+    return Err(invalid_collection_mint());
+  }
+  let collection_mint = Account::<Mint>::try_from(collection_mint_info)
+    .map_err(|_| invalid_collection_mint())?;
+  if collection_mint.decimals != 0 || collection_mint.supply != 1 {
+    return Err(invalid_collection_mint());
+  }
+
+  let invalid_collection_metadata = || {
+    error!(JustiesErrorCode::InvalidNftCollectionMetadata)
+      .with_account_name(batch_entry_name(idx, "collection_metadata"))
+  };
+  let collection_metadata =
+    Account::<MetadataAccount>::try_from(collection_metadata_info)
+      .map_err(|_| invalid_collection_metadata())?;
+  // The token metadata program only creates the metadata account of a mint at
+  // its metadata pda, so checking the mint field is sufficient.
+  if collection_metadata.mint != collection_mint_address
+    || collection_metadata.key != mpl_token_metadata::state::Key::MetadataV1
+    || collection_metadata.collection != None
+  {
+    return Err(invalid_collection_metadata());
+  }
+  Ok(())
+}
+
+pub fn handler<'info>(
+  ctx: Context<'_, '_, '_, 'info, BatchAddNftCollectionsToAllowList<'info>>,
+  collection_mint_addresses: Vec<Pubkey>,
+) -> Result<()> {
+  let num_entries = collection_mint_addresses.len();
+  if ctx.remaining_accounts.len() < 3 * num_entries {
+    return err!(JustiesErrorCode::NotEnoughPayloadAccounts);
+  }
+  let (entry_accounts, index_pages) =
+    ctx.remaining_accounts.split_at(3 * num_entries);
+
+  let global_states = &mut ctx.accounts.global_states;
+  let current_timestamp = get_current_timestamp(global_states);
+  let mut changes = vec![];
+  for (idx, collection_mint_address) in
+    collection_mint_addresses.into_iter().enumerate()
+  {
+    validate_collection(
+      idx,
+      collection_mint_address,
+      &entry_accounts[3 * idx + 1],
+      &entry_accounts[3 * idx + 2],
+    )?;
+    create_and_index_allowlist_states(
+      AllowlistType::NftCollection,
+      idx,
+      collection_mint_address,
+      &entry_accounts[3 * idx],
+      index_pages,
+      global_states,
+      ctx.accounts.authority.to_account_info(),
+      ctx.accounts.justies_program.to_account_info(),
+      ctx.accounts.system_program.to_account_info(),
+    )?;
+    changes.push(
+      ConfigChange::new(
+        AllowlistType::NftCollection.audit_field(),
+        false,
+        true,
+      )
+      .with_target(collection_mint_address),
+    );
+  }
+
+  ctx.accounts.audit_log.record_all(
+    changes,
+    ctx.accounts.authority.key(),
+    current_timestamp,
+  );
+  Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::admin::{
+  AuditField, AuditLog, ConfigChange, GlobalStates, WalletBlocklistStates,
+};
+use crate::common::{get_current_timestamp, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Blocks a wallet (e.g.: a sanctioned or abusive address).
+///
+/// A blocked wallet can't create auctions & raffles, make bids, buy raffle
+/// tickets or be a revenue recipient of new listings, while it can still
+/// withdraw the funds it has escrowed (e.g.: cancelling bids) and claim.
+///
+/// The blocklist states can be looked up via the wallet address.
+///
+/// Only callable by the authority.
+#[derive(Accounts)]
+#[instruction(wallet_address: Pubkey)]
+pub struct BlockWallet<'info> {
+  #[account(
+      init,
+      payer = authority,
+      space = 8 + WalletBlocklistStates::MAX_DATA_SIZE,
+      seeds = [b"wallet_blocklist_states", wallet_address.as_ref()],
+      bump,
+  )]
+  pub wallet_blocklist_states: Account<'info, WalletBlocklistStates>,
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.authority == authority.key() @JustiesErrorCode::NotTheAuthority,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<BlockWallet>,
+  wallet_address: Pubkey,
+  reason_code: Option<u16>,
+) -> Result<()> {
+  let wallet_blocklist_states = &mut ctx.accounts.wallet_blocklist_states;
+  wallet_blocklist_states.bump =
+    *ctx.bumps.get("wallet_blocklist_states").unwrap();
+  wallet_blocklist_states.wallet_address = wallet_address;
+  wallet_blocklist_states.reason_code = reason_code;
+
+  ctx.accounts.audit_log.record(
+    ConfigChange::new(AuditField::WalletBlocklist, false, true)
+      .with_target(wallet_address)
+      .with_reason_code(reason_code),
+    ctx.accounts.authority.key(),
+    get_current_timestamp(&ctx.accounts.global_states),
+  );
+  Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::admin::{AuditField, AuditLog, ConfigChange, GlobalStates};
+use crate::common::{get_current_timestamp, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Cancels the pending authority proposal.
+///
+/// Only callable by the authority when there is a pending authority.
+#[derive(Accounts)]
+pub struct CancelAuthorityProposal<'info> {
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.authority == authority.key() @JustiesErrorCode::NotTheAuthority,
+      constraint = global_states.pending_authority.is_some() @JustiesErrorCode::NoPendingAuthority,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CancelAuthorityProposal>) -> Result<()> {
+  let current_timestamp = get_current_timestamp(&ctx.accounts.global_states);
+  let global_states = &mut ctx.accounts.global_states;
+  let change = ConfigChange::new(
+    AuditField::PendingAuthority,
+    global_states.pending_authority,
+    None,
+  );
+  global_states.pending_authority = None;
+  ctx.accounts.audit_log.record(
+    change,
+    ctx.accounts.authority.key(),
+    current_timestamp,
+  );
+  Ok(())
+}
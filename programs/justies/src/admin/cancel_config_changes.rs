@@ -0,0 +1,55 @@
+use crate::admin::{AdminRole, AuditLog, GlobalStates, PendingConfigs};
+use crate::common::{get_current_timestamp, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Cancels the queued timelocked config changes before they are activated,
+/// after which they can only be executed.
+///
+/// The rent of the pending configs account goes back to its proposer.
+///
+/// Only callable by the authority or the treasury admin.
+#[derive(Accounts)]
+pub struct CancelConfigChanges<'info> {
+  #[account(
+      mut,
+      close = proposer,
+      seeds = [b"pending_configs"],
+      bump = pending_configs.bump,
+  )]
+  pub pending_configs: Account<'info, PendingConfigs>,
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.authority == authority.key() || global_states.has_role(AdminRole::TreasuryAdmin, authority.key()) @JustiesErrorCode::MissingAdminRole,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  /// CHECK: the address is verified in the constraint.
+  #[account(
+      mut,
+      constraint = proposer.key() == pending_configs.proposer @JustiesErrorCode::InvalidPendingConfigsProposer,
+  )]
+  pub proposer: UncheckedAccount<'info>,
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CancelConfigChanges>) -> Result<()> {
+  let current_timestamp = get_current_timestamp(&ctx.accounts.global_states);
+  let pending_configs = &ctx.accounts.pending_configs;
+  if current_timestamp >= pending_configs.activation_timestamp {
+    return err!(JustiesErrorCode::PendingConfigsActivated);
+  }
+  let changes = pending_configs.input.pending_config_changes(false);
+  ctx.accounts.audit_log.record_all(
+    changes,
+    ctx.accounts.authority.key(),
+    current_timestamp,
+  );
+  Ok(())
+}
@@ -0,0 +1,66 @@
+use crate::admin::{AdminProposal, GlobalStates, ProposalAccountMeta};
+use crate::common::JustiesErrorCode;
+use anchor_lang::prelude::*;
+
+/// Proposes a justies instruction to be executed by the multisig.
+///
+/// The instruction is described by its account metas & serialized data, and
+/// will be invoked with the multisig_authority pda as the signer. The proposer
+/// approves the proposal automatically.
+///
+/// Only callable by the multisig members.
+#[derive(Accounts)]
+#[instruction(
+    accounts: Vec<ProposalAccountMeta>,
+    data: Vec<u8>,
+)]
+pub struct CreateAdminProposal<'info> {
+  #[account(
+      init,
+      payer = proposer,
+      space = 8 + AdminProposal::MAX_DATA_SIZE,
+      seeds = [
+        b"admin_proposal",
+        global_states.total_admin_proposals.to_le_bytes().as_ref(),
+      ],
+      bump,
+  )]
+  pub admin_proposal: Box<Account<'info, AdminProposal>>,
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.is_multisig_member(proposer.key()) @JustiesErrorCode::NotAMultisigMember,
+  )]
+  pub global_states: Box<Account<'info, GlobalStates>>,
+  #[account(mut)]
+  pub proposer: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<CreateAdminProposal>,
+  accounts: Vec<ProposalAccountMeta>,
+  data: Vec<u8>,
+) -> Result<()> {
+  if accounts.len() > AdminProposal::MAX_ACCOUNTS
+    || data.len() > AdminProposal::MAX_INSTRUCTION_DATA_LEN
+  {
+    return err!(JustiesErrorCode::InvalidAdminProposalInstruction);
+  }
+
+  let global_states = &mut ctx.accounts.global_states;
+  let admin_proposal = &mut ctx.accounts.admin_proposal;
+
+  admin_proposal.bump = *ctx.bumps.get("admin_proposal").unwrap();
+  admin_proposal.id = global_states.total_admin_proposals;
+  admin_proposal.proposer = ctx.accounts.proposer.key();
+  admin_proposal.accounts = accounts;
+  admin_proposal.data = data;
+  admin_proposal.approvers = vec![ctx.accounts.proposer.key()];
+  admin_proposal.executed = false;
+  admin_proposal.multisig_nonce = global_states.multisig_nonce;
+
+  global_states.total_admin_proposals += 1;
+  Ok(())
+}
@@ -0,0 +1,112 @@
+use crate::admin::{Feature, GlobalStates};
+use crate::common::resize_account;
+use crate::common::JustiesErrorCode;
+use anchor_lang::prelude::*;
+
+/// The space of the global states account of version 0.0, i.e.: the layout
+/// before the versioning.
+const V0_SPACE: usize = 8
+  + 1
+  + 2
+  + 32
+  + 32
+  + 2
+  + 1
+  + 1
+  + 8
+  + 8
+  + 2
+  + 2
+  + 1
+  + 8
+  + 8
+  + 1
+  + 1
+  + 8
+  + 8
+  + 8
+  + 8
+  + 2
+  + 1
+  + (1 + 8);
+
+/// Rewrites the global states account in the layout of version 0.0
+/// (testing-only), so that the migration from it can be tested.
+///
+/// The fields added since version 0.0 are dropped, and the account is shrunk
+/// to the space of version 0.0 (with the rents refunded to the authority).
+///
+/// Only callable by the authority when global_states.is_test_environment is
+/// true and the program is built with the "testing" feature. The instruction is
+/// only compiled into the builds with the "testing" feature, and is dispatched
+/// via the fallback of the program (see common/testing_instructions.rs).
+#[derive(Accounts)]
+pub struct DowngradeGlobalStates<'info> {
+  /// CHECK: The account is rewritten in an older layout, so it is deserialized
+  /// & serialized in the handler.
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump,
+      owner = crate::ID,
+  )]
+  pub global_states: UncheckedAccount<'info>,
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DowngradeGlobalStates>) -> Result<()> {
+  let global_states_info = ctx.accounts.global_states.to_account_info();
+  let global_states =
+    GlobalStates::try_deserialize(&mut &global_states_info.data.borrow()[..])?;
+  if !global_states.is_test_mode() {
+    return err!(JustiesErrorCode::NotTestEnvironment);
+  }
+  if global_states.authority != ctx.accounts.authority.key() {
+    return err!(JustiesErrorCode::NotTheAuthority);
+  }
+
+  // The fields added since version 0.0 are serialized as zeros, which are cut
+  // off by the shrinking.
+  let v0_global_states = GlobalStates {
+    bump: global_states.bump,
+    market_fee_rate_bps: global_states.market_fee_rate_bps,
+    fee_treasury_address: global_states.fee_treasury_address,
+    authority: global_states.authority,
+    min_outbid_rate_bps: global_states.min_outbid_rate_bps,
+    last_minutes_for_auction_extend: global_states
+      .last_minutes_for_auction_extend,
+    auction_extend_minutes: global_states.auction_extend_minutes,
+    min_auction_duration: global_states.min_auction_duration,
+    max_auction_duration: global_states.max_auction_duration,
+    min_raffle_ticket_supply: global_states.min_raffle_ticket_supply,
+    max_raffle_ticket_supply: global_states.max_raffle_ticket_supply,
+    max_raffled_nfts: global_states.max_raffled_nfts,
+    min_raffle_duration: global_states.min_raffle_duration,
+    max_raffle_duration: global_states.max_raffle_duration,
+    auction_creation_enabled: global_states
+      .is_feature_enabled(Feature::AuctionCreation),
+    raffle_creation_enabled: global_states
+      .is_feature_enabled(Feature::RaffleCreation),
+    total_auctions: global_states.total_auctions,
+    total_raffles: global_states.total_raffles,
+    total_allowed_nft_collections: global_states.total_allowed_nft_collections,
+    total_allowed_currency_tokens: global_states.total_allowed_currency_tokens,
+    num_keys_per_index_page: global_states.num_keys_per_index_page,
+    is_test_environment: global_states.is_test_environment,
+    mock_timestamp: global_states.mock_timestamp,
+    ..Default::default()
+  };
+  {
+    let mut data = global_states_info.data.borrow_mut();
+    data.fill(0);
+    v0_global_states.try_serialize(&mut &mut data[..])?;
+  }
+  resize_account(
+    global_states_info,
+    V0_SPACE,
+    ctx.accounts.authority.to_account_info(),
+    ctx.accounts.system_program.to_account_info(),
+  )
+}
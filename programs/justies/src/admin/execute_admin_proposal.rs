@@ -0,0 +1,108 @@
+use crate::admin::{AdminProposal, GlobalStates};
+use crate::common::JustiesErrorCode;
+use crate::instruction as justies_instruction;
+use crate::program::Justies;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::Discriminator;
+
+/// Executes an admin proposal that is approved by enough multisig members.
+///
+/// The proposed instruction is invoked on the justies program with the
+/// multisig_authority pda as signer. All the accounts of the proposed
+/// instruction (except the multisig_authority pda) need to be passed via
+/// "remaining_accounts".
+///
+/// The proposals targeting the admin proposal instructions themselves, or
+/// passing the admin proposal account, are rejected. The proposal is marked as
+/// executed before the proposed instruction is invoked.
+///
+/// Callable by anyone.
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ExecuteAdminProposal<'info> {
+  #[account(
+      mut,
+      seeds = [b"admin_proposal", proposal_id.to_le_bytes().as_ref()],
+      bump = admin_proposal.bump,
+      constraint = !admin_proposal.executed @JustiesErrorCode::AdminProposalAlreadyExecuted,
+  )]
+  pub admin_proposal: Box<Account<'info, AdminProposal>>,
+  // Intentionally immutable: the proposed instruction may update the global
+  // states, which must not be overridden when this instruction exits.
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+  )]
+  pub global_states: Box<Account<'info, GlobalStates>>,
+  /// CHECK: the pda signing the proposed instruction, verified by the seeds.
+  #[account(
+      mut,
+      seeds = [b"multisig_authority"],
+      bump,
+  )]
+  pub multisig_authority: UncheckedAccount<'info>,
+  pub executor: Signer<'info>,
+  pub justies_program: Program<'info, Justies>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+  ctx: Context<'_, '_, '_, 'info, ExecuteAdminProposal<'info>>,
+  _proposal_id: u64,
+) -> Result<()> {
+  let global_states = &ctx.accounts.global_states;
+  let threshold = global_states.multisig_threshold as usize;
+  if threshold == 0
+    || ctx.accounts.admin_proposal.num_approvals(global_states) < threshold
+  {
+    return err!(JustiesErrorCode::NotEnoughAdminProposalApprovals);
+  }
+
+  let mut account_infos = ctx.remaining_accounts.to_vec();
+  account_infos.push(ctx.accounts.multisig_authority.to_account_info());
+  account_infos.push(ctx.accounts.justies_program.to_account_info());
+
+  let admin_proposal = &mut ctx.accounts.admin_proposal;
+  let admin_proposal_key = admin_proposal.key();
+  let is_admin_proposal_instruction = [
+    justies_instruction::CreateAdminProposal::discriminator(),
+    justies_instruction::ApproveAdminProposal::discriminator(),
+    justies_instruction::ExecuteAdminProposal::discriminator(),
+  ]
+  .iter()
+  .any(|discriminator| admin_proposal.data.starts_with(discriminator));
+  if is_admin_proposal_instruction {
+    return err!(JustiesErrorCode::InvalidAdminProposalInstruction);
+  }
+  let mut account_metas = vec![];
+  for meta in admin_proposal.accounts.iter() {
+    if meta.pubkey == admin_proposal_key {
+      return err!(JustiesErrorCode::InvalidAdminProposalInstruction);
+    }
+    if !account_infos.iter().any(|info| info.key() == meta.pubkey) {
+      return err!(JustiesErrorCode::InvalidAdminProposalInstruction);
+    }
+    account_metas.push(AccountMeta {
+      pubkey: meta.pubkey,
+      is_signer: meta.is_signer,
+      is_writable: meta.is_writable,
+    });
+  }
+  let instruction = Instruction {
+    program_id: crate::ID,
+    accounts: account_metas,
+    data: admin_proposal.data.clone(),
+  };
+  admin_proposal.executed = true;
+  // Persists the executed flag before the invocation, so that the proposal
+  // can't be re-executed within the proposed instruction.
+  admin_proposal.exit(&crate::ID)?;
+
+  let multisig_authority_bump = *ctx.bumps.get("multisig_authority").unwrap();
+  let signer_seed =
+    [b"multisig_authority".as_ref(), &[multisig_authority_bump]];
+  invoke_signed(&instruction, &account_infos, &[&signer_seed])?;
+  Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::admin::{AuditLog, GlobalStates, PendingConfigs};
+use crate::common::{get_current_timestamp, ConfigsStrategy, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Executes the queued timelocked config changes once they are activated.
+///
+/// The rent of the pending configs account goes back to its proposer.
+///
+/// Callable by anyone.
+#[derive(Accounts)]
+pub struct ExecuteConfigChanges<'info> {
+  #[account(
+      mut,
+      close = proposer,
+      seeds = [b"pending_configs"],
+      bump = pending_configs.bump,
+  )]
+  pub pending_configs: Account<'info, PendingConfigs>,
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  /// CHECK: the address is verified in the constraint.
+  #[account(
+      mut,
+      constraint = proposer.key() == pending_configs.proposer @JustiesErrorCode::InvalidPendingConfigsProposer,
+  )]
+  pub proposer: UncheckedAccount<'info>,
+  pub executor: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ExecuteConfigChanges>) -> Result<()> {
+  let current_timestamp = get_current_timestamp(&ctx.accounts.global_states);
+  let pending_configs = &ctx.accounts.pending_configs;
+  let mut configs_strategy =
+    ConfigsStrategy::new(&mut ctx.accounts.global_states);
+  let mut changes = configs_strategy
+    .execute_pending_configs(pending_configs, current_timestamp)?;
+  changes.extend(pending_configs.input.pending_config_changes(false));
+  // The changes are attributed to the signer that queued them.
+  ctx.accounts.audit_log.record_all(
+    changes,
+    pending_configs.proposer,
+    current_timestamp,
+  );
+  Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::admin::{AdminRole, AuditLog, ConfigChange, GlobalStates};
+use crate::common::{get_current_timestamp, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Grants an admin role to the grantee.
+///
+/// Each role is held by a single key, so granting the role to a new grantee
+/// replaces the previous holder.
+///
+/// Only callable by the authority.
+#[derive(Accounts)]
+#[instruction(
+    role: AdminRole,
+    grantee: Pubkey,
+)]
+pub struct GrantAdminRole<'info> {
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.authority == authority.key() @JustiesErrorCode::NotTheAuthority,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<GrantAdminRole>,
+  role: AdminRole,
+  grantee: Pubkey,
+) -> Result<()> {
+  let current_timestamp = get_current_timestamp(&ctx.accounts.global_states);
+  let global_states = &mut ctx.accounts.global_states;
+  let change = ConfigChange::new(
+    role.into(),
+    global_states.role_holder(role),
+    Some(grantee),
+  );
+  global_states.set_role_holder(role, Some(grantee));
+  ctx.accounts.audit_log.record(
+    change,
+    ctx.accounts.authority.key(),
+    current_timestamp,
+  );
+  Ok(())
+}
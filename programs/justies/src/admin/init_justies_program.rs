@@ -1,6 +1,6 @@
-use crate::admin::state::GlobalStates;
+use crate::admin::state::{AuditLog, Feature, GlobalStates};
 use crate::auction::state::Auction;
-use crate::common::PubkeyIndexPage;
+use crate::common::{JustiesErrorCode, PubkeyIndexPage};
 use crate::raffle::state::Raffle;
 use anchor_lang::prelude::*;
 
@@ -9,8 +9,12 @@ use anchor_lang::prelude::*;
 /// During initialization only a few params can be set explicitly, and all the
 /// the other options will be set to the default values.
 ///
+/// A test environment can only be initialized when the program is built with
+/// the "testing" feature.
+///
 /// The signer of the 1st init instruction call become the default authority.
-/// The authority can later be re-assigned.
+/// The authority can later be re-assigned via propose_authority and
+/// accept_authority.
 #[derive(Accounts)]
 #[instruction(
     market_fee_rate_bps: u16,
@@ -21,11 +25,19 @@ pub struct InitJustiesProgram<'info> {
   #[account(
       init,
       payer = authority,
-      space = 8 + GlobalStates::MAX_DATA_SIZE,
+      space = GlobalStates::SPACE,
       seeds = [b"global_states"],
       bump,
   )]
   pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      init,
+      payer = authority,
+      space = 8 + AuditLog::MAX_DATA_SIZE,
+      seeds = [b"audit_log"],
+      bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
   #[account(mut)]
   pub authority: Signer<'info>,
   pub system_program: Program<'info, System>,
@@ -37,9 +49,14 @@ pub fn handler(
   fee_treasury_address: Pubkey,
   is_test_environment: bool,
 ) -> Result<()> {
+  if is_test_environment && !cfg!(feature = "testing") {
+    return err!(JustiesErrorCode::NotTestEnvironment);
+  }
+
   let global_states = &mut ctx.accounts.global_states;
 
   global_states.bump = *ctx.bumps.get("global_states").unwrap();
+  ctx.accounts.audit_log.bump = *ctx.bumps.get("audit_log").unwrap();
   global_states.market_fee_rate_bps = market_fee_rate_bps;
   global_states.fee_treasury_address = fee_treasury_address;
   // The 1st signer that initializes the program become the authority.
@@ -47,9 +64,19 @@ pub fn handler(
   // the creation of this program, it's still possible to close the account as
   // the upgrade authority.
   //
-  // Please note that the authority is **immutable** after being initialized for
-  // safety-reason.
+  // The authority can only be transferred afterwards via the two-step
+  // propose_authority / accept_authority flow.
   global_states.authority = ctx.accounts.authority.key();
+  // All the admin roles are held by the authority by default, and can be
+  // granted to other keys afterwards.
+  global_states.config_admin = Some(ctx.accounts.authority.key());
+  global_states.allowlist_curator = Some(ctx.accounts.authority.key());
+  global_states.raffle_operator = Some(ctx.accounts.authority.key());
+  global_states.treasury_admin = Some(ctx.accounts.authority.key());
+  global_states.quarantine_wallet_address = ctx.accounts.authority.key();
+  global_states.curated_creators_only = false;
+  // No limit on the live listings of each creator.
+  global_states.max_live_listings_per_creator = 0;
   // 5% min outbid rate.
   global_states.min_outbid_rate_bps = 500;
   // Extend the auction if any bids made in the last 10 minutes.
@@ -65,8 +92,18 @@ pub fn handler(
   global_states.max_raffle_duration = Raffle::MAX_DURATION;
   global_states.auction_creation_enabled = true;
   global_states.raffle_creation_enabled = true;
+  global_states.bidding_enabled = true;
+  global_states.ticket_sales_enabled = true;
+  global_states.claims_enabled = true;
   global_states.num_keys_per_index_page = PubkeyIndexPage::KEYS_PER_PAGE as u16;
   global_states.is_test_environment = is_test_environment;
+  global_states.config_timelock_delay =
+    GlobalStates::DEFAULT_CONFIG_TIMELOCK_DELAY;
+  global_states.major_version = GlobalStates::MAJOR_VERSION;
+  global_states.minor_version = GlobalStates::MINOR_VERSION;
+  global_states.features = Feature::ALL;
+  // No listing fee by default.
+  global_states.listing_fee = 0;
 
   Ok(())
 }
@@ -0,0 +1,52 @@
+use crate::admin::{AllowlistType, TokenAllowlistStates};
+use crate::common::grow_account;
+use anchor_lang::prelude::*;
+
+/// Migrates an allowlist states account created before the suspension reason,
+/// the listing limits and the overrides were added, by growing it to the
+/// current size. The added fields are zeroed, i.e.: no reason code, no listing
+/// limits and no overrides.
+///
+/// The allowlist states of an older layout can't be deserialized, so the
+/// listings using the token can't be bid on, claimed or refunded until the
+/// account is migrated. Migrating an account of the current layout is a no-op.
+///
+/// Callable by anyone, where the signer pays the rent of the added space.
+#[derive(Accounts)]
+#[instruction(
+    allowlist_type: AllowlistType,
+    token_mint_address: Pubkey,
+)]
+pub struct MigrateAllowlistStates<'info> {
+  /// CHECK: The account of an older layout is too small to deserialize, so it
+  /// is deserialized in the handler after the reallocation.
+  #[account(
+      mut,
+      seeds = [allowlist_type.states_seed(), token_mint_address.as_ref()],
+      bump,
+      owner = crate::ID,
+  )]
+  pub allowlist_states: UncheckedAccount<'info>,
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<MigrateAllowlistStates>,
+  _allowlist_type: AllowlistType,
+  _token_mint_address: Pubkey,
+) -> Result<()> {
+  let allowlist_states_info = ctx.accounts.allowlist_states.to_account_info();
+  grow_account(
+    allowlist_states_info.clone(),
+    8 + TokenAllowlistStates::MAX_DATA_SIZE,
+    ctx.accounts.payer.to_account_info(),
+    ctx.accounts.system_program.to_account_info(),
+  )?;
+  // Verifies the account is an allowlist states account.
+  TokenAllowlistStates::try_deserialize(
+    &mut &allowlist_states_info.data.borrow()[..],
+  )?;
+  Ok(())
+}
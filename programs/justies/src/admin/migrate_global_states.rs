@@ -0,0 +1,92 @@
+use crate::admin::{AuditField, AuditLog, ConfigChange, GlobalStates};
+use crate::common::{get_current_timestamp, resize_account, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Migrates the global states account to the current layout version.
+///
+/// The account is reallocated to the current space (with the rent paid by the
+/// signer) if it is smaller, and the fields added after the version of the
+/// account are filled with their defaults. Migrating an account of the current
+/// version is a no-op.
+///
+/// The audit log is created as well if it doesn't exist yet (i.e.: for the
+/// deployments initialized before the audit log), since all the other admin
+/// instructions require it.
+///
+/// Only callable by the authority.
+#[derive(Accounts)]
+pub struct MigrateGlobalStates<'info> {
+  /// CHECK: The account of an older version may be too small to deserialize,
+  /// so it is deserialized in the handler after the reallocation.
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump,
+      owner = crate::ID,
+  )]
+  pub global_states: UncheckedAccount<'info>,
+  #[account(
+      init_if_needed,
+      payer = authority,
+      space = 8 + AuditLog::MAX_DATA_SIZE,
+      seeds = [b"audit_log"],
+      bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateGlobalStates>) -> Result<()> {
+  let global_states_info = ctx.accounts.global_states.to_account_info();
+  if global_states_info.data_len() < GlobalStates::SPACE {
+    resize_account(
+      global_states_info.clone(),
+      GlobalStates::SPACE,
+      ctx.accounts.authority.to_account_info(),
+      ctx.accounts.system_program.to_account_info(),
+    )?;
+  }
+
+  let mut global_states =
+    GlobalStates::try_deserialize(&mut &global_states_info.data.borrow()[..])?;
+  if global_states.authority != ctx.accounts.authority.key() {
+    return err!(JustiesErrorCode::NotTheAuthority);
+  }
+  ctx.accounts.audit_log.bump = *ctx.bumps.get("audit_log").unwrap();
+
+  let old_major_version = global_states.major_version;
+  let old_minor_version = global_states.minor_version;
+  global_states.migrate()?;
+
+  // Rewrite the whole account, so that no stale bytes (e.g.: left by shrinking
+  // the multisig members) are read as the new fields afterwards.
+  {
+    let mut data = global_states_info.data.borrow_mut();
+    data.fill(0);
+    global_states.try_serialize(&mut &mut data[..])?;
+  }
+
+  let mut changes = vec![];
+  if old_major_version != global_states.major_version {
+    changes.push(ConfigChange::new(
+      AuditField::GlobalStatesMajorVersion,
+      old_major_version,
+      global_states.major_version,
+    ));
+  }
+  if old_minor_version != global_states.minor_version {
+    changes.push(ConfigChange::new(
+      AuditField::GlobalStatesMinorVersion,
+      old_minor_version,
+      global_states.minor_version,
+    ));
+  }
+  ctx.accounts.audit_log.record_all(
+    changes,
+    ctx.accounts.authority.key(),
+    get_current_timestamp(&global_states),
+  );
+  Ok(())
+}
@@ -1,12 +1,78 @@
+pub mod accept_authority;
+pub use accept_authority::*;
 pub mod add_currency_token_to_allowlist;
 pub use add_currency_token_to_allowlist::*;
 pub mod add_nft_collection_to_allowlist;
 pub use add_nft_collection_to_allowlist::*;
+pub mod add_nft_creator_to_allowlist;
+pub use add_nft_creator_to_allowlist::*;
+pub mod add_nft_mint_to_allowlist;
+pub use add_nft_mint_to_allowlist::*;
+pub mod approve_admin_proposal;
+pub use approve_admin_proposal::*;
+pub mod batch_add_currency_tokens_to_allowlist;
+pub use batch_add_currency_tokens_to_allowlist::*;
+pub mod batch_add_nft_collections_to_allowlist;
+pub use batch_add_nft_collections_to_allowlist::*;
+pub mod block_wallet;
+pub use block_wallet::*;
+pub mod cancel_authority_proposal;
+pub use cancel_authority_proposal::*;
+pub mod cancel_config_changes;
+pub use cancel_config_changes::*;
+pub mod create_admin_proposal;
+pub use create_admin_proposal::*;
+#[cfg(feature = "testing")]
+pub mod downgrade_global_states;
+#[cfg(feature = "testing")]
+pub use downgrade_global_states::*;
+pub mod execute_admin_proposal;
+pub use execute_admin_proposal::*;
+pub mod execute_config_changes;
+pub use execute_config_changes::*;
+pub mod grant_admin_role;
+pub use grant_admin_role::*;
 pub mod init_justies_program;
 pub use init_justies_program::*;
+pub mod migrate_allowlist_states;
+pub use migrate_allowlist_states::*;
+pub mod migrate_global_states;
+pub use migrate_global_states::*;
+pub mod propose_authority;
+pub use propose_authority::*;
+pub mod queue_config_changes;
+pub use queue_config_changes::*;
+pub mod reinstate_allowlist_entry;
+pub use reinstate_allowlist_entry::*;
+pub mod remove_allowlist_entry;
+pub use remove_allowlist_entry::*;
+pub mod revoke_admin_role;
+pub use revoke_admin_role::*;
+pub mod set_curated_creator;
+pub use set_curated_creator::*;
+pub mod set_currency_listing_limits;
+pub use set_currency_listing_limits::*;
+pub mod set_currency_market_fee_rate;
+pub use set_currency_market_fee_rate::*;
+pub mod set_fee_discounts;
+pub use set_fee_discounts::*;
+pub mod set_fee_tiers;
+pub use set_fee_tiers::*;
+pub mod set_feature_enabled;
+pub use set_feature_enabled::*;
+#[cfg(feature = "testing")]
 pub mod set_mock_timestamp;
+#[cfg(feature = "testing")]
 pub use set_mock_timestamp::*;
+pub mod set_nft_collection_listing_rules;
+pub use set_nft_collection_listing_rules::*;
+pub mod set_multisig;
+pub use set_multisig::*;
 pub mod state;
 pub use state::*;
+pub mod suspend_allowlist_entry;
+pub use suspend_allowlist_entry::*;
+pub mod unblock_wallet;
+pub use unblock_wallet::*;
 pub mod update_configs;
 pub use update_configs::*;
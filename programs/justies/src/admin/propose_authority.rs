@@ -0,0 +1,53 @@
+use crate::admin::{AuditField, AuditLog, ConfigChange, GlobalStates};
+use crate::common::{get_current_timestamp, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Proposes a new authority.
+///
+/// This is the 1st step of the two-step authority transfer. The proposed
+/// authority doesn't take effect until it calls accept_authority, so a typo in
+/// the new authority address can never lock the program. Proposing again
+/// overrides the previous pending authority.
+///
+/// Only callable by the authority.
+#[derive(Accounts)]
+#[instruction(
+    new_authority: Pubkey,
+)]
+pub struct ProposeAuthority<'info> {
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.authority == authority.key() @JustiesErrorCode::NotTheAuthority,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<ProposeAuthority>,
+  new_authority: Pubkey,
+) -> Result<()> {
+  let current_timestamp = get_current_timestamp(&ctx.accounts.global_states);
+  let global_states = &mut ctx.accounts.global_states;
+  let change = ConfigChange::new(
+    AuditField::PendingAuthority,
+    global_states.pending_authority,
+    Some(new_authority),
+  );
+  global_states.pending_authority = Some(new_authority);
+  ctx.accounts.audit_log.record(
+    change,
+    ctx.accounts.authority.key(),
+    current_timestamp,
+  );
+  Ok(())
+}
@@ -0,0 +1,156 @@
+use crate::admin::{
+  AdminRole, AuditField, AuditLog, ConfigChange, ConfigValue, GlobalStates,
+  PendingConfigs,
+};
+use crate::common::{
+  get_current_timestamp, ConfigsStrategy, FeeShareConfig, JustiesErrorCode,
+};
+use anchor_lang::prelude::*;
+
+/// The input type for the QueueConfigChanges instruction.
+///
+/// All fields are optional so that only non-empty values are updated at the
+/// global_states account once the changes are executed.
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, PartialEq)]
+pub struct TimelockedConfigsInput {
+  pub market_fee_rate_bps: Option<u16>,
+  pub fee_treasury_address: Option<Pubkey>,
+  pub config_timelock_delay: Option<u64>,
+  /// The split of the market fee, see global_states.fee_shares.
+  pub fee_shares: Option<Vec<FeeShareConfig>>,
+  pub listing_fee: Option<u64>,
+  pub listing_fee_token_mint: Option<Pubkey>,
+}
+
+impl TimelockedConfigsInput {
+  pub const MAX_DATA_SIZE: usize = (1 + 2)
+    + (1 + 32)
+    + (1 + 8)
+    + (1
+      + 4
+      + FeeShareConfig::MAX_DATA_SIZE * FeeShareConfig::MAX_FEE_TREASURIES)
+    + (1 + 8)
+    + (1 + 32);
+
+  /// The changes of the pending configs, i.e.: the queued values are set when
+  /// the input is queued (`queued` is true), and cleared once it is executed or
+  /// cancelled.
+  ///
+  /// The queued fee split is recorded per treasury, or as a single change
+  /// without the target when the split is cleared.
+  pub fn pending_config_changes(&self, queued: bool) -> Vec<ConfigChange> {
+    let pending_change = |field: AuditField, value: ConfigValue| match queued {
+      true => ConfigChange::new(field, ConfigValue::None, value),
+      false => ConfigChange::new(field, value, ConfigValue::None),
+    };
+    let pending_values: [(AuditField, Option<ConfigValue>); 5] = [
+      (
+        AuditField::PendingMarketFeeRateBps,
+        self.market_fee_rate_bps.map(Into::into),
+      ),
+      (
+        AuditField::PendingFeeTreasuryAddress,
+        self.fee_treasury_address.map(Into::into),
+      ),
+      (
+        AuditField::PendingConfigTimelockDelay,
+        self.config_timelock_delay.map(Into::into),
+      ),
+      (
+        AuditField::PendingListingFee,
+        self.listing_fee.map(Into::into),
+      ),
+      (
+        AuditField::PendingListingFeeTokenMint,
+        self.listing_fee_token_mint.map(Into::into),
+      ),
+    ];
+    let mut changes: Vec<ConfigChange> = pending_values
+      .into_iter()
+      .filter_map(|(field, value)| {
+        value.map(|value| pending_change(field, value))
+      })
+      .collect();
+    let fee_share_field = AuditField::PendingFeeShareBps;
+    match &self.fee_shares {
+      Some(fee_shares) if fee_shares.is_empty() => {
+        changes.push(pending_change(fee_share_field, 0_u16.into()));
+      }
+      Some(fee_shares) => {
+        changes.extend(fee_shares.iter().map(|config| {
+          pending_change(fee_share_field, config.share_bps.into())
+            .with_target(config.treasury)
+        }));
+      }
+      None => {}
+    }
+    changes
+  }
+}
+
+/// Queues changes to the timelocked configs (i.e.: the market fee rate, the fee
+/// treasury, the fee split, the listing fee & its token mint and the timelock
+/// delay itself).
+///
+/// The changes are validated when queued, and can be executed by anyone via
+/// execute_config_changes after global_states.config_timelock_delay, so that
+/// creators & bidders get notice before the fees change on live listings. Only
+/// one set of changes can be pending at a time.
+///
+/// Only callable by the treasury admin.
+#[derive(Accounts)]
+#[instruction(
+    input: TimelockedConfigsInput,
+)]
+pub struct QueueConfigChanges<'info> {
+  #[account(
+      init,
+      payer = authority,
+      space = 8 + PendingConfigs::MAX_DATA_SIZE,
+      seeds = [b"pending_configs"],
+      bump,
+  )]
+  pub pending_configs: Account<'info, PendingConfigs>,
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.has_role(AdminRole::TreasuryAdmin, authority.key()) @JustiesErrorCode::MissingAdminRole,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<QueueConfigChanges>,
+  input: TimelockedConfigsInput,
+) -> Result<()> {
+  // Validates the changes against a copy of the global states.
+  let mut updated_global_states = ctx.accounts.global_states.clone();
+  let mut configs_strategy = ConfigsStrategy::new(&mut updated_global_states);
+  configs_strategy.update_timelocked_configs(&input);
+  configs_strategy.validate()?;
+
+  let global_states = &ctx.accounts.global_states;
+  let current_timestamp = get_current_timestamp(global_states);
+  let changes = input.pending_config_changes(true);
+  let pending_configs = &mut ctx.accounts.pending_configs;
+  pending_configs.bump = *ctx.bumps.get("pending_configs").unwrap();
+  pending_configs.input = input;
+  pending_configs.proposer = ctx.accounts.authority.key();
+  pending_configs.activation_timestamp =
+    current_timestamp + global_states.config_timelock_delay as i64;
+  ctx.accounts.audit_log.record_all(
+    changes,
+    ctx.accounts.authority.key(),
+    current_timestamp,
+  );
+  Ok(())
+}
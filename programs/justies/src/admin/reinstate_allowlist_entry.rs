@@ -0,0 +1,56 @@
+use crate::admin::{
+  AdminRole, AllowlistType, AuditLog, ConfigChange, GlobalStates,
+  TokenAllowlistStates,
+};
+use crate::common::{get_current_timestamp, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Reinstates a suspended currency token or NFT collection in the allowlist.
+///
+/// Only callable by the allowlist curator.
+#[derive(Accounts)]
+#[instruction(
+    allowlist_type: AllowlistType,
+    token_mint_address: Pubkey,
+)]
+pub struct ReinstateAllowlistEntry<'info> {
+  #[account(
+      mut,
+      seeds = [allowlist_type.states_seed(), token_mint_address.as_ref()],
+      bump = allowlist_states.bump,
+      constraint = !allowlist_states.allowed @JustiesErrorCode::AllowlistEntryNotSuspended,
+  )]
+  pub allowlist_states: Account<'info, TokenAllowlistStates>,
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.has_role(AdminRole::AllowlistCurator, authority.key()) @JustiesErrorCode::MissingAdminRole,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<ReinstateAllowlistEntry>,
+  allowlist_type: AllowlistType,
+  token_mint_address: Pubkey,
+) -> Result<()> {
+  let allowlist_states = &mut ctx.accounts.allowlist_states;
+  allowlist_states.allowed = true;
+  allowlist_states.reason_code = None;
+
+  ctx.accounts.audit_log.record(
+    ConfigChange::new(allowlist_type.audit_field(), false, true)
+      .with_target(token_mint_address),
+    ctx.accounts.authority.key(),
+    get_current_timestamp(&ctx.accounts.global_states),
+  );
+  Ok(())
+}
@@ -0,0 +1,111 @@
+use crate::admin::{
+  AdminRole, AllowlistType, AuditLog, ConfigChange, ConfigValue, GlobalStates,
+  TokenAllowlistStates,
+};
+use crate::common::{
+  get_current_timestamp, unindex_pubkey, JustiesErrorCode, PubkeyIndexPage,
+};
+use anchor_lang::prelude::*;
+
+/// Removes a currency token or an NFT collection from the allowlist.
+///
+/// The allowlist states account is closed (with the rent returned to the
+/// signer), and its key is removed from the allowlist index, where the last
+/// indexed key takes its place. The index page holding the key (whose id is
+/// given) and the last index page need to be passed. The live auctions &
+/// raffles using the removed token behave the same as when it's suspended. The
+/// token can be allowlisted again via add_currency_token_to_allowlist or
+/// add_nft_collection_to_allowlist.
+///
+/// Only callable by the allowlist curator.
+#[derive(Accounts)]
+#[instruction(
+    allowlist_type: AllowlistType,
+    token_mint_address: Pubkey,
+    reason_code: Option<u16>,
+    index_page_id: u64,
+)]
+pub struct RemoveAllowlistEntry<'info> {
+  #[account(
+      mut,
+      close = authority,
+      seeds = [allowlist_type.states_seed(), token_mint_address.as_ref()],
+      bump = allowlist_states.bump,
+  )]
+  pub allowlist_states: Account<'info, TokenAllowlistStates>,
+  /// CHECK: the index page holding the key of the allowlist states, which is
+  /// checked when the key is removed.
+  #[account(
+      mut,
+      seeds = [allowlist_type.index_seed(), index_page_id.to_le_bytes().as_ref()],
+      bump,
+  )]
+  pub index_page: UncheckedAccount<'info>,
+  /// CHECK: the index page holding the last indexed key, whose address is
+  /// checked in the handler.
+  #[account(mut)]
+  pub last_index_page: UncheckedAccount<'info>,
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.has_role(AdminRole::AllowlistCurator, authority.key()) @JustiesErrorCode::MissingAdminRole,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<RemoveAllowlistEntry>,
+  allowlist_type: AllowlistType,
+  token_mint_address: Pubkey,
+  reason_code: Option<u16>,
+  _index_page_id: u64,
+) -> Result<()> {
+  // Removes the key from the allowlist index, so that the token is indexed only
+  // once if it's allowlisted again.
+  let global_states = &mut ctx.accounts.global_states;
+  let num_keys_per_index_page = global_states.num_keys_per_index_page;
+  let total_allowed = allowlist_type.total_allowed(global_states);
+  *total_allowed = total_allowed
+    .checked_sub(1)
+    .ok_or_else(|| error!(JustiesErrorCode::InvalidIndexPage))?;
+  let last_page_id =
+    PubkeyIndexPage::page_id(*total_allowed, num_keys_per_index_page);
+  let (last_index_page_key, _) = Pubkey::find_program_address(
+    &[
+      allowlist_type.index_seed(),
+      last_page_id.to_le_bytes().as_ref(),
+    ],
+    &crate::ID,
+  );
+  if ctx.accounts.last_index_page.key() != last_index_page_key {
+    return err!(JustiesErrorCode::InvalidIndexPage);
+  }
+  unindex_pubkey(
+    ctx.accounts.allowlist_states.key(),
+    &ctx.accounts.index_page,
+    &ctx.accounts.last_index_page,
+  )?;
+
+  ctx.accounts.audit_log.record(
+    ConfigChange::new(
+      allowlist_type.audit_field(),
+      ctx.accounts.allowlist_states.allowed,
+      ConfigValue::None,
+    )
+    .with_target(token_mint_address)
+    .with_reason_code(reason_code),
+    ctx.accounts.authority.key(),
+    get_current_timestamp(&ctx.accounts.global_states),
+  );
+  Ok(())
+}
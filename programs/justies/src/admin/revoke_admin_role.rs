@@ -0,0 +1,44 @@
+use crate::admin::{AdminRole, AuditLog, ConfigChange, GlobalStates};
+use crate::common::{get_current_timestamp, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Revokes an admin role from its holder.
+///
+/// The role is left unassigned until it's granted again.
+///
+/// Only callable by the authority.
+#[derive(Accounts)]
+#[instruction(
+    role: AdminRole,
+)]
+pub struct RevokeAdminRole<'info> {
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.authority == authority.key() @JustiesErrorCode::NotTheAuthority,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RevokeAdminRole>, role: AdminRole) -> Result<()> {
+  let current_timestamp = get_current_timestamp(&ctx.accounts.global_states);
+  let global_states = &mut ctx.accounts.global_states;
+  let change =
+    ConfigChange::new(role.into(), global_states.role_holder(role), None);
+  global_states.set_role_holder(role, None);
+  ctx.accounts.audit_log.record(
+    change,
+    ctx.accounts.authority.key(),
+    current_timestamp,
+  );
+  Ok(())
+}
@@ -0,0 +1,81 @@
+use crate::admin::{
+  AuditField, AuditLog, ConfigChange, ConfigValue, CreatorStates, GlobalStates,
+};
+use crate::common::{get_current_timestamp, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Sets whether a creator is curated, i.e.: approved to create auctions &
+/// raffles while the curated-creator mode (curated_creators_only) is on, and
+/// the cap on the number of the creator's live listings (None for no cap).
+///
+/// The cap applies no matter whether the curated-creator mode is on, and
+/// overrides the global max_live_listings_per_creator. The creator states can
+/// be looked up via the creator address.
+///
+/// Only callable by the authority.
+#[derive(Accounts)]
+#[instruction(creator_address: Pubkey)]
+pub struct SetCuratedCreator<'info> {
+  #[account(
+      init_if_needed,
+      payer = authority,
+      space = 8 + CreatorStates::MAX_DATA_SIZE,
+      seeds = [b"creator_states", creator_address.as_ref()],
+      bump,
+  )]
+  pub creator_states: Account<'info, CreatorStates>,
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.authority == authority.key() @JustiesErrorCode::NotTheAuthority,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<SetCuratedCreator>,
+  creator_address: Pubkey,
+  curated: bool,
+  max_live_listings: Option<u16>,
+) -> Result<()> {
+  let creator_states_bump = *ctx.bumps.get("creator_states").unwrap();
+  let creator_states = &mut ctx.accounts.creator_states;
+  creator_states.init_if_needed(creator_states_bump, creator_address);
+
+  let mut changes = vec![];
+  if creator_states.curated != curated {
+    changes.push(ConfigChange::new(
+      AuditField::CuratedCreator,
+      creator_states.curated,
+      curated,
+    ));
+  }
+  if creator_states.max_live_listings != max_live_listings {
+    changes.push(ConfigChange::new(
+      AuditField::CreatorMaxLiveListings,
+      ConfigValue::from_option(creator_states.max_live_listings),
+      ConfigValue::from_option(max_live_listings),
+    ));
+  }
+  creator_states.curated = curated;
+  creator_states.max_live_listings = max_live_listings;
+
+  ctx.accounts.audit_log.record_all(
+    changes
+      .into_iter()
+      .map(|change| change.with_target(creator_address))
+      .collect(),
+    ctx.accounts.authority.key(),
+    get_current_timestamp(&ctx.accounts.global_states),
+  );
+  Ok(())
+}
@@ -0,0 +1,88 @@
+use crate::admin::{
+  AdminRole, AuditField, AuditLog, ConfigChange, ConfigValue,
+  CurrencyListingLimits, GlobalStates, TokenAllowlistStates,
+};
+use crate::common::{get_current_timestamp, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Sets the listing limits of an allowlisted currency token (i.e.: minimum
+/// start bid, ticket price range and minimum outbid increment).
+///
+/// The limits are checked when creating auctions & raffles in the currency. The
+/// minimum outbid increment is captured by the auction when it's created, so
+/// the change doesn't apply to the live auctions.
+///
+/// Only callable by the config admin.
+#[derive(Accounts)]
+#[instruction(token_mint_address: Pubkey)]
+pub struct SetCurrencyListingLimits<'info> {
+  #[account(
+      mut,
+      seeds = [b"token_allowlist_states", token_mint_address.as_ref()],
+      bump = token_allowlist_states.bump,
+  )]
+  pub token_allowlist_states: Account<'info, TokenAllowlistStates>,
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.has_role(AdminRole::ConfigAdmin, authority.key()) @JustiesErrorCode::MissingAdminRole,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub authority: Signer<'info>,
+}
+
+pub fn handler(
+  ctx: Context<SetCurrencyListingLimits>,
+  token_mint_address: Pubkey,
+  listing_limits: CurrencyListingLimits,
+) -> Result<()> {
+  listing_limits.validate()?;
+
+  let old_limits = ctx.accounts.token_allowlist_states.listing_limits;
+  ctx.accounts.token_allowlist_states.listing_limits = listing_limits;
+
+  let mut changes = vec![];
+  if old_limits.min_start_bid != listing_limits.min_start_bid {
+    changes.push(ConfigChange::new(
+      AuditField::MinStartBid,
+      old_limits.min_start_bid,
+      listing_limits.min_start_bid,
+    ));
+  }
+  if old_limits.min_ticket_price != listing_limits.min_ticket_price {
+    changes.push(ConfigChange::new(
+      AuditField::MinTicketPrice,
+      old_limits.min_ticket_price,
+      listing_limits.min_ticket_price,
+    ));
+  }
+  if old_limits.max_ticket_price != listing_limits.max_ticket_price {
+    changes.push(ConfigChange::new(
+      AuditField::MaxTicketPrice,
+      ConfigValue::from_option(old_limits.max_ticket_price),
+      ConfigValue::from_option(listing_limits.max_ticket_price),
+    ));
+  }
+  if old_limits.min_outbid_increment != listing_limits.min_outbid_increment {
+    changes.push(ConfigChange::new(
+      AuditField::MinOutbidIncrement,
+      old_limits.min_outbid_increment,
+      listing_limits.min_outbid_increment,
+    ));
+  }
+  ctx.accounts.audit_log.record_all(
+    changes
+      .into_iter()
+      .map(|change| change.with_target(token_mint_address))
+      .collect(),
+    ctx.accounts.authority.key(),
+    get_current_timestamp(&ctx.accounts.global_states),
+  );
+  Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::admin::{
+  AdminRole, AuditField, AuditLog, ConfigChange, ConfigValue, GlobalStates,
+  TokenAllowlistStates,
+};
+use crate::common::{get_current_timestamp, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Sets (or unsets with None) the market fee rate of an allowlisted currency
+/// token, which overrides the global market fee rate when the revenues of the
+/// listings in the currency are claimed.
+///
+/// The rate can't exceed the global market fee rate, as the changes of the
+/// latter are timelocked while this one applies immediately. The override is
+/// also capped by the global rate at settlement.
+///
+/// Only callable by the treasury admin.
+#[derive(Accounts)]
+#[instruction(token_mint_address: Pubkey)]
+pub struct SetCurrencyMarketFeeRate<'info> {
+  #[account(
+      mut,
+      seeds = [b"token_allowlist_states", token_mint_address.as_ref()],
+      bump = token_allowlist_states.bump,
+  )]
+  pub token_allowlist_states: Account<'info, TokenAllowlistStates>,
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.has_role(AdminRole::TreasuryAdmin, authority.key()) @JustiesErrorCode::MissingAdminRole,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub authority: Signer<'info>,
+}
+
+pub fn handler(
+  ctx: Context<SetCurrencyMarketFeeRate>,
+  token_mint_address: Pubkey,
+  market_fee_rate_bps: Option<u16>,
+) -> Result<()> {
+  if market_fee_rate_bps.map_or(false, |rate_bps| {
+    rate_bps > ctx.accounts.global_states.market_fee_rate_bps
+  }) {
+    return err!(JustiesErrorCode::InvalidCurrencyMarketFeeRate);
+  }
+
+  let old_rate_bps = ctx.accounts.token_allowlist_states.market_fee_rate_bps;
+  ctx.accounts.token_allowlist_states.market_fee_rate_bps = market_fee_rate_bps;
+  ctx.accounts.audit_log.record(
+    ConfigChange::new(
+      AuditField::CurrencyMarketFeeRateBps,
+      ConfigValue::from_option(old_rate_bps),
+      ConfigValue::from_option(market_fee_rate_bps),
+    )
+    .with_target(token_mint_address),
+    ctx.accounts.authority.key(),
+    get_current_timestamp(&ctx.accounts.global_states),
+  );
+  Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::admin::{
+  AdminRole, AuditField, AuditLog, ConfigChange, Feature, GlobalStates,
+};
+use crate::common::{get_current_timestamp, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Enables or disables a feature (i.e.: a listing type or mode, or the creation
+/// of a listing type) on the cluster.
+///
+/// The instructions of a disabled feature fail with the "FeatureDisabled" error
+/// naming the feature.
+///
+/// Only callable by the config admin.
+#[derive(Accounts)]
+pub struct SetFeatureEnabled<'info> {
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.has_role(AdminRole::ConfigAdmin, authority.key()) @JustiesErrorCode::MissingAdminRole,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<SetFeatureEnabled>,
+  feature: Feature,
+  enabled: bool,
+) -> Result<()> {
+  let global_states = &mut ctx.accounts.global_states;
+  let old_features = global_states.features;
+  global_states.set_feature_enabled(feature, enabled);
+  if global_states.features != old_features {
+    ctx.accounts.audit_log.record(
+      ConfigChange::new(
+        AuditField::Features,
+        old_features,
+        global_states.features,
+      ),
+      ctx.accounts.authority.key(),
+      get_current_timestamp(global_states),
+    );
+  }
+  Ok(())
+}
@@ -0,0 +1,97 @@
+use crate::admin::{
+  AdminRole, AuditField, AuditLog, ConfigChange, ConfigValue, GlobalStates,
+};
+use crate::common::{
+  get_current_timestamp, FeeDiscountConfig, GroupType, JustiesErrorCode,
+};
+use anchor_lang::prelude::*;
+
+/// Sets the market fee discounts for the creators in the groups (e.g.: 50% off
+/// for the holders of a membership collection), or clears them with empty
+/// discounts.
+///
+/// Each discount must be within (0, 10000] bps, without duplicated groups. Only
+/// the NFT & token holder groups are supported. The discounts apply immediately
+/// (unlike the global market fee rate), as they can only lower the fees paid by
+/// the creators.
+///
+/// Only callable by the treasury admin.
+#[derive(Accounts)]
+pub struct SetFeeDiscounts<'info> {
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.has_role(AdminRole::TreasuryAdmin, authority.key()) @JustiesErrorCode::MissingAdminRole,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub authority: Signer<'info>,
+}
+
+pub fn handler(
+  ctx: Context<SetFeeDiscounts>,
+  fee_discounts: Vec<FeeDiscountConfig>,
+) -> Result<()> {
+  if fee_discounts.len() > FeeDiscountConfig::MAX_FEE_DISCOUNTS {
+    return err!(JustiesErrorCode::InvalidFeeDiscounts);
+  }
+  let is_valid = fee_discounts.iter().enumerate().all(|(idx, config)| {
+    config.discount_bps > 0
+      && config.discount_bps <= 10000
+      && config.group.group_type != GroupType::OffChainNftGroup
+      && !fee_discounts[..idx]
+        .iter()
+        .any(|other| other.group.key == config.group.key)
+  });
+  if !is_valid {
+    return err!(JustiesErrorCode::InvalidFeeDiscounts);
+  }
+
+  // Records the discount changes of each group, where the discount of a group
+  // out of the configs is ConfigValue::None.
+  let discount_bps_of = |discounts: &Vec<FeeDiscountConfig>, key: Pubkey| {
+    discounts
+      .iter()
+      .find(|config| config.group.key == key)
+      .map(|config| config.discount_bps)
+  };
+  let old_fee_discounts = ctx.accounts.global_states.fee_discounts.clone();
+  let mut group_keys: Vec<Pubkey> = old_fee_discounts
+    .iter()
+    .map(|config| config.group.key)
+    .collect();
+  for config in fee_discounts.iter() {
+    if !group_keys.contains(&config.group.key) {
+      group_keys.push(config.group.key);
+    }
+  }
+  let changes = group_keys
+    .into_iter()
+    .filter_map(|key| {
+      let old_discount_bps = discount_bps_of(&old_fee_discounts, key);
+      let new_discount_bps = discount_bps_of(&fee_discounts, key);
+      (old_discount_bps != new_discount_bps).then(|| {
+        ConfigChange::new(
+          AuditField::FeeDiscountBps,
+          ConfigValue::from_option(old_discount_bps),
+          ConfigValue::from_option(new_discount_bps),
+        )
+        .with_target(key)
+      })
+    })
+    .collect();
+
+  ctx.accounts.global_states.fee_discounts = fee_discounts;
+  ctx.accounts.audit_log.record_all(
+    changes,
+    ctx.accounts.authority.key(),
+    get_current_timestamp(&ctx.accounts.global_states),
+  );
+  Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::admin::{
+  AdminRole, AuditField, AuditLog, ConfigChange, ConfigValue, GlobalStates,
+};
+use crate::common::{get_current_timestamp, FeeTierConfig, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Sets the market fee tiers by the creators' settled volumes, or clears them
+/// with empty tiers so that the flat market fee rate applies to everyone.
+///
+/// The volume thresholds must be in the ascending order, with the rates in the
+/// non-ascending order and not exceeding the global market fee rate. The tiers
+/// apply immediately (unlike the global market fee rate), as they can only
+/// lower the fees paid by the creators.
+///
+/// Only callable by the treasury admin.
+#[derive(Accounts)]
+pub struct SetFeeTiers<'info> {
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.has_role(AdminRole::TreasuryAdmin, authority.key()) @JustiesErrorCode::MissingAdminRole,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub authority: Signer<'info>,
+}
+
+pub fn handler(
+  ctx: Context<SetFeeTiers>,
+  fee_tiers: Vec<FeeTierConfig>,
+) -> Result<()> {
+  if fee_tiers.len() > FeeTierConfig::MAX_FEE_TIERS {
+    return err!(JustiesErrorCode::InvalidFeeTiers);
+  }
+  let market_fee_rate_bps = ctx.accounts.global_states.market_fee_rate_bps;
+  let is_ordered = fee_tiers.windows(2).all(|pair| {
+    pair[0].min_volume < pair[1].min_volume
+      && pair[0].market_fee_rate_bps >= pair[1].market_fee_rate_bps
+  });
+  if !is_ordered
+    || fee_tiers
+      .iter()
+      .any(|fee_tier| fee_tier.market_fee_rate_bps > market_fee_rate_bps)
+  {
+    return err!(JustiesErrorCode::InvalidFeeTiers);
+  }
+
+  // Records the changed tiers by their positions, where a missing tier is
+  // ConfigValue::None.
+  let old_fee_tiers = ctx.accounts.global_states.fee_tiers.clone();
+  let mut changes = vec![];
+  for idx in 0..std::cmp::max(old_fee_tiers.len(), fee_tiers.len()) {
+    let old_fee_tier = old_fee_tiers.get(idx);
+    let new_fee_tier = fee_tiers.get(idx);
+    if old_fee_tier == new_fee_tier {
+      continue;
+    }
+    changes.push(ConfigChange::new(
+      AuditField::FeeTierMinVolume,
+      ConfigValue::from_option(old_fee_tier.map(|tier| tier.min_volume)),
+      ConfigValue::from_option(new_fee_tier.map(|tier| tier.min_volume)),
+    ));
+    changes.push(ConfigChange::new(
+      AuditField::FeeTierRateBps,
+      ConfigValue::from_option(
+        old_fee_tier.map(|tier| tier.market_fee_rate_bps),
+      ),
+      ConfigValue::from_option(
+        new_fee_tier.map(|tier| tier.market_fee_rate_bps),
+      ),
+    ));
+  }
+
+  ctx.accounts.global_states.fee_tiers = fee_tiers;
+  ctx.accounts.audit_log.record_all(
+    changes,
+    ctx.accounts.authority.key(),
+    get_current_timestamp(&ctx.accounts.global_states),
+  );
+  Ok(())
+}
@@ -4,14 +4,17 @@ use anchor_lang::prelude::*;
 
 /// Sets mock timestamp.
 ///
-/// This is only callable when is_test_environment == true.
+/// This is only callable when is_test_environment == true, and the program is
+/// built with the "testing" feature.
 ///
 /// The reason to add this is that it is necessary to forward the timestamp to
 /// testing time-related smart contract behaviors. However solana test validator
 /// doesn't provide a feasible way to mock the timestamp. It is a walk around to
 /// support timestamp mocking at the application-level.
 ///
-/// Only callable by the authority.
+/// Only callable by the authority. The instruction is only compiled into the
+/// builds with the "testing" feature, and is dispatched via the fallback of the
+/// program (see common/testing_instructions.rs).
 #[derive(Accounts)]
 #[instruction(
     mock_timestamp: Option<i64>,
@@ -21,7 +24,7 @@ pub struct SetMockTimestamp<'info> {
       mut,
       seeds = [b"global_states"],
       bump = global_states.bump,
-      constraint = global_states.is_test_environment == true @JustiesErrorCode::NotTestEnvironment,
+      constraint = global_states.is_test_mode() @JustiesErrorCode::NotTestEnvironment,
       constraint = global_states.authority == authority.key() @JustiesErrorCode::NotTheAuthority,
   )]
   pub global_states: Account<'info, GlobalStates>,
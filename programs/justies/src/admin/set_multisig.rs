@@ -0,0 +1,99 @@
+use crate::admin::{AuditField, AuditLog, ConfigChange, GlobalStates};
+use crate::common::{get_current_timestamp, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Sets the members and the threshold of the built-in multisig.
+///
+/// The multisig acts on-chain as the multisig_authority pda. Once the pda is
+/// made the authority (via propose_authority and an admin proposal calling
+/// accept_authority), or granted any admin role, the corresponding admin
+/// instructions can only be called via admin proposals approved by at least
+/// `threshold` members.
+///
+/// Setting an empty member list with a zero threshold disables the multisig.
+/// Changing the members clears the approvals of all the pending admin
+/// proposals.
+///
+/// Only callable by the authority.
+#[derive(Accounts)]
+#[instruction(
+    members: Vec<Pubkey>,
+    threshold: u8,
+)]
+pub struct SetMultisig<'info> {
+  #[account(
+      mut,
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.authority == authority.key() @JustiesErrorCode::NotTheAuthority,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<SetMultisig>,
+  members: Vec<Pubkey>,
+  threshold: u8,
+) -> Result<()> {
+  if members.len() > GlobalStates::MAX_MULTISIG_MEMBERS
+    || threshold as usize > members.len()
+    || (threshold == 0 && !members.is_empty())
+  {
+    return err!(JustiesErrorCode::InvalidMultisigSettings);
+  }
+  for (idx, member) in members.iter().enumerate() {
+    if members[..idx].contains(member) {
+      return err!(JustiesErrorCode::InvalidMultisigSettings);
+    }
+  }
+
+  let current_timestamp = get_current_timestamp(&ctx.accounts.global_states);
+  let global_states = &mut ctx.accounts.global_states;
+  let mut changes = vec![];
+  for removed_member in global_states
+    .multisig_members
+    .iter()
+    .filter(|member| !members.contains(member))
+  {
+    changes.push(
+      ConfigChange::new(AuditField::MultisigMember, true, false)
+        .with_target(*removed_member),
+    );
+  }
+  for added_member in members
+    .iter()
+    .filter(|member| !global_states.is_multisig_member(**member))
+  {
+    changes.push(
+      ConfigChange::new(AuditField::MultisigMember, false, true)
+        .with_target(*added_member),
+    );
+  }
+  // Any change of the members invalidates the approvals given so far.
+  if !changes.is_empty() {
+    global_states.multisig_nonce += 1;
+  }
+  if global_states.multisig_threshold != threshold {
+    changes.push(ConfigChange::new(
+      AuditField::MultisigThreshold,
+      global_states.multisig_threshold,
+      threshold,
+    ));
+  }
+  global_states.multisig_members = members;
+  global_states.multisig_threshold = threshold;
+  ctx.accounts.audit_log.record_all(
+    changes,
+    ctx.accounts.authority.key(),
+    current_timestamp,
+  );
+  Ok(())
+}
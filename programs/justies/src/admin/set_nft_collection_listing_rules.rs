@@ -0,0 +1,138 @@
+use crate::admin::{
+  AdminRole, AuditField, AuditLog, ConfigChange, ConfigValue, GlobalStates,
+  ListingRuleOverrides, TokenAllowlistStates,
+};
+use crate::common::{get_current_timestamp, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Sets the overrides of the global listing rules (i.e.: market fee rate,
+/// auction & raffle duration ranges and max raffled NFTs) for an allowlisted
+/// NFT collection. The rules that are None fall back to the global configs.
+///
+/// The duration ranges and max raffled NFTs are checked when creating auctions
+/// & raffles of the NFTs in the collection, while the market fee rate applies
+/// when the revenues are claimed (taking precedence over the currency's one).
+///
+/// Changing the market fee rate requires the treasury admin role, and changing
+/// the other rules requires the config admin role.
+#[derive(Accounts)]
+#[instruction(collection_mint_address: Pubkey)]
+pub struct SetNftCollectionListingRules<'info> {
+  #[account(
+      mut,
+      seeds = [b"nft_allowlist_states", collection_mint_address.as_ref()],
+      bump = nft_allowlist_states.bump,
+  )]
+  pub nft_allowlist_states: Account<'info, TokenAllowlistStates>,
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  pub authority: Signer<'info>,
+}
+
+pub fn handler(
+  ctx: Context<SetNftCollectionListingRules>,
+  collection_mint_address: Pubkey,
+  listing_rules: ListingRuleOverrides,
+) -> Result<()> {
+  let global_states = &ctx.accounts.global_states;
+  let signer = ctx.accounts.authority.key();
+  let old_rules = ctx.accounts.nft_allowlist_states.listing_rule_overrides;
+
+  let updates_fee_rate =
+    listing_rules.market_fee_rate_bps != old_rules.market_fee_rate_bps;
+  let updates_other_rules = ListingRuleOverrides {
+    market_fee_rate_bps: old_rules.market_fee_rate_bps,
+    ..listing_rules
+  } != old_rules;
+  if updates_fee_rate
+    && !global_states.has_role(AdminRole::TreasuryAdmin, signer)
+  {
+    return err!(JustiesErrorCode::MissingAdminRole);
+  }
+  // Same as the global configs, submitting no changes requires the config
+  // admin role.
+  if (updates_other_rules || !updates_fee_rate)
+    && !global_states.has_role(AdminRole::ConfigAdmin, signer)
+  {
+    return err!(JustiesErrorCode::MissingAdminRole);
+  }
+  listing_rules.validate(global_states)?;
+
+  let mut changes = vec![];
+  push_change(
+    &mut changes,
+    AuditField::MarketFeeRateBps,
+    old_rules.market_fee_rate_bps,
+    listing_rules.market_fee_rate_bps,
+  );
+  push_change(
+    &mut changes,
+    AuditField::MinAuctionDuration,
+    old_rules.min_auction_duration,
+    listing_rules.min_auction_duration,
+  );
+  push_change(
+    &mut changes,
+    AuditField::MaxAuctionDuration,
+    old_rules.max_auction_duration,
+    listing_rules.max_auction_duration,
+  );
+  push_change(
+    &mut changes,
+    AuditField::MinRaffleDuration,
+    old_rules.min_raffle_duration,
+    listing_rules.min_raffle_duration,
+  );
+  push_change(
+    &mut changes,
+    AuditField::MaxRaffleDuration,
+    old_rules.max_raffle_duration,
+    listing_rules.max_raffle_duration,
+  );
+  push_change(
+    &mut changes,
+    AuditField::MaxRaffledNfts,
+    old_rules.max_raffled_nfts,
+    listing_rules.max_raffled_nfts,
+  );
+
+  let current_timestamp = get_current_timestamp(global_states);
+  ctx.accounts.nft_allowlist_states.listing_rule_overrides = listing_rules;
+  ctx.accounts.audit_log.record_all(
+    changes
+      .into_iter()
+      .map(|change| change.with_target(collection_mint_address))
+      .collect(),
+    signer,
+    current_timestamp,
+  );
+  Ok(())
+}
+
+/// Collects the change of an overridden rule when the value is actually
+/// changed.
+fn push_change<T>(
+  changes: &mut Vec<ConfigChange>,
+  field: AuditField,
+  old_value: Option<T>,
+  new_value: Option<T>,
+) where
+  T: Into<ConfigValue> + PartialEq,
+{
+  if old_value != new_value {
+    changes.push(ConfigChange::new(
+      field,
+      ConfigValue::from_option(old_value),
+      ConfigValue::from_option(new_value),
+    ));
+  }
+}
@@ -1,3 +1,10 @@
+use crate::admin::TimelockedConfigsInput;
+use crate::auction::Auction;
+use crate::common::{
+  is_account_initialized, FeeDiscountConfig, FeeShareConfig, FeeTierConfig,
+  JustiesErrorCode, ListingStatus, ListingType,
+};
+use crate::raffle::Raffle;
 use anchor_lang::prelude::*;
 
 #[account]
@@ -6,10 +13,535 @@ pub struct TokenAllowlistStates {
   pub bump: u8,
   pub token_mint_address: Pubkey,
   pub allowed: bool,
+  /// The reason code of the suspension (if any).
+  pub reason_code: Option<u16>,
+  /// The listing limits in the currency token (only for the currency tokens).
+  pub listing_limits: CurrencyListingLimits,
+  /// The market fee rate overriding the global one for the listings in the
+  /// currency token (only for the currency tokens).
+  pub market_fee_rate_bps: Option<u16>,
+  /// The overrides of the global listing rules (only for the NFT collections).
+  pub listing_rule_overrides: ListingRuleOverrides,
 }
 
 impl TokenAllowlistStates {
-  pub const MAX_DATA_SIZE: usize = 1 + 32 + 1;
+  pub const MAX_DATA_SIZE: usize = 1
+    + 32
+    + 1
+    + (1 + 2)
+    + CurrencyListingLimits::MAX_DATA_SIZE
+    + (1 + 2)
+    + ListingRuleOverrides::MAX_DATA_SIZE;
+
+  /// Whether the token is allowed by the given allowlist states account. The
+  /// removed entries (i.e.: closed accounts) are not allowed.
+  pub fn is_allowed(allowlist_states: &AccountInfo) -> Result<bool> {
+    if !is_account_initialized(allowlist_states) {
+      return Ok(false);
+    }
+    let allowlist_states: Account<TokenAllowlistStates> =
+      Account::try_from(allowlist_states)?;
+    Ok(allowlist_states.allowed)
+  }
+
+  /// The market fee rate override of the currency token in the given allowlist
+  /// states account. The removed entries (i.e.: closed accounts) have no
+  /// override.
+  pub fn market_fee_rate_override(
+    allowlist_states: &AccountInfo,
+  ) -> Result<Option<u16>> {
+    if !is_account_initialized(allowlist_states) {
+      return Ok(None);
+    }
+    let allowlist_states: Account<TokenAllowlistStates> =
+      Account::try_from(allowlist_states)?;
+    Ok(allowlist_states.market_fee_rate_bps)
+  }
+
+  /// The listing rule overrides of the NFTs in the given allowlist states
+  /// account. The removed entries (i.e.: closed accounts) have no overrides.
+  pub fn listing_rule_overrides(
+    allowlist_states: &AccountInfo,
+  ) -> Result<ListingRuleOverrides> {
+    if !is_account_initialized(allowlist_states) {
+      return Ok(ListingRuleOverrides::default());
+    }
+    let allowlist_states: Account<TokenAllowlistStates> =
+      Account::try_from(allowlist_states)?;
+    Ok(allowlist_states.listing_rule_overrides)
+  }
+}
+
+/// The limits on the listings in a currency token, which prevent the spam &
+/// dust listings. All the amounts are in the currency token's base unit.
+#[derive(
+  AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default,
+)]
+pub struct CurrencyListingLimits {
+  /// The minimum start bid of auctions.
+  pub min_start_bid: u64,
+  /// The minimum ticket price of raffles.
+  pub min_ticket_price: u64,
+  /// The maximum ticket price of raffles (None for no limit).
+  pub max_ticket_price: Option<u64>,
+  /// The minimum increment of an outbid over the top bid, which applies on top
+  /// of the global minimum outbid rate.
+  pub min_outbid_increment: u64,
+}
+
+impl CurrencyListingLimits {
+  pub const MAX_DATA_SIZE: usize = 8 + 8 + (1 + 8) + 8;
+
+  pub fn validate(&self) -> Result<()> {
+    if self.max_ticket_price.map_or(false, |max_ticket_price| {
+      max_ticket_price < self.min_ticket_price
+    }) {
+      return err!(JustiesErrorCode::InvalidCurrencyListingLimits);
+    }
+    Ok(())
+  }
+}
+
+/// The overrides of the global listing rules for the NFTs in a collection
+/// (e.g.: the negotiated terms with partner collections). The global configs
+/// apply to the rules that are not overridden (i.e.: None).
+#[derive(
+  AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default,
+)]
+pub struct ListingRuleOverrides {
+  /// The market fee rate, which can't exceed the global one.
+  pub market_fee_rate_bps: Option<u16>,
+  pub min_auction_duration: Option<u64>,
+  pub max_auction_duration: Option<u64>,
+  pub min_raffle_duration: Option<u64>,
+  pub max_raffle_duration: Option<u64>,
+  pub max_raffled_nfts: Option<u8>,
+}
+
+impl ListingRuleOverrides {
+  pub const MAX_DATA_SIZE: usize = (1 + 2) + (1 + 8) * 4 + (1 + 1);
+
+  pub fn auction_duration_range(
+    &self,
+    global_states: &GlobalStates,
+  ) -> (u64, u64) {
+    (
+      self
+        .min_auction_duration
+        .unwrap_or(global_states.min_auction_duration),
+      self
+        .max_auction_duration
+        .unwrap_or(global_states.max_auction_duration),
+    )
+  }
+
+  pub fn raffle_duration_range(
+    &self,
+    global_states: &GlobalStates,
+  ) -> (u64, u64) {
+    (
+      self
+        .min_raffle_duration
+        .unwrap_or(global_states.min_raffle_duration),
+      self
+        .max_raffle_duration
+        .unwrap_or(global_states.max_raffle_duration),
+    )
+  }
+
+  pub fn max_raffled_nfts(&self, global_states: &GlobalStates) -> u8 {
+    self
+      .max_raffled_nfts
+      .unwrap_or(global_states.max_raffled_nfts)
+  }
+
+  /// Validates the overrides in combination with the global configs.
+  pub fn validate(&self, global_states: &GlobalStates) -> Result<()> {
+    if self.market_fee_rate_bps.map_or(false, |rate_bps| {
+      rate_bps > global_states.market_fee_rate_bps
+    }) {
+      return err!(JustiesErrorCode::InvalidCollectionMarketFeeRate);
+    }
+    let (min_auction_duration, max_auction_duration) =
+      self.auction_duration_range(global_states);
+    if min_auction_duration == 0 || min_auction_duration >= max_auction_duration
+    {
+      return err!(JustiesErrorCode::InvalidAuctionDurationRangeSettings);
+    }
+    let (min_raffle_duration, max_raffle_duration) =
+      self.raffle_duration_range(global_states);
+    if min_raffle_duration == 0 || min_raffle_duration >= max_raffle_duration {
+      return err!(JustiesErrorCode::InvalidRaffleDurationRangeSettings);
+    }
+    if self.max_raffled_nfts(global_states) == 0 {
+      return err!(JustiesErrorCode::InvalidCollectionListingRules);
+    }
+    Ok(())
+  }
+}
+
+/// The blocklist entry of a wallet (e.g.: a sanctioned or abusive address),
+/// which exists only while the wallet is blocked.
+#[account]
+#[derive(Default)]
+pub struct WalletBlocklistStates {
+  pub bump: u8,
+  pub wallet_address: Pubkey,
+  /// The reason code of the blocking (if any).
+  pub reason_code: Option<u16>,
+}
+
+impl WalletBlocklistStates {
+  pub const MAX_DATA_SIZE: usize = 1 + 32 + (1 + 2);
+
+  /// Whether the wallet of the given blocklist states account is blocked. The
+  /// unblocked wallets have no (i.e.: closed) accounts.
+  ///
+  /// Only an account created by the program counts, as anyone can fund the
+  /// blocklist states address of a wallet, which unblock_wallet can't undo.
+  pub fn is_blocked(blocklist_states: &AccountInfo) -> bool {
+    blocklist_states.owner == &crate::ID
+      && WalletBlocklistStates::try_deserialize(
+        &mut &blocklist_states.data.borrow()[..],
+      )
+      .is_ok()
+  }
+}
+
+/// The per-creator states, which are created upon the creator's first listing
+/// (or when the creator is curated by the authority).
+#[account]
+#[derive(Default)]
+pub struct CreatorStates {
+  pub bump: u8,
+  pub creator: Pubkey,
+  /// Whether the creator is approved to list in the curated-creator mode.
+  pub curated: bool,
+  /// The cap on the number of the creator's live listings (if any), which
+  /// overrides the global max_live_listings_per_creator.
+  pub max_live_listings: Option<u16>,
+  /// The number of the creator's live auctions & raffles, i.e.: those neither
+  /// cancelled nor finished.
+  pub num_live_auctions: u16,
+  pub num_live_raffles: u16,
+}
+
+impl CreatorStates {
+  pub const MAX_DATA_SIZE: usize = 1 + 32 + 1 + (1 + 2) + 2 + 2;
+
+  /// Sets the bump & the creator when the account is newly created via
+  /// init_if_needed.
+  pub fn init_if_needed(&mut self, bump: u8, creator: Pubkey) {
+    if self.creator == Pubkey::default() {
+      self.bump = bump;
+      self.creator = creator;
+    }
+  }
+
+  pub fn num_live_listings(&self) -> u16 {
+    self.num_live_auctions.saturating_add(self.num_live_raffles)
+  }
+
+  /// The cap on the number of the creator's live listings, where the
+  /// creator's own cap overrides the global one (if any).
+  pub fn live_listings_cap(&self, global_states: &GlobalStates) -> Option<u16> {
+    self.max_live_listings.or(
+      match global_states.max_live_listings_per_creator {
+        0 => None,
+        max_live_listings => Some(max_live_listings),
+      },
+    )
+  }
+
+  /// Checks that the creator can open a new listing, i.e.: the creator is
+  /// curated (in the curated-creator mode) and below the cap on live listings.
+  pub fn validate_new_listing(
+    &self,
+    global_states: &GlobalStates,
+  ) -> Result<()> {
+    if global_states.curated_creators_only && !self.curated {
+      return err!(JustiesErrorCode::CreatorNotCurated);
+    }
+    if self
+      .live_listings_cap(global_states)
+      .map_or(false, |max_live_listings| {
+        self.num_live_listings() >= max_live_listings
+      })
+    {
+      return err!(JustiesErrorCode::TooManyLiveListings);
+    }
+    Ok(())
+  }
+
+  /// Removes the auction from the creator's live listings once it's finalized
+  /// (i.e.: no longer in progress). The auctions not counted upon creation
+  /// (i.e.: created before the creator states existed) or already removed are
+  /// skipped.
+  pub fn remove_live_auction(&mut self, auction: &mut Auction) -> Result<()> {
+    if auction.counted_as_live && auction.status != ListingStatus::InProgress {
+      self.num_live_auctions = self
+        .num_live_auctions
+        .checked_sub(1)
+        .ok_or_else(|| error!(JustiesErrorCode::InconsistentCreatorStates))?;
+      auction.counted_as_live = false;
+    }
+    Ok(())
+  }
+
+  /// The same as remove_live_auction, for raffles.
+  pub fn remove_live_raffle(&mut self, raffle: &mut Raffle) -> Result<()> {
+    if raffle.counted_as_live && raffle.status != ListingStatus::InProgress {
+      self.num_live_raffles = self
+        .num_live_raffles
+        .checked_sub(1)
+        .ok_or_else(|| error!(JustiesErrorCode::InconsistentCreatorStates))?;
+      raffle.counted_as_live = false;
+    }
+    Ok(())
+  }
+
+  /// Removes the finalized auction from the creator's live listings on the
+  /// paths not signed by the creator (e.g.: the bidders' refunds & claims), so
+  /// that their signers never pay rent for the creator states.
+  ///
+  /// The creator_states pda is only required (as the first of
+  /// "remaining_accounts") when the auction is still counted as live, i.e.: not
+  /// for the auctions created before the live listings were counted.
+  pub fn release_live_auction(
+    auction: &mut Auction,
+    remaining_accounts: &[AccountInfo],
+  ) -> Result<()> {
+    if !auction.counted_as_live || auction.status == ListingStatus::InProgress {
+      return Ok(());
+    }
+    let mut creator_states =
+      Self::from_remaining_accounts(auction.creator, remaining_accounts)?;
+    creator_states.remove_live_auction(auction)?;
+    creator_states.exit(&crate::ID)
+  }
+
+  /// The same as release_live_auction, for raffles.
+  pub fn release_live_raffle(
+    raffle: &mut Raffle,
+    remaining_accounts: &[AccountInfo],
+  ) -> Result<()> {
+    if !raffle.counted_as_live || raffle.status == ListingStatus::InProgress {
+      return Ok(());
+    }
+    let mut creator_states =
+      Self::from_remaining_accounts(raffle.creator, remaining_accounts)?;
+    creator_states.remove_live_raffle(raffle)?;
+    creator_states.exit(&crate::ID)
+  }
+
+  /// Loads the creator's creator_states pda from the first of
+  /// "remaining_accounts".
+  fn from_remaining_accounts<'info>(
+    creator: Pubkey,
+    remaining_accounts: &[AccountInfo<'info>],
+  ) -> Result<Account<'info, CreatorStates>> {
+    let (creator_states_address, _) = Pubkey::find_program_address(
+      &[b"creator_states", creator.as_ref()],
+      &crate::ID,
+    );
+    match remaining_accounts.first() {
+      Some(account_info)
+        if account_info.key() == creator_states_address
+          && account_info.is_writable =>
+      {
+        Account::try_from(account_info)
+      }
+      _ => err!(JustiesErrorCode::InvalidCreatorStatesAccount),
+    }
+  }
+}
+
+/// The protocol fee ledger of a currency token, which is updated upon each
+/// revenue settlement (i.e.: claim_auction_revenue & claim_raffle_revenue), so
+/// that the fee treasury token accounts can be reconciled against the on-chain
+/// totals.
+#[account]
+#[derive(Default)]
+pub struct CurrencyStats {
+  pub bump: u8,
+  pub token_mint_address: Pubkey,
+  /// The total market fees collected (across all the fee treasuries).
+  pub total_fees: u64,
+  /// The total gross volume, i.e.: the settled revenues including the fees.
+  pub total_volume: u64,
+  pub num_settled_auctions: u64,
+  pub num_settled_raffles: u64,
+}
+
+impl CurrencyStats {
+  pub const MAX_DATA_SIZE: usize = 1 + 32 + 8 + 8 + 8 + 8;
+
+  /// Sets the bump & the token mint when the account is newly created via
+  /// init_if_needed.
+  pub fn init_if_needed(&mut self, bump: u8, token_mint_address: Pubkey) {
+    if self.token_mint_address == Pubkey::default() {
+      self.bump = bump;
+      self.token_mint_address = token_mint_address;
+    }
+  }
+
+  pub fn record_settlement(
+    &mut self,
+    listing_type: ListingType,
+    volume: u64,
+    fees: u64,
+  ) {
+    self.total_volume = self.total_volume.saturating_add(volume);
+    self.total_fees = self.total_fees.saturating_add(fees);
+    match listing_type {
+      ListingType::Auction => self.num_settled_auctions += 1,
+      ListingType::Raffle => self.num_settled_raffles += 1,
+    }
+  }
+}
+
+/// The settled volume of a creator in a currency token, which is updated upon
+/// each revenue settlement and determines the creator's market fee tier (see
+/// global_states.fee_tiers).
+#[account]
+#[derive(Default)]
+pub struct CreatorVolume {
+  pub bump: u8,
+  pub creator: Pubkey,
+  pub token_mint_address: Pubkey,
+  /// The total gross volume, i.e.: the settled revenues including the fees.
+  pub total_volume: u64,
+}
+
+impl CreatorVolume {
+  pub const MAX_DATA_SIZE: usize = 1 + 32 + 32 + 8;
+
+  /// Sets the bump, the creator & the token mint when the account is newly
+  /// created via init_if_needed.
+  pub fn init_if_needed(
+    &mut self,
+    bump: u8,
+    creator: Pubkey,
+    token_mint_address: Pubkey,
+  ) {
+    if self.creator == Pubkey::default() {
+      self.bump = bump;
+      self.creator = creator;
+      self.token_mint_address = token_mint_address;
+    }
+  }
+
+  pub fn record_settlement(&mut self, volume: u64) {
+    self.total_volume = self.total_volume.saturating_add(volume);
+  }
+}
+
+/// The allowlists of tokens.
+#[derive(
+  AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default,
+)]
+pub enum AllowlistType {
+  CurrencyToken,
+  #[default]
+  NftCollection,
+  // Individual NFTs without a verified collection (e.g.: 1/1 arts).
+  NftMint,
+  // NFTs verified by the creator, which is how many legacy collections are
+  // identified.
+  NftCreator,
+}
+
+impl AllowlistType {
+  /// The seed of the allowlist states pda.
+  pub fn states_seed(&self) -> &'static [u8] {
+    match self {
+      AllowlistType::CurrencyToken => b"token_allowlist_states",
+      AllowlistType::NftCollection => b"nft_allowlist_states",
+      AllowlistType::NftMint => b"nft_mint_allowlist_states",
+      AllowlistType::NftCreator => b"nft_creator_allowlist_states",
+    }
+  }
+
+  /// The seed of the allowlist index pages.
+  pub fn index_seed(&self) -> &'static [u8] {
+    match self {
+      AllowlistType::CurrencyToken => b"token_allowlist_index",
+      AllowlistType::NftCollection => b"nft_allowlist_index",
+      AllowlistType::NftMint => b"nft_mint_allowlist_index",
+      AllowlistType::NftCreator => b"nft_creator_allowlist_index",
+    }
+  }
+
+  /// The number of the allowlisted tokens (including the suspended ones),
+  /// which is also the number of the indexed keys.
+  pub fn total_allowed<'a>(
+    &self,
+    global_states: &'a mut GlobalStates,
+  ) -> &'a mut u64 {
+    match self {
+      AllowlistType::CurrencyToken => {
+        &mut global_states.total_allowed_currency_tokens
+      }
+      AllowlistType::NftCollection => {
+        &mut global_states.total_allowed_nft_collections
+      }
+      AllowlistType::NftMint => &mut global_states.total_allowed_nft_mints,
+      AllowlistType::NftCreator => {
+        &mut global_states.total_allowed_nft_creators
+      }
+    }
+  }
+
+  pub fn audit_field(&self) -> AuditField {
+    match self {
+      AllowlistType::CurrencyToken => AuditField::CurrencyTokenAllowlist,
+      AllowlistType::NftCollection => AuditField::NftCollectionAllowlist,
+      AllowlistType::NftMint => AuditField::NftMintAllowlist,
+      AllowlistType::NftCreator => AuditField::NftCreatorAllowlist,
+    }
+  }
+}
+
+/// The admin roles that can be granted to different keys, so that the key used
+/// for a daily operation (e.g.: making raffles) doesn't need to hold the power
+/// over all the other admin operations.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AdminRole {
+  // Updates the regular program configs.
+  ConfigAdmin,
+  // Adds currency tokens & NFT collections into the allowlists.
+  AllowlistCurator,
+  // Makes raffles (and sets raffle winners in test environment).
+  RaffleOperator,
+  // Updates the market fee rate & the fee treasury.
+  TreasuryAdmin,
+}
+
+/// The listing types & modes that can be enabled or disabled per cluster, each
+/// of which is a bit of GlobalStates::features.
+#[derive(
+  AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug,
+)]
+pub enum Feature {
+  // Auctions with eligible groups.
+  ExclusiveAuctions,
+  // Raffles with eligible groups.
+  ExclusiveRaffles,
+  // Raffles of more than one NFT.
+  MultiNftRaffles,
+  // Auction creation, which replaces GlobalStates::auction_creation_enabled.
+  AuctionCreation,
+  // Raffle creation, which replaces GlobalStates::raffle_creation_enabled.
+  RaffleCreation,
+}
+
+impl Feature {
+  /// The bitmap with all the features enabled.
+  pub const ALL: u64 = (1 << 5) - 1;
+
+  pub fn mask(&self) -> u64 {
+    1 << (*self as u8)
+  }
 }
 
 /// The account type for storing the global configs & states.
@@ -44,26 +576,105 @@ pub struct GlobalStates {
   pub min_raffle_duration: u64,
   /// The maximum raffle duration.
   pub max_raffle_duration: u64,
-  /// Whether auction creation is enabled.
+  /// Deprecated since 1.7 in favor of Feature::AuctionCreation, which it's
+  /// kept in sync with for the older clients.
   pub auction_creation_enabled: bool,
-  /// Whether raffle creation is enabled.
+  /// Deprecated since 1.7 in favor of Feature::RaffleCreation, which it's kept
+  /// in sync with for the older clients.
   pub raffle_creation_enabled: bool,
   /// Total number of auctions ever created.
   pub total_auctions: u64,
   /// Total number of raffles ever created.
   pub total_raffles: u64,
-  /// Total number of NFT collections ever allowed.
+  /// Number of allowlisted NFT collections (including the suspended ones),
+  /// i.e.: the number of keys in the allowlist index.
   pub total_allowed_nft_collections: u64,
-  /// Total number of currency tokens ever allowed.
+  /// Number of allowlisted currency tokens (including the suspended ones).
   pub total_allowed_currency_tokens: u64,
   /// Number of keys stored in each index page.
   pub num_keys_per_index_page: u16,
   // This is a special flag to support test-specific features such as: system
-  // clock mocking.
+  // clock mocking. It only takes effect when the program is built with the
+  // "testing" feature.
   // !!!!!!!!!!!! This should never be true in the main network !!!!!!!!!
   pub is_test_environment: bool,
   // This can be set in the test only when "is_test_environment" is true.
   pub mock_timestamp: Option<i64>,
+  /// The proposed new authority, which becomes the authority once it accepts.
+  pub pending_authority: Option<Pubkey>,
+  /// The holders of the admin roles (None when the role is revoked). The roles
+  /// are granted & revoked by the authority.
+  pub config_admin: Option<Pubkey>,
+  pub allowlist_curator: Option<Pubkey>,
+  pub raffle_operator: Option<Pubkey>,
+  pub treasury_admin: Option<Pubkey>,
+  /// The members of the built-in multisig. The multisig signs as the
+  /// multisig_authority pda, which can be made the authority or any role
+  /// holder.
+  pub multisig_members: Vec<Pubkey>,
+  /// The number of member approvals required for executing an admin proposal.
+  pub multisig_threshold: u8,
+  /// The total number of admin proposals ever created.
+  pub total_admin_proposals: u64,
+  /// The delay (in seconds) before the queued timelocked config changes can be
+  /// executed.
+  pub config_timelock_delay: u64,
+  /// Number of allowlisted individual NFT mints (including the suspended ones).
+  pub total_allowed_nft_mints: u64,
+  /// Number of allowlisted NFT creators (including the suspended ones).
+  pub total_allowed_nft_creators: u64,
+  /// Whether bidding is enabled (i.e.: the emergency pause of make_bid).
+  pub bidding_enabled: bool,
+  /// Whether raffle ticket sales are enabled (i.e.: the emergency pause of
+  /// buy_raffle_tickets).
+  pub ticket_sales_enabled: bool,
+  /// Whether the revenue, lot & reward claims are enabled (i.e.: the emergency
+  /// pause of claim_auction_revenue, claim_lot_nft, claim_raffle_revenue and
+  /// claim_raffle_reward). The refunds are never paused.
+  pub claims_enabled: bool,
+  /// The wallet that can receive the NFTs of the force-cancelled listings
+  /// (e.g.: the stolen NFTs) instead of the creators.
+  pub quarantine_wallet_address: Pubkey,
+  /// Whether only the curated creators can create auctions & raffles.
+  pub curated_creators_only: bool,
+  /// The maximum number of live listings (auctions & raffles) of each creator,
+  /// where 0 means no limit.
+  pub max_live_listings_per_creator: u16,
+  /// The layout version of the account, where 0.0 is the layout before the
+  /// versioning. The major version only changes on breaking layout changes,
+  /// while the new fields are appended (with a minor version bump), so that the
+  /// older clients of the same major version can still deserialize the account.
+  pub major_version: u8,
+  pub minor_version: u8,
+  /// The bitmap of the enabled features (see "Feature"), which replaces the
+  /// per-feature bools for the new listing types & modes. Added in 1.1.
+  pub features: u64,
+  /// The split of the market fee across the treasuries, where the whole fee
+  /// goes to the fee treasury when empty. Added in 1.2.
+  pub fee_shares: Vec<FeeShareConfig>,
+  /// The flat fee charged upon the creation of each auction & raffle, where 0
+  /// means no fee. Added in 1.3.
+  pub listing_fee: u64,
+  /// The token mint of the listing fee, where the default pubkey means SOL (in
+  /// lamports).
+  pub listing_fee_token_mint: Pubkey,
+  /// Whether the listing fee is refunded to the creator once the listing
+  /// settles (i.e.: the revenue is claimed), which is only supported for the
+  /// listing fee in SOL. The refundable fee is held by the listing until then,
+  /// and goes to the fee treasury if the listing is cancelled.
+  pub listing_fee_refundable: bool,
+  /// The market fee tiers by the creators' settled volumes, in the ascending
+  /// order of the volume thresholds. Added in 1.4.
+  pub fee_tiers: Vec<FeeTierConfig>,
+  /// The market fee discounts for the creators in the groups (e.g.: the holders
+  /// of a membership collection). Added in 1.5.
+  pub fee_discounts: Vec<FeeDiscountConfig>,
+  /// Incremented upon each change of the multisig members, so that the
+  /// approvals given to the admin proposals before the change no longer count.
+  /// Added in 1.6.
+  pub multisig_nonce: u64,
+  // The new fields must be appended here, with their defaults filled in
+  // migrate().
 }
 
 impl GlobalStates {
@@ -89,5 +700,533 @@ impl GlobalStates {
     + 8
     + 2
     + 1
-    + (1 + 8);
+    + (1 + 8)
+    + (1 + 32)
+    + (1 + 32)
+    + (1 + 32)
+    + (1 + 32)
+    + (1 + 32)
+    + (4 + 32 * GlobalStates::MAX_MULTISIG_MEMBERS)
+    + 1
+    + 8
+    + 8
+    + 8
+    + 8
+    + 1
+    + 1
+    + 1
+    + 32
+    + 1
+    + 2
+    + 1
+    + 1
+    + 8
+    + (4 + FeeShareConfig::MAX_DATA_SIZE * FeeShareConfig::MAX_FEE_TREASURIES)
+    + 8
+    + 32
+    + 1
+    + (4 + FeeTierConfig::MAX_DATA_SIZE * FeeTierConfig::MAX_FEE_TIERS)
+    + (4
+      + FeeDiscountConfig::MAX_DATA_SIZE
+        * FeeDiscountConfig::MAX_FEE_DISCOUNTS)
+    + 8;
+
+  /// The space reserved for the new fields, so that the account of an older
+  /// minor version can still be deserialized before it is migrated.
+  pub const RESERVED_SPACE: usize = 256;
+  /// The space of the account of the current version.
+  pub const SPACE: usize =
+    8 + GlobalStates::MAX_DATA_SIZE + GlobalStates::RESERVED_SPACE;
+
+  pub const MAJOR_VERSION: u8 = 1;
+  pub const MINOR_VERSION: u8 = 7;
+
+  pub const MAX_MULTISIG_MEMBERS: usize = 10;
+  pub const DEFAULT_CONFIG_TIMELOCK_DELAY: u64 = 2 * 24 * 3600;
+  pub const MAX_CONFIG_TIMELOCK_DELAY: u64 = 30 * 24 * 3600;
+
+  pub fn role_holder(&self, role: AdminRole) -> Option<Pubkey> {
+    match role {
+      AdminRole::ConfigAdmin => self.config_admin,
+      AdminRole::AllowlistCurator => self.allowlist_curator,
+      AdminRole::RaffleOperator => self.raffle_operator,
+      AdminRole::TreasuryAdmin => self.treasury_admin,
+    }
+  }
+
+  pub fn set_role_holder(&mut self, role: AdminRole, holder: Option<Pubkey>) {
+    match role {
+      AdminRole::ConfigAdmin => self.config_admin = holder,
+      AdminRole::AllowlistCurator => self.allowlist_curator = holder,
+      AdminRole::RaffleOperator => self.raffle_operator = holder,
+      AdminRole::TreasuryAdmin => self.treasury_admin = holder,
+    }
+  }
+
+  pub fn has_role(&self, role: AdminRole, key: Pubkey) -> bool {
+    self.role_holder(role) == Some(key)
+  }
+
+  /// Fills the defaults of the fields added after the version of the account,
+  /// and bumps it to the current version.
+  ///
+  /// The accounts created before the versioning (i.e.: version 0.0) are
+  /// migrated as well, while the ones of any other major version can't be.
+  pub fn migrate(&mut self) -> Result<()> {
+    let is_compatible = self.major_version == 0
+      || (self.major_version == Self::MAJOR_VERSION
+        && self.minor_version <= Self::MINOR_VERSION);
+    if !is_compatible {
+      return err!(JustiesErrorCode::IncompatibleGlobalStatesVersion);
+    }
+    // The fields added before the versioning (i.e.: the accounts of version
+    // 0.0) get the same defaults as init_justies_program, where the admin
+    // roles are held by the authority and the multisig is disabled.
+    if self.major_version == 0 {
+      self.pending_authority = None;
+      self.config_admin = Some(self.authority);
+      self.allowlist_curator = Some(self.authority);
+      self.raffle_operator = Some(self.authority);
+      self.treasury_admin = Some(self.authority);
+      self.multisig_members = vec![];
+      self.multisig_threshold = 0;
+      self.total_admin_proposals = 0;
+      self.config_timelock_delay = Self::DEFAULT_CONFIG_TIMELOCK_DELAY;
+      self.total_allowed_nft_mints = 0;
+      self.total_allowed_nft_creators = 0;
+      self.bidding_enabled = true;
+      self.ticket_sales_enabled = true;
+      self.claims_enabled = true;
+      self.quarantine_wallet_address = self.authority;
+      self.curated_creators_only = false;
+      self.max_live_listings_per_creator = 0;
+    }
+    // The defaults of the new fields are filled here, by checking the minor
+    // version.
+    if self.major_version == 0 || self.minor_version < 1 {
+      self.features = Feature::ALL;
+    }
+    if self.major_version == 0 || self.minor_version < 2 {
+      self.fee_shares = vec![];
+    }
+    if self.major_version == 0 || self.minor_version < 3 {
+      self.listing_fee = 0;
+      self.listing_fee_token_mint = Pubkey::default();
+      self.listing_fee_refundable = false;
+    }
+    if self.major_version == 0 || self.minor_version < 4 {
+      self.fee_tiers = vec![];
+    }
+    if self.major_version == 0 || self.minor_version < 5 {
+      self.fee_discounts = vec![];
+    }
+    if self.major_version == 0 || self.minor_version < 6 {
+      self.multisig_nonce = 0;
+    }
+    if self.major_version == 0 || self.minor_version < 7 {
+      // The creation toggles are moved into the feature bitmap, while the
+      // legacy bools keep their values.
+      self.set_feature_enabled(
+        Feature::AuctionCreation,
+        self.auction_creation_enabled,
+      );
+      self.set_feature_enabled(
+        Feature::RaffleCreation,
+        self.raffle_creation_enabled,
+      );
+    }
+    self.major_version = Self::MAJOR_VERSION;
+    self.minor_version = Self::MINOR_VERSION;
+    Ok(())
+  }
+
+  pub fn is_feature_enabled(&self, feature: Feature) -> bool {
+    self.features & feature.mask() != 0
+  }
+
+  /// Sets the feature bit, and the legacy bool of the creation features, so
+  /// that the older clients of the same major version read the same toggles.
+  pub fn set_feature_enabled(&mut self, feature: Feature, enabled: bool) {
+    if enabled {
+      self.features |= feature.mask();
+    } else {
+      self.features &= !feature.mask();
+    }
+    match feature {
+      Feature::AuctionCreation => self.auction_creation_enabled = enabled,
+      Feature::RaffleCreation => self.raffle_creation_enabled = enabled,
+      _ => {}
+    }
+  }
+
+  /// Checks that the feature is enabled, where the error message names the
+  /// disabled feature.
+  pub fn check_feature_enabled(&self, feature: Feature) -> Result<()> {
+    if self.is_feature_enabled(feature) {
+      return Ok(());
+    }
+    let mut error = error!(JustiesErrorCode::FeatureDisabled);
+    if let Error::AnchorError(anchor_error) = &mut error {
+      anchor_error.error_msg =
+        format!("{}: {:?}", anchor_error.error_msg, feature);
+    }
+    Err(error)
+  }
+
+  /// Whether the testing-only features (e.g.: timestamp mocking) are enabled,
+  /// which requires the program to be built with the "testing" feature and to
+  /// be initialized as a test environment.
+  ///
+  /// In release builds this is always false, so that the testing-only code
+  /// paths are compiled out.
+  pub fn is_test_mode(&self) -> bool {
+    cfg!(feature = "testing") && self.is_test_environment
+  }
+
+  /// The market fee rate of the highest fee tier reached by the creator's
+  /// settled volume (if any).
+  pub fn fee_tier_rate_bps(&self, creator_volume: u64) -> Option<u16> {
+    self
+      .fee_tiers
+      .iter()
+      .rev()
+      .find(|fee_tier| creator_volume >= fee_tier.min_volume)
+      .map(|fee_tier| fee_tier.market_fee_rate_bps)
+  }
+
+  pub fn is_listing_fee_in_sol(&self) -> bool {
+    self.listing_fee_token_mint == Pubkey::default()
+  }
+
+  pub fn is_multisig_member(&self, key: Pubkey) -> bool {
+    self.multisig_members.contains(&key)
+  }
+}
+
+/// The account meta of an instruction wrapped in an admin proposal.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct ProposalAccountMeta {
+  pub pubkey: Pubkey,
+  pub is_signer: bool,
+  pub is_writable: bool,
+}
+
+impl ProposalAccountMeta {
+  pub const MAX_DATA_SIZE: usize = 32 + 1 + 1;
+}
+
+/// An admin instruction proposed by a multisig member.
+///
+/// The instruction is invoked with the multisig_authority pda as signer once
+/// enough members approved it.
+#[account]
+pub struct AdminProposal {
+  pub bump: u8,
+  pub id: u64,
+  pub proposer: Pubkey,
+  /// The account metas of the proposed justies instruction.
+  pub accounts: Vec<ProposalAccountMeta>,
+  /// The serialized data of the proposed justies instruction.
+  pub data: Vec<u8>,
+  /// The multisig members that approved the proposal.
+  pub approvers: Vec<Pubkey>,
+  pub executed: bool,
+  /// The multisig nonce when the approvals were given, i.e.: the approvals are
+  /// cleared once the multisig members change.
+  pub multisig_nonce: u64,
+}
+
+impl AdminProposal {
+  pub const MAX_ACCOUNTS: usize = 16;
+  pub const MAX_INSTRUCTION_DATA_LEN: usize = 512;
+  pub const MAX_DATA_SIZE: usize = 1
+    + 8
+    + 32
+    + (4 + ProposalAccountMeta::MAX_DATA_SIZE * AdminProposal::MAX_ACCOUNTS)
+    + (4 + AdminProposal::MAX_INSTRUCTION_DATA_LEN)
+    + (4 + 32 * GlobalStates::MAX_MULTISIG_MEMBERS)
+    + 1
+    + 8;
+
+  /// Whether the approvals were given under the current multisig members.
+  pub fn has_current_approvals(&self, global_states: &GlobalStates) -> bool {
+    self.multisig_nonce == global_states.multisig_nonce
+  }
+
+  /// Counts the approvals given under the current multisig members, so that
+  /// the approvals given before any change of the members (e.g.: by a member
+  /// that was removed and re-added since) no longer count.
+  pub fn num_approvals(&self, global_states: &GlobalStates) -> usize {
+    if !self.has_current_approvals(global_states) {
+      return 0;
+    }
+    self.approvers.len()
+  }
+}
+
+/// The timelocked config changes waiting for the activation.
+#[account]
+pub struct PendingConfigs {
+  pub bump: u8,
+  pub input: TimelockedConfigsInput,
+  /// The signer that queued the changes, which receives the rent back once the
+  /// changes are executed or cancelled.
+  pub proposer: Pubkey,
+  /// The changes can be executed at or after this timestamp.
+  pub activation_timestamp: i64,
+}
+
+impl PendingConfigs {
+  pub const MAX_DATA_SIZE: usize =
+    1 + TimelockedConfigsInput::MAX_DATA_SIZE + 32 + 8;
+}
+
+/// The audited fields.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AuditField {
+  MarketFeeRateBps,
+  FeeTreasuryAddress,
+  MinOutbidRateBps,
+  LastMinutesForAuctionExtend,
+  AuctionExtendMinutes,
+  MinAuctionDuration,
+  MaxAuctionDuration,
+  MinRaffleTicketSupply,
+  MaxRaffleTicketSupply,
+  MaxRaffledNfts,
+  MinRaffleDuration,
+  MaxRaffleDuration,
+  NumKeysPerIndexPage,
+  ConfigTimelockDelay,
+  Authority,
+  PendingAuthority,
+  ConfigAdmin,
+  AllowlistCurator,
+  RaffleOperator,
+  TreasuryAdmin,
+  // The target is the multisig member being added or removed.
+  MultisigMember,
+  MultisigThreshold,
+  // The target is the allowlisted token mint.
+  CurrencyTokenAllowlist,
+  // The target is the allowlisted collection mint.
+  NftCollectionAllowlist,
+  // The target is the allowlisted NFT mint.
+  NftMintAllowlist,
+  // The target is the allowlisted creator.
+  NftCreatorAllowlist,
+  // The listing limits, whose target is the currency token mint.
+  MinStartBid,
+  MinTicketPrice,
+  MaxTicketPrice,
+  MinOutbidIncrement,
+  CurrencyMarketFeeRateBps,
+  BiddingEnabled,
+  TicketSalesEnabled,
+  ClaimsEnabled,
+  QuarantineWalletAddress,
+  AuctionForceCancelled,
+  RaffleForceCancelled,
+  // The target is the blocked wallet.
+  WalletBlocklist,
+  CuratedCreatorsOnly,
+  // The target is the creator.
+  CuratedCreator,
+  // The target is the creator.
+  CreatorMaxLiveListings,
+  MaxLiveListingsPerCreator,
+  GlobalStatesMajorVersion,
+  GlobalStatesMinorVersion,
+  Features,
+  // The target is the fee treasury.
+  FeeShareBps,
+  ListingFee,
+  ListingFeeTokenMint,
+  ListingFeeRefundable,
+  // The fee tiers are recorded in pairs of the volume threshold & the rate.
+  FeeTierMinVolume,
+  FeeTierRateBps,
+  // The target is the key of the group.
+  FeeDiscountBps,
+  // The queued values of the timelocked configs, which are cleared once the
+  // changes are executed or cancelled.
+  PendingMarketFeeRateBps,
+  PendingFeeTreasuryAddress,
+  PendingConfigTimelockDelay,
+  // The target is the fee treasury, or none when the fee split is cleared.
+  PendingFeeShareBps,
+  PendingListingFee,
+  PendingListingFeeTokenMint,
+}
+
+impl From<AdminRole> for AuditField {
+  fn from(role: AdminRole) -> Self {
+    match role {
+      AdminRole::ConfigAdmin => AuditField::ConfigAdmin,
+      AdminRole::AllowlistCurator => AuditField::AllowlistCurator,
+      AdminRole::RaffleOperator => AuditField::RaffleOperator,
+      AdminRole::TreasuryAdmin => AuditField::TreasuryAdmin,
+    }
+  }
+}
+
+/// The value of an audited field.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigValue {
+  None,
+  Bool(bool),
+  U8(u8),
+  U16(u16),
+  U64(u64),
+  Pubkey(Pubkey),
+}
+
+impl ConfigValue {
+  pub const MAX_DATA_SIZE: usize = 1 + 32;
+
+  /// Converts an optional value, where None is ConfigValue::None.
+  pub fn from_option<T: Into<ConfigValue>>(value: Option<T>) -> Self {
+    value.map_or(ConfigValue::None, Into::into)
+  }
+}
+
+impl From<bool> for ConfigValue {
+  fn from(value: bool) -> Self {
+    ConfigValue::Bool(value)
+  }
+}
+
+impl From<u8> for ConfigValue {
+  fn from(value: u8) -> Self {
+    ConfigValue::U8(value)
+  }
+}
+
+impl From<u16> for ConfigValue {
+  fn from(value: u16) -> Self {
+    ConfigValue::U16(value)
+  }
+}
+
+impl From<u64> for ConfigValue {
+  fn from(value: u64) -> Self {
+    ConfigValue::U64(value)
+  }
+}
+
+impl From<Pubkey> for ConfigValue {
+  fn from(value: Pubkey) -> Self {
+    ConfigValue::Pubkey(value)
+  }
+}
+
+impl From<Option<Pubkey>> for ConfigValue {
+  fn from(value: Option<Pubkey>) -> Self {
+    value.map_or(ConfigValue::None, ConfigValue::Pubkey)
+  }
+}
+
+/// A change to an audited field.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigChange {
+  pub field: AuditField,
+  /// The entity the change applies to (e.g.: the allowlisted token mint), if
+  /// the field is not a global one.
+  pub target: Option<Pubkey>,
+  pub old_value: ConfigValue,
+  /// The new value, which is ConfigValue::None if the entity is removed.
+  pub new_value: ConfigValue,
+  /// The reason code provided by the signer (if any).
+  pub reason_code: Option<u16>,
+}
+
+impl ConfigChange {
+  pub const MAX_DATA_SIZE: usize =
+    1 + (1 + 32) + ConfigValue::MAX_DATA_SIZE * 2 + (1 + 2);
+
+  pub fn new(
+    field: AuditField,
+    old_value: impl Into<ConfigValue>,
+    new_value: impl Into<ConfigValue>,
+  ) -> Self {
+    Self {
+      field,
+      target: None,
+      old_value: old_value.into(),
+      new_value: new_value.into(),
+      reason_code: None,
+    }
+  }
+
+  pub fn with_target(mut self, target: Pubkey) -> Self {
+    self.target = Some(target);
+    self
+  }
+
+  pub fn with_reason_code(mut self, reason_code: Option<u16>) -> Self {
+    self.reason_code = reason_code;
+    self
+  }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct AuditEntry {
+  pub change: ConfigChange,
+  pub signer: Pubkey,
+  pub timestamp: i64,
+}
+
+impl AuditEntry {
+  pub const MAX_DATA_SIZE: usize = ConfigChange::MAX_DATA_SIZE + 32 + 8;
+}
+
+/// The ring buffer of the most recent config changes.
+///
+/// Once the buffer is full, the oldest entry is overwritten by the new one.
+#[account]
+pub struct AuditLog {
+  pub bump: u8,
+  /// The index of the entry to be written next.
+  pub next_index: u16,
+  /// The total number of entries ever recorded.
+  pub total_entries: u64,
+  pub entries: Vec<AuditEntry>,
+}
+
+impl AuditLog {
+  pub const CAPACITY: usize = 32;
+  pub const MAX_DATA_SIZE: usize =
+    1 + 2 + 8 + (4 + AuditEntry::MAX_DATA_SIZE * AuditLog::CAPACITY);
+
+  pub fn record(
+    &mut self,
+    change: ConfigChange,
+    signer: Pubkey,
+    timestamp: i64,
+  ) {
+    let entry = AuditEntry {
+      change,
+      signer,
+      timestamp,
+    };
+    if self.entries.len() < AuditLog::CAPACITY {
+      self.entries.push(entry);
+    } else {
+      self.entries[self.next_index as usize] = entry;
+    }
+    self.next_index =
+      ((self.next_index as usize + 1) % AuditLog::CAPACITY) as u16;
+    self.total_entries += 1;
+  }
+
+  pub fn record_all(
+    &mut self,
+    changes: Vec<ConfigChange>,
+    signer: Pubkey,
+    timestamp: i64,
+  ) {
+    for change in changes {
+      self.record(change, signer, timestamp);
+    }
+  }
 }
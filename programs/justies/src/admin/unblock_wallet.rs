@@ -0,0 +1,53 @@
+use crate::admin::{
+  AuditField, AuditLog, ConfigChange, GlobalStates, WalletBlocklistStates,
+};
+use crate::common::{get_current_timestamp, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// Unblocks a blocked wallet.
+///
+/// The blocklist states account is closed (with the rent returned to the
+/// signer).
+///
+/// Only callable by the authority.
+#[derive(Accounts)]
+#[instruction(wallet_address: Pubkey)]
+pub struct UnblockWallet<'info> {
+  #[account(
+      mut,
+      close = authority,
+      seeds = [b"wallet_blocklist_states", wallet_address.as_ref()],
+      bump = wallet_blocklist_states.bump,
+  )]
+  pub wallet_blocklist_states: Account<'info, WalletBlocklistStates>,
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.authority == authority.key() @JustiesErrorCode::NotTheAuthority,
+  )]
+  pub global_states: Account<'info, GlobalStates>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<UnblockWallet>,
+  wallet_address: Pubkey,
+  reason_code: Option<u16>,
+) -> Result<()> {
+  ctx.accounts.audit_log.record(
+    ConfigChange::new(AuditField::WalletBlocklist, true, false)
+      .with_target(wallet_address)
+      .with_reason_code(reason_code),
+    ctx.accounts.authority.key(),
+    get_current_timestamp(&ctx.accounts.global_states),
+  );
+  Ok(())
+}
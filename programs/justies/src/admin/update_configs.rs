@@ -1,5 +1,5 @@
-use crate::admin::GlobalStates;
-use crate::common::{ConfigsStrategy, JustiesErrorCode};
+use crate::admin::{AuditLog, GlobalStates};
+use crate::common::{get_current_timestamp, ConfigsStrategy};
 use anchor_lang::prelude::*;
 
 /// The input type for the UpdateConfigs instruction.
@@ -20,15 +20,52 @@ pub struct UpdateConfigsInput {
   pub max_raffled_nfts: Option<u8>,
   pub min_raffle_duration: Option<u64>,
   pub max_raffle_duration: Option<u64>,
-  pub auction_creation_enabled: Option<bool>,
-  pub raffle_creation_enabled: Option<bool>,
-  // This can only be set when is_test_environment == true for safety purpose.
+  pub bidding_enabled: Option<bool>,
+  pub ticket_sales_enabled: Option<bool>,
+  pub claims_enabled: Option<bool>,
+  pub quarantine_wallet_address: Option<Pubkey>,
+  pub curated_creators_only: Option<bool>,
+  pub max_live_listings_per_creator: Option<u16>,
+  pub listing_fee: Option<u64>,
+  pub listing_fee_token_mint: Option<Pubkey>,
+  pub listing_fee_refundable: Option<bool>,
+  // This can only be set when is_test_environment == true in builds with the
+  // "testing" feature for safety purpose.
   pub num_keys_per_index_page: Option<u16>,
 }
 
+impl UpdateConfigsInput {
+  /// Whether any of the treasury configs (i.e.: the market fee rate, the fee
+  /// treasury and the listing fee configs) is being updated.
+  pub fn updates_treasury_configs(&self) -> bool {
+    self.market_fee_rate_bps.is_some()
+      || self.fee_treasury_address.is_some()
+      || self.listing_fee.is_some()
+      || self.listing_fee_token_mint.is_some()
+      || self.listing_fee_refundable.is_some()
+  }
+
+  /// Whether any of the configs other than the treasury configs is being
+  /// updated.
+  pub fn updates_regular_configs(&self) -> bool {
+    let mut regular_configs = self.clone();
+    regular_configs.market_fee_rate_bps = None;
+    regular_configs.fee_treasury_address = None;
+    regular_configs.listing_fee = None;
+    regular_configs.listing_fee_token_mint = None;
+    regular_configs.listing_fee_refundable = None;
+    regular_configs != Self::default()
+  }
+}
+
 /// Updates program configs.
 ///
-/// Only callable by the authority.
+/// The market fee rate, the fee treasury, the listing fee & its token mint are
+/// timelocked: they can only be changed via queue_config_changes, and setting
+/// them to their current values here is a no-op.
+///
+/// The treasury configs are only updatable by the treasury admin, and the other
+/// configs are only updatable by the config admin.
 #[derive(Accounts)]
 #[instruction(
     input: UpdateConfigsInput,
@@ -41,8 +78,11 @@ pub struct UpdateConfigs<'info> {
   )]
   pub global_states: Account<'info, GlobalStates>,
   #[account(
-      constraint = authority.key() == global_states.authority @JustiesErrorCode::NotTheAuthority,
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
   )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
   pub authority: Signer<'info>,
   pub system_program: Program<'info, System>,
 }
@@ -51,9 +91,17 @@ pub fn handler(
   ctx: Context<UpdateConfigs>,
   input: UpdateConfigsInput,
 ) -> Result<()> {
+  let signer = ctx.accounts.authority.key();
+  let current_timestamp = get_current_timestamp(&ctx.accounts.global_states);
   let mut configs_strategy =
     ConfigsStrategy::new(&mut ctx.accounts.global_states);
-  configs_strategy.update_configs(&input);
+  configs_strategy.check_permission(&input, signer)?;
+  configs_strategy.check_timelocked_configs(&input)?;
+  let changes = configs_strategy.update_configs(&input);
   configs_strategy.validate()?;
+  ctx
+    .accounts
+    .audit_log
+    .record_all(changes, signer, current_timestamp);
   Ok(())
 }
@@ -1,5 +1,6 @@
-use crate::admin::GlobalStates;
+use crate::admin::{CreatorStates, GlobalStates};
 use crate::auction::Auction;
+use crate::common::release_listing_fee_deposit;
 use crate::common::AuctionStrategy;
 use crate::common::JustiesErrorCode;
 use anchor_lang::prelude::*;
@@ -15,6 +16,9 @@ use anchor_spl::token::{Token, TokenAccount};
 /// Please note that the only pre-requisite required cancellation is that there
 /// are no bids, no matter of the auction status.
 ///
+/// The refundable listing fee held by the auction (if any) goes to the fee
+/// treasury.
+///
 /// The auction status will be marked as "Cancelled".
 #[derive(Accounts)]
 #[instruction(
@@ -32,6 +36,14 @@ pub struct CancelAuction<'info> {
   pub auction: Box<Account<'info, Auction>>,
   #[account(mut)]
   pub creator: Signer<'info>,
+  #[account(
+      init_if_needed,
+      payer = creator,
+      space = 8 + CreatorStates::MAX_DATA_SIZE,
+      seeds = [b"creator_states", auction.creator.as_ref()],
+      bump,
+  )]
+  pub creator_states: Box<Account<'info, CreatorStates>>,
   /// CHECK: verified in the constraint.
   pub nft_mint: UncheckedAccount<'info>,
   #[account(
@@ -54,6 +66,12 @@ pub struct CancelAuction<'info> {
       bump = global_states.bump,
   )]
   pub global_states: Box<Account<'info, GlobalStates>>,
+  /// CHECK: the address is verified in the constraint.
+  #[account(
+      mut,
+      constraint = global_states.fee_treasury_address == fee_treasury.key() @JustiesErrorCode::InvalidFeeTreasuryAddress,
+  )]
+  pub fee_treasury: UncheckedAccount<'info>,
 
   pub token_program: Program<'info, Token>,
   pub associated_token_program: Program<'info, AssociatedToken>,
@@ -76,5 +94,21 @@ pub fn handler(ctx: Context<CancelAuction>) -> Result<()> {
     &ctx.accounts.creator.to_account_info(),
   )?;
   auction_strategy.cancel();
+  release_listing_fee_deposit(
+    &ctx.accounts.auction.to_account_info(),
+    &ctx.accounts.fee_treasury.to_account_info(),
+    ctx.accounts.auction.listing_fee_deposit,
+  )?;
+  ctx.accounts.auction.listing_fee_deposit = 0;
+  let creator_states_bump = *ctx.bumps.get("creator_states").unwrap();
+  let creator_key = ctx.accounts.auction.creator;
+  ctx
+    .accounts
+    .creator_states
+    .init_if_needed(creator_states_bump, creator_key);
+  ctx
+    .accounts
+    .creator_states
+    .remove_live_auction(&mut ctx.accounts.auction)?;
   Ok(())
 }
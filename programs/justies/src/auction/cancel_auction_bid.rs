@@ -1,18 +1,27 @@
-use crate::admin::GlobalStates;
+use crate::admin::{CreatorStates, GlobalStates};
 use crate::auction::{Auction, AuctionBid};
-use crate::common::{get_current_timestamp, BidStrategy, JustiesErrorCode};
+use crate::common::{
+  get_current_timestamp, BidStrategy, JustiesErrorCode, ListingStatus,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
 /// Cancels the auction bid.
 ///
-/// Only callable by non-top-bidder. The bid funds will be refunded from the bid
-/// escrow token account to the bidder's token account. The bid escrow token
-/// account will be closed with the rents refunded to the bidder.
+/// Only callable by non-top-bidder, unless the auction is force-cancelled by
+/// the authority (when all the bidders can cancel their bids). The bid funds
+/// will be refunded from the bid escrow token account to the bidder's token
+/// account. The bid escrow token account will be closed with the rents refunded
+/// to the bidder.
+///
+/// As a refund, it's never paused (i.e.: the bidders can always get their funds
+/// out during the emergency pauses).
 ///
 /// The auction status is guaranteed to be "Finished" once confirmed after
-/// auction ended.
+/// auction ended (when it's removed from the creator's live listings). The
+/// creator_states of the auction creator needs to be passed as the first
+/// remaining account while the auction is still counted as live.
 #[derive(Accounts)]
 #[instruction(
     auction_id: u64,
@@ -34,7 +43,7 @@ pub struct CancelAuctionBid<'info> {
       mut,
       seeds = [b"auction", auction_id.to_le_bytes().as_ref()],
       bump = auction.bump,
-      constraint = auction.top_bidder != Some(bidder.key()) @JustiesErrorCode::TopBidderCannotCancelBid,
+      constraint = auction.top_bidder != Some(bidder.key()) || auction.status == ListingStatus::ForceCancelled @JustiesErrorCode::TopBidderCannotCancelBid,
   )]
   pub auction: Box<Account<'info, Auction>>,
   #[account(mut)]
@@ -98,5 +107,9 @@ pub fn handler(ctx: Context<CancelAuctionBid>) -> Result<()> {
   bid_strategy
     .close_bid_escrow_token_account(token_program, bid_escrow_token_account)?;
   bid_strategy.cancel_bid();
+  CreatorStates::release_live_auction(
+    &mut ctx.accounts.auction,
+    ctx.remaining_accounts,
+  )?;
   Ok(())
 }
@@ -1,8 +1,13 @@
-use crate::admin::GlobalStates;
+use crate::admin::{
+  CreatorStates, CreatorVolume, CurrencyStats, GlobalStates,
+  TokenAllowlistStates,
+};
 use crate::auction::{Auction, AuctionBid};
 use crate::common::{
-  get_current_timestamp, init_revenue_distribution_accounts, AuctionStrategy,
-  JustiesErrorCode, RevenueDistributionStrategy,
+  fee_discount_bps, get_current_timestamp, init_fee_distribution_accounts,
+  init_revenue_distribution_accounts, release_listing_fee_deposit,
+  split_distribution_accounts, AuctionStrategy, EligibilityCheckInput,
+  JustiesErrorCode, ListingType, RevenueDistributionStrategy,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
@@ -18,7 +23,26 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 /// distributed, the top-bidder's bid escrow account will be closed with the
 /// rents refunded to the bidder.
 ///
-/// The auction status is guaranteed to be "Finished" once confirmed.
+/// When the market fee is split (see global_states.fee_shares), the fee goes to
+/// the token accounts of the fee treasuries instead, which need to be passed as
+/// "remaining_accounts" after the revenue distribution accounts. The share of a
+/// treasury without the token account goes to the fee treasury.
+///
+/// The creator in a fee discount group (see global_states.fee_discounts) can
+/// prove the membership with the eligibility check input, whose payload
+/// accounts need to be passed as "remaining_accounts" after the fee
+/// distribution accounts.
+///
+/// The settlement is recorded in the currency stats (i.e.: the fee ledger) of
+/// the currency token, which the creator pays rent for if not initialized. So
+/// is the creator's volume in the currency token, which determines the
+/// creator's market fee tier (see global_states.fee_tiers).
+///
+/// The refundable listing fee held by the auction (if any) is refunded to the
+/// creator.
+///
+/// The auction status is guaranteed to be "Finished" once confirmed. Not
+/// callable while claims are paused.
 #[derive(Accounts)]
 #[instruction(
     auction_id: u64,
@@ -28,6 +52,7 @@ pub struct ClaimAuctionRevenue<'info> {
       mut,
       seeds = [b"auction", auction_id.to_le_bytes().as_ref()],
       bump = auction.bump,
+      constraint = auction.creator == creator.key() @JustiesErrorCode::NotAuctionCreator,
   )]
   pub auction: Box<Account<'info, Auction>>,
   #[account(
@@ -44,6 +69,14 @@ pub struct ClaimAuctionRevenue<'info> {
   pub top_bid: Box<Account<'info, AuctionBid>>,
   #[account(mut)]
   pub creator: Signer<'info>,
+  #[account(
+      init_if_needed,
+      payer = creator,
+      space = 8 + CreatorStates::MAX_DATA_SIZE,
+      seeds = [b"creator_states", auction.creator.as_ref()],
+      bump,
+  )]
+  pub creator_states: Box<Account<'info, CreatorStates>>,
   #[account(
       mut,
       seeds = [
@@ -68,6 +101,26 @@ pub struct ClaimAuctionRevenue<'info> {
       constraint = token_mint.key() == auction.currency_token_mint_address @JustiesErrorCode::InvalidCurrencyTokenMint,
   )]
   pub token_mint: Box<Account<'info, Mint>>,
+  #[account(
+      init_if_needed,
+      payer = creator,
+      space = 8 + CurrencyStats::MAX_DATA_SIZE,
+      seeds = [b"currency_stats", token_mint.key().as_ref()],
+      bump,
+  )]
+  pub currency_stats: Box<Account<'info, CurrencyStats>>,
+  #[account(
+      init_if_needed,
+      payer = creator,
+      space = 8 + CreatorVolume::MAX_DATA_SIZE,
+      seeds = [
+        b"creator_volume",
+        creator.key().as_ref(),
+        token_mint.key().as_ref(),
+      ],
+      bump,
+  )]
+  pub creator_volume: Box<Account<'info, CreatorVolume>>,
   /// CHECK: the address is verified in the constraint.
   #[account(
       mut,
@@ -82,8 +135,29 @@ pub struct ClaimAuctionRevenue<'info> {
   #[account(
       seeds = [b"global_states"],
       bump = global_states.bump,
+      constraint = global_states.claims_enabled == true @JustiesErrorCode::ClaimsDisabled,
   )]
   pub global_states: Box<Account<'info, GlobalStates>>,
+  /// CHECK: the allowlist states may have been removed (i.e.: closed), which is
+  /// checked in the handler.
+  #[account(
+      seeds = [
+        b"token_allowlist_states",
+        auction.currency_token_mint_address.as_ref(),
+      ],
+      bump,
+  )]
+  pub token_allowlist_states: UncheckedAccount<'info>,
+  /// CHECK: the allowlist states may have been removed (i.e.: closed), which is
+  /// checked in the handler.
+  #[account(
+      seeds = [
+        auction.nft_allowlist_type.states_seed(),
+        auction.nft_allowlist_key.as_ref(),
+      ],
+      bump,
+  )]
+  pub nft_allowlist_states: UncheckedAccount<'info>,
 
   pub token_program: Program<'info, Token>,
   pub associated_token_program: Program<'info, AssociatedToken>,
@@ -93,6 +167,7 @@ pub struct ClaimAuctionRevenue<'info> {
 pub fn handler<'info>(
   ctx: Context<'_, '_, '_, 'info, ClaimAuctionRevenue<'info>>,
   _auction_id: u64,
+  eligibility_check_input: Option<EligibilityCheckInput>,
 ) -> Result<()> {
   let global_states = ctx.accounts.global_states.clone();
   let current_timestamp = get_current_timestamp(&global_states);
@@ -100,11 +175,28 @@ pub fn handler<'info>(
     &mut ctx.accounts.auction,
     &mut ctx.accounts.global_states,
   );
+  if auction_strategy.is_cancelled() {
+    return err!(JustiesErrorCode::AuctionCancelled);
+  }
   if !auction_strategy.is_ended(current_timestamp) {
     return err!(JustiesErrorCode::OngoingAuction);
   }
 
-  let revenue_distribution_accounts = ctx.remaining_accounts.to_vec();
+  let (
+    revenue_distribution_accounts,
+    fee_distribution_accounts,
+    account_payloads,
+  ) = split_distribution_accounts(
+    ctx.remaining_accounts,
+    &ctx.accounts.auction.revenue_shares,
+    &ctx.accounts.global_states.fee_shares,
+  );
+  let fee_discount_bps = fee_discount_bps(
+    &ctx.accounts.global_states.fee_discounts,
+    ctx.accounts.creator.key(),
+    eligibility_check_input,
+    &account_payloads,
+  )?;
   let revenue_distributions = init_revenue_distribution_accounts(
     ctx.accounts.creator.to_account_info(),
     ctx.accounts.token_mint.to_account_info(),
@@ -114,10 +206,38 @@ pub fn handler<'info>(
     &ctx.accounts.token_program,
     &ctx.accounts.system_program,
   )?;
+  let currency_stats_bump = *ctx.bumps.get("currency_stats").unwrap();
+  let token_mint_key = ctx.accounts.token_mint.key();
+  ctx
+    .accounts
+    .currency_stats
+    .init_if_needed(currency_stats_bump, token_mint_key);
+  let creator_volume_bump = *ctx.bumps.get("creator_volume").unwrap();
+  let creator_key = ctx.accounts.creator.key();
+  ctx.accounts.creator_volume.init_if_needed(
+    creator_volume_bump,
+    creator_key,
+    token_mint_key,
+  );
+  let fee_distributions = init_fee_distribution_accounts(
+    ctx.accounts.token_mint.to_account_info(),
+    &ctx.accounts.global_states.fee_shares,
+    &fee_distribution_accounts,
+    &ctx.accounts.fee_treasury_token_account,
+  )?;
   let auction_key = ctx.accounts.auction.key();
   let bidder_key = ctx.accounts.top_bidder.key();
   let bid_bump_bytes = ctx.accounts.top_bid.bump.to_le_bytes();
   let global_states = ctx.accounts.global_states.clone();
+  let currency_market_fee_rate_bps =
+    TokenAllowlistStates::market_fee_rate_override(
+      &ctx.accounts.token_allowlist_states,
+    )?;
+  let collection_market_fee_rate_bps =
+    TokenAllowlistStates::listing_rule_overrides(
+      &ctx.accounts.nft_allowlist_states,
+    )?
+    .market_fee_rate_bps;
   let escrow_signer_seed_fn = || {
     vec![
       b"auction".as_ref(),
@@ -131,10 +251,17 @@ pub fn handler<'info>(
     revenue_escrow_token_account: &mut ctx.accounts.bid_escrow_token_account,
     fee_treasury_token_account: &ctx.accounts.fee_treasury_token_account,
     global_states: &global_states,
+    currency_market_fee_rate_bps,
+    collection_market_fee_rate_bps,
+    fee_discount_bps,
     escrow_authority: ctx.accounts.top_bid.to_account_info(),
     escrow_token_account_creator: ctx.accounts.top_bidder.to_account_info(),
     escrow_signer_seed_fn,
     revenue_distributions,
+    fee_distributions,
+    currency_stats: &mut ctx.accounts.currency_stats,
+    creator_volume: &mut ctx.accounts.creator_volume,
+    listing_type: ListingType::Auction,
   };
 
   let mut auction_strategy = AuctionStrategy::new(
@@ -148,5 +275,21 @@ pub fn handler<'info>(
   revenue_distribution_strategy
     .close_revenue_escrow_token_account(&ctx.accounts.token_program)?;
   auction_strategy.finalize_auction_if_need();
+  release_listing_fee_deposit(
+    &ctx.accounts.auction.to_account_info(),
+    &ctx.accounts.creator.to_account_info(),
+    ctx.accounts.auction.listing_fee_deposit,
+  )?;
+  ctx.accounts.auction.listing_fee_deposit = 0;
+  let creator_states_bump = *ctx.bumps.get("creator_states").unwrap();
+  let creator_key = ctx.accounts.auction.creator;
+  ctx
+    .accounts
+    .creator_states
+    .init_if_needed(creator_states_bump, creator_key);
+  ctx
+    .accounts
+    .creator_states
+    .remove_live_auction(&mut ctx.accounts.auction)?;
   Ok(())
 }
@@ -1,4 +1,4 @@
-use crate::admin::GlobalStates;
+use crate::admin::{CreatorStates, GlobalStates};
 use crate::auction::{Auction, AuctionBid};
 use crate::common::{get_current_timestamp, AuctionStrategy, JustiesErrorCode};
 use anchor_lang::prelude::*;
@@ -12,7 +12,10 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 /// lot escrow NFT account will be closed with the rents refunded to the
 /// auction creator.
 ///
-/// The auction status is guaranteed to be "Finished" once confirmed.
+/// The auction status is guaranteed to be "Finished" once confirmed (when it's
+/// removed from the creator's live listings). The creator_states of the auction
+/// creator needs to be passed as the first remaining account while the auction
+/// is still counted as live. Not callable while claims are paused.
 #[derive(Accounts)]
 #[instruction(
     auction_id: u64,
@@ -58,6 +61,7 @@ pub struct ClaimLotNft<'info> {
   #[account(
       seeds = [b"global_states"],
       bump = global_states.bump,
+      constraint = global_states.claims_enabled == true @JustiesErrorCode::ClaimsDisabled,
   )]
   pub global_states: Account<'info, GlobalStates>,
   #[account(
@@ -99,5 +103,9 @@ pub fn handler(ctx: Context<ClaimLotNft>, _auction_id: u64) -> Result<()> {
     &ctx.accounts.auction_creator,
   )?;
   auction_strategy.finalize_auction_if_need();
+  CreatorStates::release_live_auction(
+    &mut ctx.accounts.auction,
+    ctx.remaining_accounts,
+  )?;
   Ok(())
 }
@@ -1,8 +1,11 @@
-use crate::admin::{GlobalStates, TokenAllowlistStates};
+use crate::admin::{
+  CreatorStates, GlobalStates, TokenAllowlistStates, WalletBlocklistStates,
+};
 use crate::auction::state::Auction;
 use crate::common::{
-  get_current_timestamp, validate_share_configs, AuctionStrategy,
-  JustiesErrorCode, GroupConfig, RevenueShareConfig,
+  check_nft_allowlisted, check_revenue_receivers_not_blocked,
+  collect_listing_fee, get_current_timestamp, validate_share_configs,
+  AuctionStrategy, GroupConfig, JustiesErrorCode, RevenueShareConfig,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::metadata::Metadata;
@@ -15,13 +18,22 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 /// bid, revenue recipients, NFT token, currency token, eligible groups config,
 /// etc). Once created, the NFT token will be transferred from the creator's NFT
 /// account to the lot escrow NFT account (with the auction as the token
-/// authority). Both of the NFT collection and currency token must have been
-/// allow-listed.
+/// authority). Both of the NFT and currency token must have been allow-listed,
+/// where the NFT can be allow-listed via its verified collection, its mint or
+/// one of its verified creators.
 ///
 /// Once confirmed, the auction status will be set to "InProgress".
 ///
 /// For eligible groups config and revenue recipients config, check
 /// "GroupConfig" and "RevenueShareConfig" for more details.
+///
+/// Neither the creator nor the revenue recipients can be blocked, where the
+/// wallet_blocklist_states pdas of the revenue recipients need to be passed as
+/// "remaining_accounts" in the same order as revenue_shares.
+///
+/// The listing fee (see global_states.listing_fee) is charged to the creator,
+/// which goes to the fee treasury, or is held by the auction until the revenue
+/// is claimed when refundable.
 #[derive(Accounts)]
 #[instruction(
     id: u64,
@@ -37,7 +49,6 @@ pub struct CreateAuction<'info> {
       space = 8 + Auction::MAX_DATA_SIZE,
       seeds = [b"auction", id.to_le_bytes().as_ref()],
       bump,
-      constraint = global_states.auction_creation_enabled == true @JustiesErrorCode::AuctionCreationDisabled,
       constraint = global_states.total_auctions == id @JustiesErrorCode::InvalidAuctionId,
       constraint = revenue_shares.len() <= RevenueShareConfig::MAX_REVENUE_RECEIVERS @JustiesErrorCode::InvalidRevenueRecipientNumber,
   )]
@@ -46,6 +57,22 @@ pub struct CreateAuction<'info> {
   pub currency_token_mint: Box<Account<'info, Mint>>,
   #[account(mut)]
   pub creator: Signer<'info>,
+  /// CHECK: the blocklist states of the creator, which only exists when the
+  /// creator is blocked.
+  #[account(
+      seeds = [b"wallet_blocklist_states", creator.key().as_ref()],
+      bump,
+      constraint = !WalletBlocklistStates::is_blocked(&creator_blocklist_states) @JustiesErrorCode::WalletBlocked,
+  )]
+  pub creator_blocklist_states: UncheckedAccount<'info>,
+  #[account(
+      init_if_needed,
+      payer = creator,
+      space = 8 + CreatorStates::MAX_DATA_SIZE,
+      seeds = [b"creator_states", creator.key().as_ref()],
+      bump,
+  )]
+  pub creator_states: Box<Account<'info, CreatorStates>>,
   #[account(
       init,
       payer = creator,
@@ -75,8 +102,6 @@ pub struct CreateAuction<'info> {
       seeds::program = Metadata::id(),
       bump,
       constraint = nft_metadata.mint == nft_mint.key() @JustiesErrorCode::InvalidNftMetadata,
-      constraint = nft_metadata.collection != None @JustiesErrorCode::InvalidNftMetadata,
-      constraint = nft_metadata.collection.as_ref().unwrap().verified == true @JustiesErrorCode::InvalidNftMetadata,
   )]
   pub nft_metadata: Box<Account<'info, MetadataAccount>>,
   #[account(
@@ -85,16 +110,9 @@ pub struct CreateAuction<'info> {
       bump = global_states.bump,
   )]
   pub global_states: Account<'info, GlobalStates>,
-  #[account(
-      seeds = [
-        b"nft_allowlist_states",
-        nft_metadata.collection.as_ref().unwrap().key.as_ref(),
-      ],
-      bump = nft_allowlist_states.bump,
-      constraint = nft_allowlist_states.token_mint_address == nft_metadata.collection.as_ref().unwrap().key @JustiesErrorCode::NftCollectionNotInAllowlist,
-      constraint = nft_allowlist_states.allowed == true @JustiesErrorCode::NftCollectionNotInAllowlist,
-  )]
-  pub nft_allowlist_states: Account<'info, TokenAllowlistStates>,
+  /// CHECK: it can be the allowlist states of the NFT collection, the NFT mint
+  /// or the NFT creator, which is checked in the handler.
+  pub nft_allowlist_states: UncheckedAccount<'info>,
   #[account(
       seeds = [b"token_allowlist_states", currency_token_mint.key().as_ref()],
       bump = token_allowlist_states.bump,
@@ -102,6 +120,22 @@ pub struct CreateAuction<'info> {
       constraint = token_allowlist_states.allowed == true @JustiesErrorCode::TokenNotInAllowlist,
   )]
   pub token_allowlist_states: Account<'info, TokenAllowlistStates>,
+  /// CHECK: the address is verified in the constraint.
+  #[account(
+      mut,
+      constraint = global_states.fee_treasury_address == fee_treasury.key() @JustiesErrorCode::InvalidFeeTreasuryAddress,
+  )]
+  pub fee_treasury: UncheckedAccount<'info>,
+  /// CHECK: the creator's associated token account of the listing fee token,
+  /// which is only used (and checked in the handler) for the listing fee in
+  /// tokens.
+  #[account(mut)]
+  pub creator_listing_fee_token_account: UncheckedAccount<'info>,
+  /// CHECK: the fee treasury's associated token account of the listing fee
+  /// token, which is only used (and checked in the handler) for the listing fee
+  /// in tokens.
+  #[account(mut)]
+  pub fee_treasury_listing_fee_token_account: UncheckedAccount<'info>,
   pub token_program: Program<'info, Token>,
   pub system_program: Program<'info, System>,
 }
@@ -114,6 +148,20 @@ pub fn handler(
   eligible_groups: Vec<GroupConfig>,
   revenue_shares: Vec<RevenueShareConfig>,
 ) -> Result<()> {
+  let (nft_allowlist_type, nft_allowlist_key) = check_nft_allowlisted(
+    &ctx.accounts.nft_metadata,
+    &ctx.accounts.nft_allowlist_states,
+  )?;
+  let listing_rules = TokenAllowlistStates::listing_rule_overrides(
+    &ctx.accounts.nft_allowlist_states,
+  )?;
+  let creator_states_bump = *ctx.bumps.get("creator_states").unwrap();
+  let creator_key = ctx.accounts.creator.key();
+  ctx
+    .accounts
+    .creator_states
+    .init_if_needed(creator_states_bump, creator_key);
+
   let global_states = &mut ctx.accounts.global_states;
   let current_timestamp = get_current_timestamp(global_states);
   let creator = &ctx.accounts.creator;
@@ -122,6 +170,7 @@ pub fn handler(
   let currency_token_mint_address = ctx.accounts.currency_token_mint.key();
 
   validate_share_configs(&revenue_shares)?;
+  check_revenue_receivers_not_blocked(&revenue_shares, ctx.remaining_accounts)?;
 
   let mut auction_strategy = AuctionStrategy::new(
     &mut ctx.accounts.auction,
@@ -137,6 +186,9 @@ pub fn handler(
     start_bid,
     &eligible_groups,
     &revenue_shares,
+    &ctx.accounts.token_allowlist_states.listing_limits,
+    &listing_rules,
+    &mut ctx.accounts.creator_states,
     current_timestamp,
   )?;
   auction_strategy.deposit_nft(
@@ -145,5 +197,17 @@ pub fn handler(
     &ctx.accounts.lot_escrow_nft_account,
     creator.to_account_info(),
   )?;
+  ctx.accounts.auction.nft_allowlist_type = nft_allowlist_type;
+  ctx.accounts.auction.nft_allowlist_key = nft_allowlist_key;
+  ctx.accounts.auction.listing_fee_deposit = collect_listing_fee(
+    &ctx.accounts.global_states,
+    ctx.accounts.creator.to_account_info(),
+    ctx.accounts.auction.to_account_info(),
+    ctx.accounts.fee_treasury.to_account_info(),
+    &ctx.accounts.creator_listing_fee_token_account,
+    &ctx.accounts.fee_treasury_listing_fee_token_account,
+    &ctx.accounts.token_program,
+    &ctx.accounts.system_program,
+  )?;
   Ok(())
 }
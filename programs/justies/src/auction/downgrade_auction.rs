@@ -0,0 +1,58 @@
+use crate::admin::GlobalStates;
+use crate::auction::state::Auction;
+use crate::common::{resize_account, JustiesErrorCode};
+use anchor_lang::prelude::*;
+
+/// The space of the fields added to the auctions since their layout before the
+/// allowlist entries, i.e.: nft_allowlist_type, nft_allowlist_key,
+/// min_outbid_increment, listing_fee_deposit & counted_as_live.
+const ADDED_FIELDS_SPACE: usize = 1 + 32 + 8 + 8 + 1;
+
+/// Rewrites an auction in the layout before the allowlist entries
+/// (testing-only), so that the migration from it can be tested.
+///
+/// The fields added since then are zeroed, and the account is shrunk to the
+/// space of that layout (with the rents refunded to the authority).
+///
+/// Only callable by the authority when global_states.is_test_environment is
+/// true and the program is built with the "testing" feature. The instruction is
+/// only compiled into the builds with the "testing" feature, and is dispatched
+/// via the fallback of the program (see common/testing_instructions.rs).
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct DowngradeAuction<'info> {
+  /// CHECK: The account is rewritten in an older layout, so it is deserialized
+  /// in the handler.
+  #[account(
+      mut,
+      seeds = [b"auction", auction_id.to_le_bytes().as_ref()],
+      bump,
+      owner = crate::ID,
+  )]
+  pub auction: UncheckedAccount<'info>,
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.is_test_mode() @JustiesErrorCode::NotTestEnvironment,
+      constraint = global_states.authority == authority.key() @JustiesErrorCode::NotTheAuthority,
+  )]
+  pub global_states: Box<Account<'info, GlobalStates>>,
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DowngradeAuction>) -> Result<()> {
+  let auction_info = ctx.accounts.auction.to_account_info();
+  let auction = Auction::try_deserialize(&mut &auction_info.data.borrow()[..])?;
+  // The added fields are serialized right after the fields of the older
+  // layout, which are followed by zeros in the older layout.
+  let data_len = 8 + auction.try_to_vec()?.len() - ADDED_FIELDS_SPACE;
+  auction_info.data.borrow_mut()[data_len..].fill(0);
+  resize_account(
+    auction_info,
+    8 + Auction::MAX_DATA_SIZE - ADDED_FIELDS_SPACE,
+    ctx.accounts.authority.to_account_info(),
+    ctx.accounts.system_program.to_account_info(),
+  )
+}
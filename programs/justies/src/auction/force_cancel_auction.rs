@@ -0,0 +1,167 @@
+use crate::admin::{
+  AuditField, AuditLog, ConfigChange, CreatorStates, GlobalStates,
+};
+use crate::auction::Auction;
+use crate::common::{
+  get_current_timestamp, is_account_initialized, release_listing_fee_deposit,
+  AuctionStrategy, JustiesErrorCode, ListingStatus,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount};
+
+/// Force-cancels an auction (e.g.: a fraudulent listing of a stolen NFT).
+///
+/// Only callable by the authority, at any stage before the lot NFT or the
+/// revenue is claimed. The lot NFT will be transferred from the escrow to
+/// either the creator or the quarantine wallet, and the escrow NFT account will
+/// be closed with the rents refunded to the creator.
+///
+/// Once force-cancelled, every bidder (including the top bidder) can withdraw
+/// the escrowed funds via cancel_auction_bid, while the revenue can't be
+/// claimed anymore. The top bid escrow token account is required (when there
+/// is a top bidder) for checking that the revenue has not been claimed.
+///
+/// The refundable listing fee held by the auction (if any) goes to the fee
+/// treasury.
+///
+/// The auction status will be marked as "ForceCancelled". The creator_states
+/// of the auction creator needs to be passed as the first remaining account
+/// while the auction is still counted as live.
+#[derive(Accounts)]
+#[instruction(
+    auction_id: u64,
+)]
+pub struct ForceCancelAuction<'info> {
+  #[account(
+      mut,
+      seeds = [b"auction", auction_id.to_le_bytes().as_ref()],
+      bump = auction.bump,
+      constraint = auction.status != ListingStatus::Cancelled && auction.status != ListingStatus::ForceCancelled @JustiesErrorCode::AuctionCancelled,
+      constraint = auction.nft_mint_address == nft_mint.key() @JustiesErrorCode::NftMintAddressMismatch,
+  )]
+  pub auction: Box<Account<'info, Auction>>,
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  /// CHECK: the address is verified in the constraint.
+  #[account(
+      mut,
+      constraint = creator.key() == auction.creator @JustiesErrorCode::NotAuctionCreator,
+  )]
+  pub creator: UncheckedAccount<'info>,
+  /// CHECK: the address is verified in the constraint.
+  #[account(
+      constraint = nft_recipient.key() == auction.creator || nft_recipient.key() == global_states.quarantine_wallet_address @JustiesErrorCode::InvalidNftRecipient,
+  )]
+  pub nft_recipient: UncheckedAccount<'info>,
+  /// CHECK: verified in the constraint.
+  pub nft_mint: UncheckedAccount<'info>,
+  #[account(
+      mut,
+      seeds = [b"auction", auction.key().as_ref(), b"lot_escrow"],
+      bump,
+      token::mint = nft_mint,
+      token::authority = auction,
+  )]
+  pub lot_escrow_nft_account: Box<Account<'info, TokenAccount>>,
+  #[account(
+      init_if_needed,
+      payer = authority,
+      associated_token::mint = nft_mint,
+      associated_token::authority = nft_recipient,
+  )]
+  pub nft_recipient_nft_account: Box<Account<'info, TokenAccount>>,
+  /// CHECK: the bid escrow token account of the top bidder (if any), which is
+  /// checked in the handler.
+  pub top_bid_escrow_token_account: UncheckedAccount<'info>,
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.authority == authority.key() @JustiesErrorCode::NotTheAuthority,
+  )]
+  pub global_states: Box<Account<'info, GlobalStates>>,
+  /// CHECK: the address is verified in the constraint.
+  #[account(
+      mut,
+      constraint = global_states.fee_treasury_address == fee_treasury.key() @JustiesErrorCode::InvalidFeeTreasuryAddress,
+  )]
+  pub fee_treasury: UncheckedAccount<'info>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+
+  pub token_program: Program<'info, Token>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<ForceCancelAuction>,
+  reason_code: Option<u16>,
+) -> Result<()> {
+  let auction_key = ctx.accounts.auction.key();
+  // The top bid escrow token account is closed once the revenue is claimed, as
+  // the top bidder can't cancel the bid otherwise.
+  if let Some(top_bidder) = ctx.accounts.auction.top_bidder {
+    let (top_bid_escrow_address, _) = Pubkey::find_program_address(
+      &[
+        b"auction",
+        auction_key.as_ref(),
+        b"bid",
+        top_bidder.as_ref(),
+        b"escrow",
+      ],
+      ctx.program_id,
+    );
+    if ctx.accounts.top_bid_escrow_token_account.key() != top_bid_escrow_address
+    {
+      return err!(JustiesErrorCode::InvalidTopBidEscrowAccount);
+    }
+    if !is_account_initialized(&ctx.accounts.top_bid_escrow_token_account) {
+      return err!(JustiesErrorCode::AuctionNotForceCancelable);
+    }
+  }
+
+  let current_timestamp = get_current_timestamp(&ctx.accounts.global_states);
+  let mut auction_strategy = AuctionStrategy::new(
+    &mut ctx.accounts.auction,
+    &mut ctx.accounts.global_states,
+  );
+  auction_strategy.transfer_lot_nft(
+    &ctx.accounts.token_program,
+    &ctx.accounts.lot_escrow_nft_account,
+    &ctx.accounts.nft_recipient_nft_account,
+  )?;
+  auction_strategy.close_lot_escrow_nft_account(
+    &ctx.accounts.token_program,
+    &mut ctx.accounts.lot_escrow_nft_account,
+    &ctx.accounts.creator.to_account_info(),
+  )?;
+  auction_strategy.force_cancel();
+  release_listing_fee_deposit(
+    &ctx.accounts.auction.to_account_info(),
+    &ctx.accounts.fee_treasury.to_account_info(),
+    ctx.accounts.auction.listing_fee_deposit,
+  )?;
+  ctx.accounts.auction.listing_fee_deposit = 0;
+
+  ctx.accounts.audit_log.record(
+    ConfigChange::new(
+      AuditField::AuctionForceCancelled,
+      ctx.accounts.creator.key(),
+      ctx.accounts.nft_recipient.key(),
+    )
+    .with_target(auction_key)
+    .with_reason_code(reason_code),
+    ctx.accounts.authority.key(),
+    current_timestamp,
+  );
+  CreatorStates::release_live_auction(
+    &mut ctx.accounts.auction,
+    ctx.remaining_accounts,
+  )?;
+  Ok(())
+}
@@ -1,8 +1,9 @@
-use crate::admin::GlobalStates;
+use crate::admin::{GlobalStates, WalletBlocklistStates};
 use crate::auction::{Auction, AuctionBid};
 use crate::common::{
-  get_current_timestamp, BidStrategy, JustiesErrorCode, EligibilityCheckInput,
-  EligibilityCheckStrategy, PubkeyIndexPage,
+  check_listing_allowlisted, get_current_timestamp, BidStrategy,
+  EligibilityCheckInput, EligibilityCheckStrategy, JustiesErrorCode,
+  PubkeyIndexPage,
 };
 use crate::program::Justies;
 use anchor_lang::prelude::*;
@@ -25,6 +26,12 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 /// "remaining_accounts". For more details, check the docstring of
 /// EligibilityCheckInput.
 ///
+/// No bids can be made once the currency token or the NFT allowlist entry
+/// (i.e.: the NFT collection, the NFT mint or the NFT creator) is suspended or
+/// removed from the allowlists, or while bidding is paused.
+///
+/// Blocked wallets can't make bids, while they can still cancel their bids.
+///
 /// Once confirmed, the top bidding states will be updated in auction account.
 #[derive(Accounts)]
 #[instruction(
@@ -73,6 +80,14 @@ pub struct MakeBid<'info> {
       constraint = bidder.key() != auction.creator @JustiesErrorCode::AuctionCreatorCannotMakeBid,
   )]
   pub bidder: Signer<'info>,
+  /// CHECK: the blocklist states of the bidder, which only exists when the
+  /// bidder is blocked.
+  #[account(
+      seeds = [b"wallet_blocklist_states", bidder.key().as_ref()],
+      bump,
+      constraint = !WalletBlocklistStates::is_blocked(&bidder_blocklist_states) @JustiesErrorCode::WalletBlocked,
+  )]
+  pub bidder_blocklist_states: UncheckedAccount<'info>,
   #[account(
       init_if_needed,
       payer = bidder,
@@ -101,8 +116,29 @@ pub struct MakeBid<'info> {
   #[account(
       seeds = [b"global_states"],
       bump = global_states.bump,
+      constraint = global_states.bidding_enabled == true @JustiesErrorCode::BiddingDisabled,
   )]
   pub global_states: Box<Account<'info, GlobalStates>>,
+  /// CHECK: the allowlist states may have been removed (i.e.: closed), which is
+  /// checked in the handler.
+  #[account(
+      seeds = [
+        b"token_allowlist_states",
+        auction.currency_token_mint_address.as_ref(),
+      ],
+      bump,
+  )]
+  pub token_allowlist_states: UncheckedAccount<'info>,
+  /// CHECK: the allowlist states may have been removed (i.e.: closed), which is
+  /// checked in the handler.
+  #[account(
+      seeds = [
+        auction.nft_allowlist_type.states_seed(),
+        auction.nft_allowlist_key.as_ref(),
+      ],
+      bump,
+  )]
+  pub nft_allowlist_states: UncheckedAccount<'info>,
 
   pub justies_program: Program<'info, Justies>,
   pub token_program: Program<'info, Token>,
@@ -116,6 +152,11 @@ pub fn handler(
   max_allowed_bid_amount: u64,
   eligibility_check_input: Option<EligibilityCheckInput>,
 ) -> Result<()> {
+  check_listing_allowlisted(
+    &ctx.accounts.token_allowlist_states,
+    &ctx.accounts.nft_allowlist_states,
+  )?;
+
   // Check the bidder's eligibility first.
   let eligibility_check_strategy = EligibilityCheckStrategy::new(
     &ctx.accounts.auction.eligible_groups,
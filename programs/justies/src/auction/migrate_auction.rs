@@ -0,0 +1,61 @@
+use crate::auction::state::Auction;
+use crate::common::{grow_account, legacy_nft_allowlist_entry};
+use anchor_lang::prelude::*;
+
+/// Migrates an auction created before the program upgrade that added the
+/// auction's allowlist entry, minimum outbid increment and listing fee deposit,
+/// by growing it to the current size and filling the defaults of the new
+/// fields.
+///
+/// The lot NFT of such an auction was allowlisted via its verified collection,
+/// which is read from the NFT metadata. The minimum outbid increment & the
+/// listing fee deposit are 0. Until it's migrated, the auction can't be bid on
+/// (and can't be loaded at all if its vectors fill the account), while the
+/// migration of an auction of the current layout is a no-op.
+///
+/// Callable by anyone, where the signer pays the rent of the added space.
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct MigrateAuction<'info> {
+  /// CHECK: The account of an older layout may be too small to deserialize, so
+  /// it is deserialized in the handler after the reallocation.
+  #[account(
+      mut,
+      seeds = [b"auction", auction_id.to_le_bytes().as_ref()],
+      bump,
+      owner = crate::ID,
+  )]
+  pub auction: UncheckedAccount<'info>,
+  /// CHECK: the metadata of the lot NFT, which is checked in the handler.
+  pub nft_metadata: UncheckedAccount<'info>,
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateAuction>, _auction_id: u64) -> Result<()> {
+  let auction_info = ctx.accounts.auction.to_account_info();
+  grow_account(
+    auction_info.clone(),
+    8 + Auction::MAX_DATA_SIZE,
+    ctx.accounts.payer.to_account_info(),
+    ctx.accounts.system_program.to_account_info(),
+  )?;
+
+  let mut auction =
+    Auction::try_deserialize(&mut &auction_info.data.borrow()[..])?;
+  // The auctions created after the upgrade always have the allowlist entry.
+  if auction.nft_allowlist_key != Pubkey::default() {
+    return Ok(());
+  }
+  let (nft_allowlist_type, nft_allowlist_key) = legacy_nft_allowlist_entry(
+    &ctx.accounts.nft_metadata,
+    auction.nft_mint_address,
+  )?;
+  auction.nft_allowlist_type = nft_allowlist_type;
+  auction.nft_allowlist_key = nft_allowlist_key;
+  auction.min_outbid_increment = 0;
+  auction.listing_fee_deposit = 0;
+  auction.try_serialize(&mut &mut auction_info.data.borrow_mut()[..])?;
+  Ok(())
+}
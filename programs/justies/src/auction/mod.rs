@@ -6,9 +6,17 @@ pub mod create_auction;
 pub use create_auction::*;
 pub mod make_bid;
 pub use make_bid::*;
+#[cfg(feature = "testing")]
+pub mod downgrade_auction;
+#[cfg(feature = "testing")]
+pub use downgrade_auction::*;
+pub mod migrate_auction;
+pub use migrate_auction::*;
 pub mod cancel_auction_bid;
 pub use cancel_auction_bid::*;
 pub mod cancel_auction;
 pub use cancel_auction::*;
+pub mod force_cancel_auction;
+pub use force_cancel_auction::*;
 pub mod state;
 pub use state::*;
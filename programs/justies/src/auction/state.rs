@@ -1,3 +1,4 @@
+use crate::admin::AllowlistType;
 use crate::common::types::{GroupConfig, ListingStatus, RevenueShareConfig};
 use anchor_lang::prelude::*;
 
@@ -27,6 +28,22 @@ pub struct Auction {
   pub total_bids: u64,
   pub top_bid: u64,
   pub top_bidder: Option<Pubkey>,
+  // The allowlist entry that the lot NFT was listed under (i.e.: the NFT
+  // collection, the NFT mint or the NFT creator), whose allowlist states are
+  // checked when making bids.
+  pub nft_allowlist_type: AllowlistType,
+  pub nft_allowlist_key: Pubkey,
+  // The minimum increment of an outbid, captured from the listing limits of
+  // the currency token when the auction is created.
+  pub min_outbid_increment: u64,
+  // The refundable listing fee (in lamports) held by the auction, which is
+  // refunded to the creator once the revenue is claimed, or goes to the fee
+  // treasury if the auction is cancelled.
+  pub listing_fee_deposit: u64,
+  // Whether the auction is counted in the live listings of the creator, which
+  // is true from the creation until the auction is finalized (i.e.: cancelled
+  // or finished).
+  pub counted_as_live: bool,
 }
 
 impl Auction {
@@ -47,7 +64,12 @@ impl Auction {
     + 4
     + 8
     + 8
-    + (1 + 32));
+    + (1 + 32)
+    + 1
+    + 32
+    + 8
+    + 8
+    + 1);
 }
 
 #[account]
@@ -0,0 +1,190 @@
+use crate::admin::{AllowlistType, GlobalStates, TokenAllowlistStates};
+use crate::common::{
+  create_pda, index_pubkey, JustiesErrorCode, PubkeyIndexPage,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+
+/// The name of the idx-th entry of a batch instruction, which is attached to
+/// the errors so that the failed entry can be told.
+pub fn batch_entry_name(idx: usize, account_name: &str) -> String {
+  format!("entries[{}].{}", idx, account_name)
+}
+
+/// Creates the allowlist states pda of the token, and indexes it into the
+/// allowlist index.
+///
+/// This is used by the batch allowlisting instructions, whose accounts are
+/// passed via "remaining_accounts" and therefore have to be validated and
+/// created manually. The index pages that the new keys go into need to be
+/// included in index_pages.
+#[allow(clippy::too_many_arguments)]
+pub fn create_and_index_allowlist_states<'info>(
+  allowlist_type: AllowlistType,
+  entry_idx: usize,
+  token_mint_address: Pubkey,
+  allowlist_states_info: &AccountInfo<'info>,
+  index_pages: &[AccountInfo<'info>],
+  global_states: &mut GlobalStates,
+  payer: AccountInfo<'info>,
+  justies_program: AccountInfo<'info>,
+  system_program: AccountInfo<'info>,
+) -> Result<()> {
+  let (allowlist_states_key, allowlist_states_bump) =
+    Pubkey::find_program_address(
+      &[allowlist_type.states_seed(), token_mint_address.as_ref()],
+      &crate::ID,
+    );
+  if allowlist_states_info.key() != allowlist_states_key {
+    return Err(
+      error!(JustiesErrorCode::InvalidAllowlistStatesAccount)
+        .with_account_name(batch_entry_name(entry_idx, "allowlist_states")),
+    );
+  }
+  // Only an account created by the program means the token is allowlisted, as
+  // the pda address may have been funded by anyone beforehand.
+  if allowlist_states_info.owner == &crate::ID {
+    return Err(
+      error!(JustiesErrorCode::TokenAlreadyInAllowlist)
+        .with_account_name(batch_entry_name(entry_idx, "allowlist_states")),
+    );
+  }
+
+  let allowlist_states_bump_bytes = allowlist_states_bump.to_le_bytes();
+  create_pda(
+    payer.clone(),
+    allowlist_states_info.clone(),
+    8 + TokenAllowlistStates::MAX_DATA_SIZE,
+    &[
+      allowlist_type.states_seed(),
+      token_mint_address.as_ref(),
+      allowlist_states_bump_bytes.as_ref(),
+    ],
+    justies_program.clone(),
+    system_program.clone(),
+  )?;
+  let mut allowlist_states: Account<TokenAllowlistStates> =
+    Account::try_from_unchecked(allowlist_states_info)?;
+  allowlist_states.bump = allowlist_states_bump;
+  allowlist_states.token_mint_address = token_mint_address;
+  allowlist_states.allowed = true;
+  // Need to call exit manually to persistent the account state.
+  allowlist_states.exit(&crate::ID)?;
+
+  // Indexes the allowlist states key to make it iterable.
+  let num_keys_per_index_page = global_states.num_keys_per_index_page;
+  let total_allowed = allowlist_type.total_allowed(global_states);
+  let page_id =
+    PubkeyIndexPage::page_id(*total_allowed, num_keys_per_index_page);
+  let page_id_bytes = page_id.to_le_bytes();
+  let (index_page_key, index_page_bump) = Pubkey::find_program_address(
+    &[allowlist_type.index_seed(), page_id_bytes.as_ref()],
+    &crate::ID,
+  );
+  let index_page_info = match index_pages
+    .iter()
+    .find(|index_page| index_page.key() == index_page_key)
+  {
+    Some(index_page_info) => index_page_info,
+    None => {
+      return Err(
+        error!(JustiesErrorCode::MissingAllowlistIndexPage)
+          .with_account_name(batch_entry_name(entry_idx, "index_page")),
+      );
+    }
+  };
+  let index_page_bump_bytes = index_page_bump.to_le_bytes();
+  let signer_seed = vec![
+    allowlist_type.index_seed(),
+    page_id_bytes.as_ref(),
+    index_page_bump_bytes.as_ref(),
+  ];
+  index_pubkey(
+    allowlist_states_key,
+    index_page_bump,
+    signer_seed.as_slice(),
+    index_page_info.clone(),
+    payer,
+    justies_program,
+    system_program,
+  )?;
+
+  *total_allowed += 1;
+  Ok(())
+}
+
+/// Whether the key is one of the verified creators of the NFT.
+fn is_verified_creator(nft_metadata: &MetadataAccount, key: Pubkey) -> bool {
+  match &nft_metadata.data.creators {
+    Some(creators) => creators
+      .iter()
+      .any(|creator| creator.verified && creator.address == key),
+    None => false,
+  }
+}
+
+/// Checks that the NFT is allowed by the given NFT allowlist states, which can
+/// be the allowlist states of the NFT's verified collection, of the NFT mint
+/// itself, or of one of the NFT's verified creators. Returns the type & the key
+/// of the allowlist entry.
+pub fn check_nft_allowlisted(
+  nft_metadata: &MetadataAccount,
+  nft_allowlist_states_info: &AccountInfo,
+) -> Result<(AllowlistType, Pubkey)> {
+  let nft_allowlist_states: Account<TokenAllowlistStates> =
+    Account::try_from(nft_allowlist_states_info)
+      .map_err(|err| err.with_account_name("nft_allowlist_states"))?;
+  let allowlist_key = nft_allowlist_states.token_mint_address;
+  let allowlist_type = match &nft_metadata.collection {
+    Some(collection)
+      if collection.verified && collection.key == allowlist_key =>
+    {
+      AllowlistType::NftCollection
+    }
+    _ if nft_metadata.mint == allowlist_key => AllowlistType::NftMint,
+    _ if is_verified_creator(nft_metadata, allowlist_key) => {
+      AllowlistType::NftCreator
+    }
+    _ => return err!(JustiesErrorCode::NftCollectionNotInAllowlist),
+  };
+
+  // Verifies the allowlist states is the pda of the matched allowlist entry.
+  let allowlist_states_key = Pubkey::create_program_address(
+    &[
+      allowlist_type.states_seed(),
+      allowlist_key.as_ref(),
+      &[nft_allowlist_states.bump],
+    ],
+    &crate::ID,
+  )
+  .map_err(|_| error!(JustiesErrorCode::InvalidNftAllowlistStates))?;
+  if allowlist_states_key != nft_allowlist_states_info.key() {
+    return err!(JustiesErrorCode::InvalidNftAllowlistStates);
+  }
+
+  if !nft_allowlist_states.allowed {
+    return err!(JustiesErrorCode::NftCollectionNotInAllowlist);
+  }
+  Ok((allowlist_type, allowlist_key))
+}
+
+/// The allowlist entry of an NFT listed before the listings recorded their
+/// allowlist entries, i.e.: the verified collection of the NFT, which was the
+/// only way to allowlist NFTs back then.
+pub fn legacy_nft_allowlist_entry(
+  nft_metadata_info: &AccountInfo,
+  nft_mint_address: Pubkey,
+) -> Result<(AllowlistType, Pubkey)> {
+  let nft_metadata: Account<MetadataAccount> =
+    Account::try_from(nft_metadata_info)
+      .map_err(|err| err.with_account_name("nft_metadata"))?;
+  if nft_metadata.mint != nft_mint_address {
+    return err!(JustiesErrorCode::InvalidNftMetadata);
+  }
+  match &nft_metadata.collection {
+    Some(collection) if collection.verified => {
+      Ok((AllowlistType::NftCollection, collection.key))
+    }
+    _ => err!(JustiesErrorCode::InvalidNftMetadata),
+  }
+}
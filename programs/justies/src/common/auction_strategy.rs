@@ -1,8 +1,12 @@
-use crate::admin::GlobalStates;
+use crate::admin::{
+  CreatorStates, CurrencyListingLimits, Feature, GlobalStates,
+  ListingRuleOverrides,
+};
 use crate::auction::Auction;
 use anchor_lang::prelude::*;
 use anchor_lang::ToAccountInfo;
 use anchor_spl::token::{Token, TokenAccount};
+use std::cmp;
 
 use crate::common::cpi_utils::{
   close_token_account_with_signer, transfer_token,
@@ -46,15 +50,35 @@ impl<'accounts, 'info> AuctionStrategy<'accounts, 'info> {
     start_bid: u64,
     eligible_groups: &Vec<GroupConfig>,
     revenue_shares: &Vec<RevenueShareConfig>,
+    listing_limits: &CurrencyListingLimits,
+    listing_rules: &ListingRuleOverrides,
+    creator_states: &mut CreatorStates,
     current_timestamp: i64,
   ) -> Result<()> {
-    if (duration as u64) < self.global_states.min_auction_duration
-      || (duration as u64) > self.global_states.max_auction_duration
+    self
+      .global_states
+      .check_feature_enabled(Feature::AuctionCreation)?;
+    creator_states.validate_new_listing(self.global_states)?;
+    if !eligible_groups.is_empty() {
+      self
+        .global_states
+        .check_feature_enabled(Feature::ExclusiveAuctions)?;
+    }
+
+    let (min_auction_duration, max_auction_duration) =
+      listing_rules.auction_duration_range(self.global_states);
+    if (duration as u64) < min_auction_duration
+      || (duration as u64) > max_auction_duration
     {
       return err!(JustiesErrorCode::InvalidAuctionDuration);
     }
 
+    if start_bid < listing_limits.min_start_bid {
+      return err!(JustiesErrorCode::InvalidStartBid);
+    }
+
     self.global_states.total_auctions += 1;
+    creator_states.num_live_auctions += 1;
     self.auction.bump = bump;
     self.auction.id = id;
     self.auction.nft_mint_address = nft_mint_address;
@@ -69,6 +93,8 @@ impl<'accounts, 'info> AuctionStrategy<'accounts, 'info> {
     self.auction.total_bids = 0;
     self.auction.top_bid = 0;
     self.auction.top_bidder = None;
+    self.auction.min_outbid_increment = listing_limits.min_outbid_increment;
+    self.auction.counted_as_live = true;
     Ok(())
   }
 
@@ -95,7 +121,8 @@ impl<'accounts, 'info> AuctionStrategy<'accounts, 'info> {
   }
 
   pub fn is_cancelled(&self) -> bool {
-    return self.auction.status == ListingStatus::Cancelled;
+    return self.auction.status == ListingStatus::Cancelled
+      || self.auction.status == ListingStatus::ForceCancelled;
   }
 
   pub fn need_to_extend(&self, current_timestamp: i64) -> bool {
@@ -107,7 +134,20 @@ impl<'accounts, 'info> AuctionStrategy<'accounts, 'info> {
   pub fn get_min_eligible_bid(&self) -> u64 {
     let top_bid = self.auction.top_bid as i64;
     let min_outbid_rate_bps = self.global_states.min_outbid_rate_bps as i64;
-    (top_bid + top_bid * min_outbid_rate_bps / 10000) as u64
+    let min_eligible_bid =
+      (top_bid + top_bid * min_outbid_rate_bps / 10000) as u64;
+    if top_bid == 0 {
+      return min_eligible_bid;
+    }
+    // The sum never overflows for a valid bid, which is checked in the bid
+    // validation.
+    cmp::max(
+      min_eligible_bid,
+      self
+        .auction
+        .top_bid
+        .saturating_add(self.auction.min_outbid_increment),
+    )
   }
 
   pub fn extend(&mut self, current_timestamp: i64) {
@@ -119,6 +159,10 @@ impl<'accounts, 'info> AuctionStrategy<'accounts, 'info> {
     self.auction.status = ListingStatus::Cancelled;
   }
 
+  pub fn force_cancel(&mut self) {
+    self.auction.status = ListingStatus::ForceCancelled;
+  }
+
   pub fn get_lot_escrow_signer_seed(&self) -> Box<Vec<&[u8]>> {
     Box::new(vec![
       b"auction".as_ref(),
@@ -161,7 +205,7 @@ impl<'accounts, 'info> AuctionStrategy<'accounts, 'info> {
   }
 
   pub fn finalize_auction_if_need(&mut self) {
-    if self.auction.status != ListingStatus::Finished {
+    if self.auction.status == ListingStatus::InProgress {
       self.auction.status = ListingStatus::Finished;
     }
   }
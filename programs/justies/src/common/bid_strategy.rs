@@ -86,6 +86,15 @@ impl<'accounts, 'info> BidStrategy<'accounts, 'info> {
     }
 
     if top_bid > 0 {
+      // The minimum outbid increment of the currency token is checked
+      // separately for a clearer error, where no bid can meet it if the sum
+      // overflows.
+      let min_incremented_bid = top_bid
+        .checked_add(auction.min_outbid_increment)
+        .ok_or_else(|| error!(JustiesErrorCode::NotMetMinOutbidIncrement))?;
+      if self.max_allowed_bid_amount < min_incremented_bid {
+        return err!(JustiesErrorCode::NotMetMinOutbidIncrement);
+      }
       let min_eligible_bid = self.auction_strategy.get_min_eligible_bid();
       if self.max_allowed_bid_amount < min_eligible_bid {
         return err!(JustiesErrorCode::NotMetMinOutbidRate);
@@ -1,11 +1,29 @@
-use crate::admin::{GlobalStates, UpdateConfigsInput};
-use crate::common::JustiesErrorCode;
+use crate::admin::{
+  AdminRole, AuditField, ConfigChange, GlobalStates, PendingConfigs,
+  TimelockedConfigsInput, UpdateConfigsInput,
+};
+use crate::common::{validate_share_configs, FeeShareConfig, JustiesErrorCode};
 use anchor_lang::prelude::*;
 
+/// Updates the config if it's set in the input, and collects the change into
+/// `$changes` when the value is actually changed.
 macro_rules! try_update_config {
-  ($input:ident, $global_states:ident, $property_name:ident) => {
+  (
+    $input:ident,
+    $global_states:ident,
+    $changes:ident,
+    $property_name:ident,
+    $audit_field:ident
+  ) => {
     match $input.$property_name {
       Some(value) => {
+        if $global_states.$property_name != value {
+          $changes.push(ConfigChange::new(
+            AuditField::$audit_field,
+            $global_states.$property_name,
+            value,
+          ));
+        }
         $global_states.$property_name = value;
       }
       _ => {}
@@ -24,25 +42,267 @@ impl<'accounts, 'info> ConfigsStrategy<'accounts, 'info> {
     Self { global_states }
   }
 
-  pub fn update_configs(&mut self, input: &UpdateConfigsInput) {
+  /// Checks that the signer holds the admin roles required for the update.
+  pub fn check_permission(
+    &self,
+    input: &UpdateConfigsInput,
+    signer: Pubkey,
+  ) -> Result<()> {
+    let global_states = &*self.global_states;
+    if input.updates_treasury_configs()
+      && !global_states.has_role(AdminRole::TreasuryAdmin, signer)
+    {
+      return err!(JustiesErrorCode::MissingAdminRole);
+    }
+    if (input.updates_regular_configs() || !input.updates_treasury_configs())
+      && !global_states.has_role(AdminRole::ConfigAdmin, signer)
+    {
+      return err!(JustiesErrorCode::MissingAdminRole);
+    }
+    Ok(())
+  }
+
+  /// Checks that the timelocked configs are not changed by the input. Setting
+  /// them to their current values is allowed.
+  pub fn check_timelocked_configs(
+    &self,
+    input: &UpdateConfigsInput,
+  ) -> Result<()> {
+    let global_states = &*self.global_states;
+    if input
+      .market_fee_rate_bps
+      .map_or(false, |value| value != global_states.market_fee_rate_bps)
+      || input
+        .fee_treasury_address
+        .map_or(false, |value| value != global_states.fee_treasury_address)
+      || input
+        .listing_fee
+        .map_or(false, |value| value != global_states.listing_fee)
+      || input
+        .listing_fee_token_mint
+        .map_or(false, |value| value != global_states.listing_fee_token_mint)
+    {
+      return err!(JustiesErrorCode::TimelockRequired);
+    }
+    Ok(())
+  }
+
+  /// Updates the timelocked configs and returns the changes.
+  pub fn update_timelocked_configs(
+    &mut self,
+    input: &TimelockedConfigsInput,
+  ) -> Vec<ConfigChange> {
     let global_states = &mut self.global_states;
-    try_update_config!(input, global_states, market_fee_rate_bps);
-    try_update_config!(input, global_states, fee_treasury_address);
-    try_update_config!(input, global_states, min_outbid_rate_bps);
-    try_update_config!(input, global_states, last_minutes_for_auction_extend);
-    try_update_config!(input, global_states, auction_extend_minutes);
-    try_update_config!(input, global_states, min_auction_duration);
-    try_update_config!(input, global_states, max_auction_duration);
-    try_update_config!(input, global_states, min_raffle_ticket_supply);
-    try_update_config!(input, global_states, max_raffle_ticket_supply);
-    try_update_config!(input, global_states, max_raffled_nfts);
-    try_update_config!(input, global_states, min_raffle_duration);
-    try_update_config!(input, global_states, max_raffle_duration);
-    try_update_config!(input, global_states, auction_creation_enabled);
-    try_update_config!(input, global_states, raffle_creation_enabled);
-    if global_states.is_test_environment {
-      try_update_config!(input, global_states, num_keys_per_index_page);
+    let mut changes = vec![];
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      market_fee_rate_bps,
+      MarketFeeRateBps
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      fee_treasury_address,
+      FeeTreasuryAddress
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      config_timelock_delay,
+      ConfigTimelockDelay
+    );
+    try_update_config!(input, global_states, changes, listing_fee, ListingFee);
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      listing_fee_token_mint,
+      ListingFeeTokenMint
+    );
+    if let Some(fee_shares) = &input.fee_shares {
+      changes.extend(fee_share_changes(&global_states.fee_shares, fee_shares));
+      global_states.fee_shares = fee_shares.clone();
     }
+    changes
+  }
+
+  /// Applies the pending config changes once they are activated, and returns
+  /// the changes.
+  pub fn execute_pending_configs(
+    &mut self,
+    pending_configs: &PendingConfigs,
+    current_timestamp: i64,
+  ) -> Result<Vec<ConfigChange>> {
+    if current_timestamp < pending_configs.activation_timestamp {
+      return err!(JustiesErrorCode::PendingConfigsNotActivated);
+    }
+    let changes = self.update_timelocked_configs(&pending_configs.input);
+    self.validate()?;
+    Ok(changes)
+  }
+
+  /// Updates the configs and returns the changes.
+  pub fn update_configs(
+    &mut self,
+    input: &UpdateConfigsInput,
+  ) -> Vec<ConfigChange> {
+    let global_states = &mut self.global_states;
+    let mut changes = vec![];
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      market_fee_rate_bps,
+      MarketFeeRateBps
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      fee_treasury_address,
+      FeeTreasuryAddress
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      min_outbid_rate_bps,
+      MinOutbidRateBps
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      last_minutes_for_auction_extend,
+      LastMinutesForAuctionExtend
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      auction_extend_minutes,
+      AuctionExtendMinutes
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      min_auction_duration,
+      MinAuctionDuration
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      max_auction_duration,
+      MaxAuctionDuration
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      min_raffle_ticket_supply,
+      MinRaffleTicketSupply
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      max_raffle_ticket_supply,
+      MaxRaffleTicketSupply
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      max_raffled_nfts,
+      MaxRaffledNfts
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      min_raffle_duration,
+      MinRaffleDuration
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      max_raffle_duration,
+      MaxRaffleDuration
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      bidding_enabled,
+      BiddingEnabled
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      ticket_sales_enabled,
+      TicketSalesEnabled
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      claims_enabled,
+      ClaimsEnabled
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      quarantine_wallet_address,
+      QuarantineWalletAddress
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      curated_creators_only,
+      CuratedCreatorsOnly
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      max_live_listings_per_creator,
+      MaxLiveListingsPerCreator
+    );
+    try_update_config!(input, global_states, changes, listing_fee, ListingFee);
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      listing_fee_token_mint,
+      ListingFeeTokenMint
+    );
+    try_update_config!(
+      input,
+      global_states,
+      changes,
+      listing_fee_refundable,
+      ListingFeeRefundable
+    );
+    if global_states.is_test_mode() {
+      try_update_config!(
+        input,
+        global_states,
+        changes,
+        num_keys_per_index_page,
+        NumKeysPerIndexPage
+      );
+    }
+    changes
   }
 
   pub fn validate(&self) -> Result<()> {
@@ -80,6 +340,72 @@ impl<'accounts, 'info> ConfigsStrategy<'accounts, 'info> {
     {
       return err!(JustiesErrorCode::InvalidRaffleDurationRangeSettings);
     }
+    if global_states.config_timelock_delay
+      > GlobalStates::MAX_CONFIG_TIMELOCK_DELAY
+    {
+      return err!(JustiesErrorCode::InvalidConfigTimelockDelay);
+    }
+    validate_fee_split(&global_states.fee_shares)?;
+    if global_states.listing_fee_refundable
+      && !global_states.is_listing_fee_in_sol()
+    {
+      return err!(JustiesErrorCode::InvalidListingFeeConfigs);
+    }
     Ok(())
   }
 }
+
+/// Checks that the shares of the fee split add up to 10000 bps without
+/// duplicated treasuries, unless the split is empty.
+fn validate_fee_split(fee_shares: &Vec<FeeShareConfig>) -> Result<()> {
+  if fee_shares.len() > FeeShareConfig::MAX_FEE_TREASURIES {
+    return err!(JustiesErrorCode::InvalidFeeSplit);
+  }
+  if !fee_shares.is_empty() {
+    validate_share_configs(fee_shares)
+      .map_err(|_| error!(JustiesErrorCode::InvalidFeeSplit))?;
+  }
+  let has_duplicates = fee_shares.iter().enumerate().any(|(idx, config)| {
+    fee_shares[..idx]
+      .iter()
+      .any(|other| other.treasury == config.treasury)
+  });
+  if has_duplicates {
+    return err!(JustiesErrorCode::InvalidFeeSplit);
+  }
+  Ok(())
+}
+
+/// The share changes of each treasury, where the share of a treasury out of
+/// the split is 0.
+fn fee_share_changes(
+  old_fee_shares: &Vec<FeeShareConfig>,
+  new_fee_shares: &Vec<FeeShareConfig>,
+) -> Vec<ConfigChange> {
+  let share_bps_of = |shares: &Vec<FeeShareConfig>, treasury: Pubkey| {
+    shares
+      .iter()
+      .find(|config| config.treasury == treasury)
+      .map_or(0, |config| config.share_bps)
+  };
+  let mut treasuries: Vec<Pubkey> = old_fee_shares
+    .iter()
+    .map(|config| config.treasury)
+    .collect();
+  for config in new_fee_shares.iter() {
+    if !treasuries.contains(&config.treasury) {
+      treasuries.push(config.treasury);
+    }
+  }
+  treasuries
+    .into_iter()
+    .filter_map(|treasury| {
+      let old_share_bps = share_bps_of(old_fee_shares, treasury);
+      let new_share_bps = share_bps_of(new_fee_shares, treasury);
+      (old_share_bps != new_share_bps).then(|| {
+        ConfigChange::new(AuditField::FeeShareBps, old_share_bps, new_share_bps)
+          .with_target(treasury)
+      })
+    })
+    .collect()
+}
@@ -2,7 +2,9 @@ use crate::common::JustiesErrorCode;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program::invoke;
 use anchor_lang::solana_program::system_instruction::transfer;
-use anchor_lang::system_program::{create_account, CreateAccount};
+use anchor_lang::system_program::{
+  allocate, assign, create_account, Allocate, Assign, CreateAccount,
+};
 use anchor_lang::ToAccountInfo;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{CloseAccount, Transfer};
@@ -102,6 +104,12 @@ pub fn is_account_initialized(account_info: &AccountInfo) -> bool {
   account_info.lamports() > 0
 }
 
+/// Creates the pda owned by the owner program.
+///
+/// The same as Anchor's init, a pda that has been funded beforehand (which
+/// makes create_account fail) is topped up to the rent-exempt minimum, then
+/// allocated & assigned instead, so that anyone transferring lamports to the
+/// pda address can't block its creation.
 pub fn create_pda<'info>(
   payer: AccountInfo<'info>,
   pda: AccountInfo<'info>,
@@ -112,20 +120,51 @@ pub fn create_pda<'info>(
 ) -> Result<()> {
   let rent = Rent::get()?;
   let minimum_balance = rent.minimum_balance(space);
-  let cpi_accounts = CreateAccount {
-    from: payer.to_account_info(),
-    to: pda,
-  };
   let seeds = vec![signer_seed.as_ref()];
-  let cpi_context = CpiContext::new_with_signer(
-    system_program.clone(),
-    cpi_accounts,
-    seeds.as_slice(),
-  );
-  create_account(
-    cpi_context,
-    minimum_balance,
+  let current_balance = pda.lamports();
+  if current_balance == 0 {
+    let cpi_accounts = CreateAccount {
+      from: payer.to_account_info(),
+      to: pda,
+    };
+    let cpi_context = CpiContext::new_with_signer(
+      system_program.clone(),
+      cpi_accounts,
+      seeds.as_slice(),
+    );
+    create_account(
+      cpi_context,
+      minimum_balance,
+      space as u64,
+      owner_program.key,
+    )?;
+    return Ok(());
+  }
+
+  if current_balance < minimum_balance {
+    invoke(
+      &transfer(payer.key, pda.key, minimum_balance - current_balance),
+      &[payer.clone(), pda.clone(), system_program.clone()],
+    )?;
+  }
+  allocate(
+    CpiContext::new_with_signer(
+      system_program.clone(),
+      Allocate {
+        account_to_allocate: pda.clone(),
+      },
+      seeds.as_slice(),
+    ),
     space as u64,
+  )?;
+  assign(
+    CpiContext::new_with_signer(
+      system_program.clone(),
+      Assign {
+        account_to_assign: pda,
+      },
+      seeds.as_slice(),
+    ),
     owner_program.key,
   )?;
   Ok(())
@@ -162,3 +201,29 @@ pub fn resize_account<'info>(
   account_info.realloc(new_space, false)?;
   Ok(())
 }
+
+/// Grows the account to the new space if it's smaller (e.g.: an account of an
+/// older layout), where the added bytes are zeroed and the rent of the added
+/// space is paid by the payer. The lamports held by the account are never
+/// withdrawn.
+pub fn grow_account<'info>(
+  account_info: AccountInfo<'info>,
+  new_space: usize,
+  payer: AccountInfo<'info>,
+  system_program: AccountInfo<'info>,
+) -> Result<()> {
+  if account_info.data_len() >= new_space {
+    return Ok(());
+  }
+  let new_minimum_balance = Rent::get()?.minimum_balance(new_space);
+  let lamports_diff =
+    new_minimum_balance.saturating_sub(account_info.lamports());
+  if lamports_diff > 0 {
+    invoke(
+      &transfer(&payer.key(), &account_info.key(), lamports_diff),
+      &[payer, account_info.clone(), system_program],
+    )?;
+  }
+  account_info.realloc(new_space, true)?;
+  Ok(())
+}
@@ -104,6 +104,16 @@ impl<'info> EligibilityCheckStrategy<'info> {
     Ok(())
   }
 
+  /// Whether the signer is a member of the group, as proven by the eligibility
+  /// check input.
+  pub fn is_group_member(&self, group: &GroupConfig) -> bool {
+    match self.group_type {
+      Some(GroupType::NftHolderGroup) => self.is_nft_holder(group),
+      Some(GroupType::TokenHolderGroup) => self.is_token_holder(group),
+      _ => false,
+    }
+  }
+
   fn check_group_membership<IsGroupMemberFn>(
     &self,
     is_group_member_fn: IsGroupMemberFn,
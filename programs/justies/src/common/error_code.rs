@@ -114,7 +114,7 @@ pub enum JustiesErrorCode {
   InvalidAuctionCreatorNftAccount = 56,
   #[msg("invalid nft metadata")]
   InvalidNftMetadata = 57,
-  #[msg("the nft collection is not in the allowlist")]
+  #[msg("neither the nft nor its collection is in the allowlist")]
   NftCollectionNotInAllowlist = 58,
   #[msg("the token is not in the allowlist")]
   TokenNotInAllowlist = 59,
@@ -140,8 +140,116 @@ pub enum JustiesErrorCode {
   InvalidRaffleCreatorNftAccount = 69,
   #[msg("the raffle ticket position account contains inconsistent states")]
   InconsistentRaffleTicketPosition = 70,
+  // Replaced by FeatureDisabled since 1.7.
   #[msg("auction creation is disabled")]
   AuctionCreationDisabled = 71,
+  // Replaced by FeatureDisabled since 1.7.
   #[msg("raffle creation is disabled")]
   RaffleCreationDisabled = 72,
+  #[msg("the signer is not the pending authority")]
+  NotThePendingAuthority = 73,
+  #[msg("there is no pending authority")]
+  NoPendingAuthority = 74,
+  #[msg("the signer doesn't hold the required admin role")]
+  MissingAdminRole = 75,
+  #[msg("invalid multisig members or threshold")]
+  InvalidMultisigSettings = 76,
+  #[msg("the signer is not a multisig member")]
+  NotAMultisigMember = 77,
+  #[msg("the admin proposal is already approved by the signer")]
+  AdminProposalAlreadyApproved = 78,
+  #[msg("the admin proposal is already executed")]
+  AdminProposalAlreadyExecuted = 79,
+  #[msg("the admin proposal doesn't have enough approvals")]
+  NotEnoughAdminProposalApprovals = 80,
+  #[msg("invalid admin proposal instruction")]
+  InvalidAdminProposalInstruction = 81,
+  #[msg("the market fee rate & the fee treasury can only be changed via queue_config_changes")]
+  TimelockRequired = 82,
+  #[msg("the pending config changes are not activated yet")]
+  PendingConfigsNotActivated = 83,
+  #[msg("invalid config timelock delay")]
+  InvalidConfigTimelockDelay = 84,
+  #[msg("the proposer doesn't match the pending config changes")]
+  InvalidPendingConfigsProposer = 85,
+  #[msg("the allowlist entry is already suspended")]
+  AllowlistEntryAlreadySuspended = 86,
+  #[msg("the allowlist entry is not suspended")]
+  AllowlistEntryNotSuspended = 87,
+  #[msg("the allowlist states account mismatches the token mint")]
+  InvalidAllowlistStatesAccount = 88,
+  #[msg("the token is already in the allowlist")]
+  TokenAlreadyInAllowlist = 89,
+  #[msg("the allowlist index page is missing")]
+  MissingAllowlistIndexPage = 90,
+  #[msg("the fee treasury token account is invalid")]
+  InvalidFeeTreasuryTokenAccount = 91,
+  #[msg("the nft allowlist states mismatch the nft")]
+  InvalidNftAllowlistStates = 92,
+  #[msg("invalid currency listing limits")]
+  InvalidCurrencyListingLimits = 93,
+  #[msg("the start bid is below the minimum of the currency")]
+  InvalidStartBid = 94,
+  #[msg("the ticket price is out of the range of the currency")]
+  InvalidTicketPrice = 95,
+  #[msg("the minimum outbid increment of the currency isn't met")]
+  NotMetMinOutbidIncrement = 96,
+  #[msg("the market fee rate of the currency exceeds the global one")]
+  InvalidCurrencyMarketFeeRate = 97,
+  #[msg("the market fee rate of the collection exceeds the global one")]
+  InvalidCollectionMarketFeeRate = 98,
+  #[msg("invalid listing rules of the collection")]
+  InvalidCollectionListingRules = 99,
+  #[msg("bidding is paused")]
+  BiddingDisabled = 100,
+  #[msg("raffle ticket sales are paused")]
+  TicketSalesDisabled = 101,
+  #[msg("claims are paused")]
+  ClaimsDisabled = 102,
+  #[msg("the nft recipient is neither the creator nor the quarantine wallet")]
+  InvalidNftRecipient = 103,
+  #[msg("the auction revenue has been claimed")]
+  AuctionNotForceCancelable = 104,
+  #[msg("the raffle rewards have been claimed")]
+  RaffleNotForceCancelable = 105,
+  #[msg("the bid escrow token account mismatches the top bidder")]
+  InvalidTopBidEscrowAccount = 106,
+  #[msg("the raffle is not force-cancelled")]
+  RaffleNotForceCancelled = 107,
+  #[msg("no raffle tickets to refund")]
+  NoRaffleTicketsToRefund = 108,
+  #[msg("the wallet is blocked")]
+  WalletBlocked = 109,
+  #[msg("invalid wallet blocklist states account")]
+  InvalidWalletBlocklistStates = 110,
+  #[msg("the creator is not curated")]
+  CreatorNotCurated = 111,
+  #[msg("the creator has reached the cap on live listings")]
+  TooManyLiveListings = 112,
+  #[msg("the version of the global states account is incompatible")]
+  IncompatibleGlobalStatesVersion = 113,
+  #[msg("the feature is disabled")]
+  FeatureDisabled = 114,
+  #[msg("invalid fee split config")]
+  InvalidFeeSplit = 115,
+  #[msg("fee distribution accounts doesn't match the fee split config")]
+  FeeDistributionAccountsDoesntMatch = 116,
+  #[msg("only the listing fee in SOL can be refundable")]
+  InvalidListingFeeConfigs = 117,
+  #[msg("invalid listing fee token account")]
+  InvalidListingFeeTokenAccount = 118,
+  #[msg("invalid fee tiers config")]
+  InvalidFeeTiers = 119,
+  #[msg("invalid fee discounts config")]
+  InvalidFeeDiscounts = 120,
+  #[msg("invalid index page")]
+  InvalidIndexPage = 121,
+  #[msg("the creator states account contains inconsistent states")]
+  InconsistentCreatorStates = 122,
+  #[msg("invalid creator states account")]
+  InvalidCreatorStatesAccount = 123,
+  #[msg("invalid listing fee deposit")]
+  InvalidListingFeeDeposit = 124,
+  #[msg("the pending config changes are already activated")]
+  PendingConfigsActivated = 125,
 }
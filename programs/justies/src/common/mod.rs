@@ -22,3 +22,7 @@ pub mod configs_strategy;
 pub use configs_strategy::*;
 pub mod pubkey_indexing;
 pub use pubkey_indexing::*;
+pub mod allowlist_utils;
+pub use allowlist_utils::*;
+pub mod testing_instructions;
+pub use testing_instructions::*;
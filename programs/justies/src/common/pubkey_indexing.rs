@@ -1,5 +1,5 @@
 use crate::common::{
-  create_pda, is_account_initialized, resize_account, PubkeyIndexPage,
+  create_pda, resize_account, JustiesErrorCode, PubkeyIndexPage,
 };
 use anchor_lang::prelude::*;
 
@@ -23,8 +23,9 @@ pub fn index_pubkey<'info>(
   system_program_info: AccountInfo<'info>,
 ) -> Result<()> {
   let mut index_page: Account<'info, PubkeyIndexPage>;
-  // Creates the indexing page if it is uninitialized.
-  if !is_account_initialized(&index_page_info) {
+  // Creates the indexing page if it is not created by the program yet (the
+  // page address may have been funded by anyone beforehand).
+  if index_page_info.owner != owner_program_info.key {
     create_pda(
       payer_info.clone(),
       index_page_info.clone(),
@@ -60,3 +61,48 @@ pub fn index_pubkey<'info>(
   index_page.exit(owner_program_info.key)?;
   Ok(())
 }
+
+/// Removes a pubkey from the index, where the last indexed key is moved into
+/// its slot so that the indexed keys stay contiguous (i.e.: the key at any
+/// position below the total number of keys is still found via page_id()).
+///
+/// The index page holding the key and the last index page can be the same
+/// account. The last index page is not shrunk, as it's resized when the next
+/// key is indexed.
+pub fn unindex_pubkey<'info>(
+  key: Pubkey,
+  index_page_info: &AccountInfo<'info>,
+  last_index_page_info: &AccountInfo<'info>,
+) -> Result<()> {
+  let mut last_index_page: Account<'info, PubkeyIndexPage> =
+    Account::try_from(last_index_page_info)?;
+  let last_key = last_index_page
+    .keys
+    .pop()
+    .ok_or_else(|| error!(JustiesErrorCode::InvalidIndexPage))?;
+  if last_key != key {
+    if index_page_info.key() == last_index_page_info.key() {
+      replace_key(&mut last_index_page.keys, key, last_key)?;
+    } else {
+      let mut index_page: Account<'info, PubkeyIndexPage> =
+        Account::try_from(index_page_info)?;
+      replace_key(&mut index_page.keys, key, last_key)?;
+      index_page.exit(&crate::ID)?;
+    }
+  }
+  last_index_page.exit(&crate::ID)?;
+  Ok(())
+}
+
+fn replace_key(
+  keys: &mut [Pubkey],
+  key: Pubkey,
+  new_key: Pubkey,
+) -> Result<()> {
+  let slot = keys
+    .iter_mut()
+    .find(|indexed_key| **indexed_key == key)
+    .ok_or_else(|| error!(JustiesErrorCode::InvalidIndexPage))?;
+  *slot = new_key;
+  Ok(())
+}
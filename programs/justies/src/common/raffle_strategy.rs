@@ -3,7 +3,10 @@ use anchor_lang::Key;
 use anchor_lang::ToAccountInfo;
 use anchor_spl::token::{Token, TokenAccount};
 
-use crate::admin::GlobalStates;
+use crate::admin::{
+  CreatorStates, CurrencyListingLimits, Feature, GlobalStates,
+  ListingRuleOverrides,
+};
 use crate::common::cpi_utils::{
   close_token_account_with_signer, transfer_token,
 };
@@ -42,6 +45,7 @@ impl<'accounts, 'info> RaffleStrategy<'accounts, 'info> {
     }
   }
 
+  #[cfg(feature = "testing")]
   pub fn validate_set_winners(&self, current_timestamp: i64) -> Result<()> {
     if self.is_cancelled() {
       return err!(JustiesErrorCode::RaffleCancelled);
@@ -65,10 +69,7 @@ impl<'accounts, 'info> RaffleStrategy<'accounts, 'info> {
     }
 
     if rerun {
-      if !self.global_states.is_test_environment {
-        return err!(JustiesErrorCode::NotTestEnvironment);
-      }
-      return Ok(());
+      return self.validate_rerun();
     }
 
     if self.has_winners() {
@@ -77,7 +78,25 @@ impl<'accounts, 'info> RaffleStrategy<'accounts, 'info> {
     Ok(())
   }
 
+  // Rerunning the raffle is only compiled into the builds with the "testing"
+  // feature.
+  #[cfg(feature = "testing")]
+  fn validate_rerun(&self) -> Result<()> {
+    if !self.global_states.is_test_mode() {
+      return err!(JustiesErrorCode::NotTestEnvironment);
+    }
+    Ok(())
+  }
+
+  #[cfg(not(feature = "testing"))]
+  fn validate_rerun(&self) -> Result<()> {
+    err!(JustiesErrorCode::NotTestEnvironment)
+  }
+
   pub fn validate_claim_remaining_rewards(&self) -> Result<()> {
+    if self.is_cancelled() {
+      return err!(JustiesErrorCode::RaffleCancelled);
+    }
     if !self.has_winners() {
       return err!(JustiesErrorCode::RaffleNotMade);
     }
@@ -93,6 +112,7 @@ impl<'accounts, 'info> RaffleStrategy<'accounts, 'info> {
 
   pub fn is_cancelled(&self) -> bool {
     self.raffle.status == ListingStatus::Cancelled
+      || self.raffle.status == ListingStatus::ForceCancelled
   }
 
   pub fn is_raffle_made(&self) -> bool {
@@ -144,16 +164,36 @@ impl<'accounts, 'info> RaffleStrategy<'accounts, 'info> {
     ticket_price: u64,
     eligible_groups: &Vec<GroupConfig>,
     revenue_shares: &Vec<RevenueShareConfig>,
+    listing_limits: &CurrencyListingLimits,
+    listing_rules: &ListingRuleOverrides,
+    creator_states: &mut CreatorStates,
     current_timestamp: i64,
   ) -> Result<()> {
-    if (duration as u64) < self.global_states.min_raffle_duration
-      || (duration as u64) > self.global_states.max_raffle_duration
+    self
+      .global_states
+      .check_feature_enabled(Feature::RaffleCreation)?;
+    creator_states.validate_new_listing(self.global_states)?;
+    if !eligible_groups.is_empty() {
+      self
+        .global_states
+        .check_feature_enabled(Feature::ExclusiveRaffles)?;
+    }
+    if num_raffled_nft > 1 {
+      self
+        .global_states
+        .check_feature_enabled(Feature::MultiNftRaffles)?;
+    }
+
+    let (min_raffle_duration, max_raffle_duration) =
+      listing_rules.raffle_duration_range(self.global_states);
+    if (duration as u64) < min_raffle_duration
+      || (duration as u64) > max_raffle_duration
     {
       return err!(JustiesErrorCode::InvalidRaffleDuration);
     }
 
     if num_raffled_nft == 0
-      || num_raffled_nft > self.global_states.max_raffled_nfts
+      || num_raffled_nft > listing_rules.max_raffled_nfts(self.global_states)
     {
       return err!(JustiesErrorCode::InvalidNumRaffledNfts);
     }
@@ -164,7 +204,16 @@ impl<'accounts, 'info> RaffleStrategy<'accounts, 'info> {
       return err!(JustiesErrorCode::InvalidRaffleTicketSupply);
     }
 
+    if ticket_price < listing_limits.min_ticket_price
+      || listing_limits
+        .max_ticket_price
+        .map_or(false, |max_ticket_price| ticket_price > max_ticket_price)
+    {
+      return err!(JustiesErrorCode::InvalidTicketPrice);
+    }
+
     self.global_states.total_raffles += 1;
+    creator_states.num_live_raffles += 1;
 
     self.raffle.id = id;
     self.raffle.bump = bump;
@@ -183,6 +232,7 @@ impl<'accounts, 'info> RaffleStrategy<'accounts, 'info> {
     self.raffle.status = ListingStatus::InProgress;
     self.raffle.winner_ids = vec![];
     self.raffle.claim_mask = 0;
+    self.raffle.counted_as_live = true;
     Ok(())
   }
 
@@ -308,6 +358,7 @@ impl<'accounts, 'info> RaffleStrategy<'accounts, 'info> {
     Ok(())
   }
 
+  #[cfg(feature = "testing")]
   pub fn set_winners(&mut self, winner_ids: &Vec<u16>) {
     self.raffle.winner_ids = winner_ids.clone();
     self.raffle.status = ListingStatus::Finished;
@@ -329,6 +380,10 @@ impl<'accounts, 'info> RaffleStrategy<'accounts, 'info> {
     self.raffle.status = ListingStatus::Cancelled;
   }
 
+  pub fn force_cancel(&mut self) {
+    self.raffle.status = ListingStatus::ForceCancelled;
+  }
+
   pub fn claim_reward(&mut self, ticket_position_id: u64) {
     let winner_index = self.get_winner_index(ticket_position_id).unwrap();
     self.raffle.claim_mask |= 1 << winner_index;
@@ -372,6 +427,42 @@ impl<'accounts, 'info> RaffleStrategy<'accounts, 'info> {
     Ok(())
   }
 
+  pub fn refund_tickets(
+    &mut self,
+    token_program: &'accounts Program<'info, Token>,
+    ticket_position: &mut Account<'info, RaffleTicketPosition>,
+    revenue_escrow_token_account: &'accounts Account<'info, TokenAccount>,
+    buyer_token_account: &'accounts Account<'info, TokenAccount>,
+  ) -> Result<()> {
+    transfer_token(
+      token_program,
+      revenue_escrow_token_account,
+      buyer_token_account,
+      self.raffle.to_account_info(),
+      (ticket_position.total_num_tickets as u64) * self.raffle.ticket_price,
+      Some(self.get_raffle_signer_seed().as_ref()),
+    )?;
+    ticket_position.total_num_tickets = 0;
+    Ok(())
+  }
+
+  pub fn try_close_revenue_escrow_token_account(
+    &mut self,
+    token_program: &Program<'info, Token>,
+    revenue_escrow_token_account: &mut Account<'info, TokenAccount>,
+    raffle_creator: &AccountInfo<'info>,
+  ) -> Result<()> {
+    revenue_escrow_token_account.reload()?;
+    if revenue_escrow_token_account.amount != 0 {
+      return Ok(());
+    }
+    self.close_revenue_escrow_token_account(
+      token_program,
+      revenue_escrow_token_account,
+      raffle_creator,
+    )
+  }
+
   pub fn close_revenue_escrow_token_account(
     &mut self,
     token_program: &Program<'info, Token>,
@@ -1,10 +1,12 @@
-use crate::admin::GlobalStates;
+use crate::admin::{CreatorVolume, CurrencyStats, GlobalStates};
 use crate::common::cpi_utils::{
   close_token_account_with_signer, create_associated_token_account,
   transfer_token,
 };
 use crate::common::{
-  JustiesErrorCode, RevenueDistribution, RevenueShareConfig, ShareConfig,
+  EligibilityCheckInput, EligibilityCheckStrategy, FeeDiscountConfig,
+  FeeShareConfig, JustiesErrorCode, ListingType, RevenueDistribution,
+  RevenueShareConfig, ShareConfig,
 };
 use anchor_lang::prelude::*;
 use anchor_lang::{Key, ToAccountInfo};
@@ -13,6 +15,7 @@ use anchor_spl::associated_token::{
 };
 use anchor_spl::token::{Token, TokenAccount};
 use itertools::izip;
+use std::cmp;
 
 pub fn validate_share_configs<T>(share_configs: &Vec<T>) -> Result<()>
 where
@@ -76,6 +79,98 @@ pub fn init_revenue_distribution_accounts<'info>(
     .collect()
 }
 
+/// Splits the remaining accounts of a revenue claim into the revenue
+/// distribution accounts, the fee distribution accounts (i.e.: the token
+/// accounts of the fee treasuries) and the eligibility check payloads of the
+/// fee discount, in that order.
+pub fn split_distribution_accounts<'info>(
+  remaining_accounts: &[AccountInfo<'info>],
+  revenue_share_configs: &Vec<RevenueShareConfig>,
+  fee_share_configs: &Vec<FeeShareConfig>,
+) -> (
+  Vec<AccountInfo<'info>>,
+  Vec<AccountInfo<'info>>,
+  Vec<AccountInfo<'info>>,
+) {
+  let (revenue_distribution_accounts, remaining_accounts) = remaining_accounts
+    .split_at(cmp::min(
+      revenue_share_configs.len() * 2,
+      remaining_accounts.len(),
+    ));
+  let (fee_distribution_accounts, account_payloads) = remaining_accounts
+    .split_at(cmp::min(fee_share_configs.len(), remaining_accounts.len()));
+  (
+    revenue_distribution_accounts.to_vec(),
+    fee_distribution_accounts.to_vec(),
+    account_payloads.to_vec(),
+  )
+}
+
+/// The market fee discount of the creator, i.e.: the highest discount among
+/// the groups that the creator proves the membership of, or 0 without the
+/// eligibility check input.
+pub fn fee_discount_bps<'info>(
+  fee_discounts: &[FeeDiscountConfig],
+  creator: Pubkey,
+  eligibility_check_input: Option<EligibilityCheckInput>,
+  account_payloads: &[AccountInfo<'info>],
+) -> Result<u16> {
+  if eligibility_check_input.is_none() {
+    return Ok(0);
+  }
+  let eligibility_check_strategy = EligibilityCheckStrategy::new(
+    &vec![],
+    creator,
+    eligibility_check_input,
+    account_payloads,
+  )?;
+  fee_discounts
+    .iter()
+    .filter(|config| eligibility_check_strategy.is_group_member(&config.group))
+    .map(|config| config.discount_bps)
+    .max()
+    .ok_or_else(|| error!(JustiesErrorCode::Ineligible))
+}
+
+/// Loads the token accounts of the fee treasuries, which must be the associated
+/// token accounts of the treasuries in the same order as the fee shares.
+///
+/// The share of a treasury whose associated token account doesn't exist (e.g.:
+/// closed by the treasury) goes to the fee treasury instead, so that a single
+/// treasury can't block the revenue claims.
+pub fn init_fee_distribution_accounts<'info>(
+  token_mint: AccountInfo<'info>,
+  fee_shares: &Vec<FeeShareConfig>,
+  fee_distribution_accounts: &Vec<AccountInfo<'info>>,
+  fee_treasury_token_account: &Account<'info, TokenAccount>,
+) -> Result<Vec<RevenueDistribution<'info>>> {
+  if fee_shares.len() != fee_distribution_accounts.len() {
+    return err!(JustiesErrorCode::FeeDistributionAccountsDoesntMatch);
+  }
+
+  fee_shares
+    .iter()
+    .zip(fee_distribution_accounts)
+    .map(|(config, account)| -> Result<RevenueDistribution> {
+      let associated_token_account =
+        get_associated_token_address(&config.treasury, &token_mint.key());
+      if !associated_token_account.eq(&account.key()) {
+        return err!(JustiesErrorCode::FeeDistributionAccountsDoesntMatch);
+      }
+      let treasury_token_account: Account<'info, TokenAccount> =
+        if account.owner == &Token::id() {
+          Account::try_from(account)?
+        } else {
+          fee_treasury_token_account.clone()
+        };
+      Ok(RevenueDistribution {
+        revenue_receiver_token_account: treasury_token_account,
+        share_bps: config.share_bps,
+      })
+    })
+    .collect()
+}
+
 fn validate_revenue_distribution_accounts<'info>(
   token_mint: AccountInfo<'info>,
   revenue_share_configs: &Vec<RevenueShareConfig>,
@@ -126,6 +221,13 @@ where
   pub revenue_escrow_token_account: &'accounts mut Account<'info, TokenAccount>,
   pub fee_treasury_token_account: &'accounts Account<'info, TokenAccount>,
   pub global_states: &'accounts Account<'info, GlobalStates>,
+  /// The market fee rate override of the currency token (if any).
+  pub currency_market_fee_rate_bps: Option<u16>,
+  /// The market fee rate override of the NFT collection (if any).
+  pub collection_market_fee_rate_bps: Option<u16>,
+  /// The market fee discount of the creator's group (if any), see
+  /// fee_discount_bps().
+  pub fee_discount_bps: u16,
   /// CHECK: this is safe as no data are read from it and the sanity are
   /// checked via account constraints.
   pub escrow_authority: AccountInfo<'info>,
@@ -134,6 +236,15 @@ where
   pub escrow_token_account_creator: AccountInfo<'info>,
   pub escrow_signer_seed_fn: EscrowSignerSeedFn,
   pub revenue_distributions: Vec<RevenueDistribution<'info>>,
+  /// The split of the market fee across the treasuries, where the whole fee
+  /// goes to the fee treasury when empty.
+  pub fee_distributions: Vec<RevenueDistribution<'info>>,
+  /// The fee ledger of the currency token, which records the settlement.
+  pub currency_stats: &'accounts mut Account<'info, CurrencyStats>,
+  /// The creator's settled volume in the currency token, which determines the
+  /// creator's fee tier and records the settlement.
+  pub creator_volume: &'accounts mut Account<'info, CreatorVolume>,
+  pub listing_type: ListingType,
 }
 
 impl<'accounts, 'info, EscrowSignerSeedFn>
@@ -142,7 +253,12 @@ where
   EscrowSignerSeedFn: Fn() -> Vec<&'accounts [u8]>,
 {
   pub fn validate(&self) -> Result<()> {
-    validate_share_configs(&self.revenue_distributions)
+    validate_share_configs(&self.revenue_distributions)?;
+    if !self.fee_distributions.is_empty() {
+      validate_share_configs(&self.fee_distributions)
+        .map_err(|_| error!(JustiesErrorCode::InvalidFeeSplit))?;
+    }
+    Ok(())
   }
 
   pub fn distribute_revenue(
@@ -150,10 +266,17 @@ where
     token_program: &'accounts Program<'info, Token>,
   ) -> Result<()> {
     let revenue_distributions = self.revenue_distributions.to_vec();
-    let mut remaining_revenue = self.revenue_escrow_token_account.amount;
+    let total_revenue = self.revenue_escrow_token_account.amount;
+    let mut remaining_revenue = total_revenue;
     let mut remaining_share = 10000_u16;
     remaining_revenue = self.collect_fees(token_program, remaining_revenue)?;
     let total_amount = remaining_revenue;
+    self.currency_stats.record_settlement(
+      self.listing_type,
+      total_revenue,
+      total_revenue - total_amount,
+    );
+    self.creator_volume.record_settlement(total_revenue);
 
     for revenue_distribution in revenue_distributions.iter() {
       (remaining_revenue, remaining_share) = self.make_distribution(
@@ -168,23 +291,58 @@ where
     Ok(())
   }
 
+  /// The market fee rate of the revenue. The collection's override takes
+  /// precedence over the currency's one, and both are capped by the global rate
+  /// as only the latter is timelocked. The rate of the creator's fee tier (if
+  /// any) applies instead when it is lower, where the tier is determined by the
+  /// volume settled before this revenue. The creator's fee discount (if any)
+  /// applies on top of that.
+  pub fn market_fee_rate_bps(&self) -> u16 {
+    let global_rate_bps = self.global_states.market_fee_rate_bps;
+    let rate_bps = self
+      .collection_market_fee_rate_bps
+      .or(self.currency_market_fee_rate_bps)
+      .map_or(global_rate_bps, |rate_bps| {
+        cmp::min(rate_bps, global_rate_bps)
+      });
+    let rate_bps = self
+      .global_states
+      .fee_tier_rate_bps(self.creator_volume.total_volume)
+      .map_or(rate_bps, |tier_rate_bps| cmp::min(tier_rate_bps, rate_bps));
+    (rate_bps as u64 * (10000 - self.fee_discount_bps) as u64 / 10000) as u16
+  }
+
   fn collect_fees(
     &mut self,
     token_program: &'accounts Program<'info, Token>,
     total_revenue: u64,
   ) -> Result<u64> {
     let fee_amount =
-      total_revenue * (self.global_states.market_fee_rate_bps as u64) / 10000;
-    let signer_seed = (self.escrow_signer_seed_fn)();
-
-    transfer_token(
-      token_program,
-      self.revenue_escrow_token_account,
-      self.fee_treasury_token_account,
-      self.escrow_authority.clone(),
-      fee_amount,
-      Some(&signer_seed),
-    )?;
+      total_revenue * (self.market_fee_rate_bps() as u64) / 10000;
+    if self.fee_distributions.is_empty() {
+      let signer_seed = (self.escrow_signer_seed_fn)();
+      transfer_token(
+        token_program,
+        self.revenue_escrow_token_account,
+        self.fee_treasury_token_account,
+        self.escrow_authority.clone(),
+        fee_amount,
+        Some(&signer_seed),
+      )?;
+    } else {
+      let fee_distributions = self.fee_distributions.to_vec();
+      let mut remaining_fee = fee_amount;
+      let mut remaining_share = 10000_u16;
+      for fee_distribution in fee_distributions.iter() {
+        (remaining_fee, remaining_share) = self.make_distribution(
+          token_program,
+          fee_amount,
+          fee_distribution,
+          remaining_fee,
+          remaining_share,
+        )?;
+      }
+    }
 
     Ok(total_revenue - fee_amount)
   }
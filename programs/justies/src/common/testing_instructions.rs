@@ -0,0 +1,144 @@
+#[cfg(feature = "testing")]
+use crate::admin::{
+  downgrade_global_states, set_mock_timestamp, DowngradeGlobalStates,
+  SetMockTimestamp,
+};
+#[cfg(feature = "testing")]
+use crate::auction::{downgrade_auction, DowngradeAuction};
+#[cfg(feature = "testing")]
+use crate::raffle::{
+  downgrade_raffle, set_raffle_winners, DowngradeRaffle, SetRaffleWinners,
+};
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+#[cfg(feature = "testing")]
+use anchor_lang::solana_program::hash::hash;
+#[cfg(feature = "testing")]
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(feature = "testing")]
+#[derive(AnchorDeserialize)]
+struct SetMockTimestampArgs {
+  mock_timestamp: Option<i64>,
+}
+
+#[cfg(feature = "testing")]
+#[derive(AnchorDeserialize)]
+struct SetRaffleWinnersArgs {
+  _raffle_id: u64,
+  winner_ids: Vec<u16>,
+}
+
+/// Dispatches the testing-only instructions (i.e.: set_mock_timestamp,
+/// downgrade_global_states, downgrade_auction, downgrade_raffle &
+/// set_raffle_winners).
+///
+/// Anchor can't exclude an instruction from the program by features, so these
+/// instructions are left out of the program module, and are dispatched here via
+/// the fallback of the program instead. They are identified by the same 8-byte
+/// discriminators as the other instructions, i.e.:
+/// sha256("global:<instruction_name>")[..8].
+///
+/// Only compiled into the builds with the "testing" feature, while the other
+/// builds never dispatch any of them.
+#[cfg(feature = "testing")]
+pub fn dispatch_testing_instruction<'info>(
+  program_id: &Pubkey,
+  accounts: &[AccountInfo<'info>],
+  data: &[u8],
+) -> Result<()> {
+  if data.len() < 8 {
+    return Err(ErrorCode::InstructionFallbackNotFound.into());
+  }
+  let (discriminator, ix_data) = data.split_at(8);
+  if discriminator == testing_discriminator("set_mock_timestamp") {
+    msg!("Instruction: SetMockTimestamp");
+    let args = deserialize_args::<SetMockTimestampArgs>(ix_data)?;
+    invoke_handler::<SetMockTimestamp>(program_id, accounts, ix_data, |ctx| {
+      set_mock_timestamp::handler(ctx, args.mock_timestamp)
+    })
+  } else if discriminator == testing_discriminator("downgrade_global_states") {
+    msg!("Instruction: DowngradeGlobalStates");
+    invoke_handler::<DowngradeGlobalStates>(
+      program_id,
+      accounts,
+      ix_data,
+      downgrade_global_states::handler,
+    )
+  } else if discriminator == testing_discriminator("downgrade_auction") {
+    msg!("Instruction: DowngradeAuction");
+    invoke_handler::<DowngradeAuction>(
+      program_id,
+      accounts,
+      ix_data,
+      downgrade_auction::handler,
+    )
+  } else if discriminator == testing_discriminator("downgrade_raffle") {
+    msg!("Instruction: DowngradeRaffle");
+    invoke_handler::<DowngradeRaffle>(
+      program_id,
+      accounts,
+      ix_data,
+      downgrade_raffle::handler,
+    )
+  } else if discriminator == testing_discriminator("set_raffle_winners") {
+    msg!("Instruction: SetRaffleWinners");
+    let args = deserialize_args::<SetRaffleWinnersArgs>(ix_data)?;
+    invoke_handler::<SetRaffleWinners>(program_id, accounts, ix_data, |ctx| {
+      set_raffle_winners::handler(ctx, args.winner_ids)
+    })
+  } else {
+    Err(ErrorCode::InstructionFallbackNotFound.into())
+  }
+}
+
+#[cfg(not(feature = "testing"))]
+pub fn dispatch_testing_instruction(
+  _program_id: &Pubkey,
+  _accounts: &[AccountInfo],
+  _data: &[u8],
+) -> Result<()> {
+  Err(ErrorCode::InstructionFallbackNotFound.into())
+}
+
+#[cfg(feature = "testing")]
+fn testing_discriminator(instruction_name: &str) -> [u8; 8] {
+  let preimage = format!("global:{}", instruction_name);
+  let mut discriminator = [0u8; 8];
+  discriminator.copy_from_slice(&hash(preimage.as_bytes()).to_bytes()[..8]);
+  discriminator
+}
+
+#[cfg(feature = "testing")]
+fn deserialize_args<T: AnchorDeserialize>(ix_data: &[u8]) -> Result<T> {
+  T::deserialize(&mut &ix_data[..])
+    .map_err(|_| ErrorCode::InstructionDidNotDeserialize.into())
+}
+
+/// Deserializes the accounts, invokes the handler & persists the accounts, the
+/// same way as the instructions dispatched by Anchor.
+#[cfg(feature = "testing")]
+fn invoke_handler<'info, T: Accounts<'info> + AccountsExit<'info>>(
+  program_id: &Pubkey,
+  accounts: &[AccountInfo<'info>],
+  ix_data: &[u8],
+  handler: impl FnOnce(Context<'_, '_, '_, 'info, T>) -> Result<()>,
+) -> Result<()> {
+  let mut bumps = BTreeMap::new();
+  let mut reallocs = BTreeSet::new();
+  let mut remaining_accounts = accounts;
+  let mut ix_accounts = T::try_accounts(
+    program_id,
+    &mut remaining_accounts,
+    ix_data,
+    &mut bumps,
+    &mut reallocs,
+  )?;
+  handler(Context::new(
+    program_id,
+    &mut ix_accounts,
+    remaining_accounts,
+    bumps,
+  ))?;
+  ix_accounts.exit(program_id)
+}
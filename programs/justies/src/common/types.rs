@@ -24,12 +24,21 @@ impl PubkeyIndexPage {
   }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ListingType {
+  Auction,
+  Raffle,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default)]
 pub enum ListingStatus {
   #[default]
   InProgress,
   Finished,
   Cancelled,
+  // Cancelled by the authority (e.g.: for fraudulent listings), after which
+  // all the escrowed funds can be refunded.
+  ForceCancelled,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy)]
@@ -43,6 +52,36 @@ impl RevenueShareConfig {
   pub const MAX_REVENUE_RECEIVERS: usize = 6;
 }
 
+/// A share of the market fee, which is sent to the associated token account of
+/// the treasury.
+#[derive(
+  AnchorSerialize, AnchorDeserialize, Default, Clone, Copy, PartialEq, Eq,
+)]
+pub struct FeeShareConfig {
+  pub treasury: Pubkey,
+  pub share_bps: u16,
+}
+
+impl FeeShareConfig {
+  pub const MAX_DATA_SIZE: usize = 32 + 2;
+  pub const MAX_FEE_TREASURIES: usize = 5;
+}
+
+/// A tier of the market fee rate, which applies to the creators whose settled
+/// volume in the currency token (in the token's own units) reaches min_volume.
+#[derive(
+  AnchorSerialize, AnchorDeserialize, Default, Clone, Copy, PartialEq, Eq,
+)]
+pub struct FeeTierConfig {
+  pub min_volume: u64,
+  pub market_fee_rate_bps: u16,
+}
+
+impl FeeTierConfig {
+  pub const MAX_DATA_SIZE: usize = 8 + 2;
+  pub const MAX_FEE_TIERS: usize = 5;
+}
+
 #[derive(Clone)]
 pub struct RevenueDistribution<'info> {
   pub revenue_receiver_token_account: Account<'info, TokenAccount>,
@@ -59,6 +98,12 @@ impl ShareConfig for RevenueShareConfig {
   }
 }
 
+impl ShareConfig for FeeShareConfig {
+  fn share_bps(&self) -> u16 {
+    self.share_bps
+  }
+}
+
 impl<'info> ShareConfig for RevenueDistribution<'info> {
   fn share_bps(&self) -> u16 {
     self.share_bps
@@ -97,6 +142,20 @@ impl GroupConfig {
   pub const MAX_GROUP_CONFIGS: usize = 10;
 }
 
+/// A discount off the market fee for the creators in the group (e.g.: the
+/// holders of a membership collection), who prove the membership with an
+/// eligibility check input when claiming the revenue.
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy)]
+pub struct FeeDiscountConfig {
+  pub group: GroupConfig,
+  pub discount_bps: u16,
+}
+
+impl FeeDiscountConfig {
+  pub const MAX_DATA_SIZE: usize = GroupConfig::MAX_DATA_SIZE + 2;
+  pub const MAX_FEE_DISCOUNTS: usize = 5;
+}
+
 // The input data required for eligibility check.
 // The input and associated remaining_accounts payload will be matched against
 // the specified eligible_groups configs of the auction/raffle.
@@ -1,14 +1,142 @@
-use crate::admin::GlobalStates;
+use crate::admin::{GlobalStates, TokenAllowlistStates, WalletBlocklistStates};
+use crate::common::{transfer_token, JustiesErrorCode, RevenueShareConfig};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::system_instruction::transfer;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{Token, TokenAccount};
 
-pub fn get_current_timestamp(global_states: &Account<GlobalStates>) -> i64 {
+#[cfg_attr(not(feature = "testing"), allow(unused_variables))]
+pub fn get_current_timestamp(global_states: &GlobalStates) -> i64 {
   let real_timestamp = Clock::get().unwrap().unix_timestamp;
-  if !global_states.is_test_environment {
-    return real_timestamp;
+  // The mock timestamp is only compiled into the builds with the "testing"
+  // feature.
+  #[cfg(feature = "testing")]
+  if global_states.is_test_mode() {
+    if let Some(mock_timestamp) = global_states.mock_timestamp {
+      return mock_timestamp;
+    }
   }
+  real_timestamp
+}
+
+/// Checks that the currency token & the NFT collection of a listing are still
+/// allowed, i.e.: they are neither suspended nor removed from the allowlists.
+pub fn check_listing_allowlisted(
+  token_allowlist_states: &AccountInfo,
+  nft_allowlist_states: &AccountInfo,
+) -> Result<()> {
+  if !TokenAllowlistStates::is_allowed(token_allowlist_states)? {
+    return err!(JustiesErrorCode::TokenNotInAllowlist);
+  }
+  if !TokenAllowlistStates::is_allowed(nft_allowlist_states)? {
+    return err!(JustiesErrorCode::NftCollectionNotInAllowlist);
+  }
+  Ok(())
+}
+
+/// Checks that none of the revenue receivers is blocked, where the blocklist
+/// states pdas of the receivers are given in the same order as the revenue
+/// shares.
+pub fn check_revenue_receivers_not_blocked(
+  revenue_shares: &[RevenueShareConfig],
+  blocklist_states_infos: &[AccountInfo],
+) -> Result<()> {
+  if revenue_shares.len() != blocklist_states_infos.len() {
+    return err!(JustiesErrorCode::InvalidWalletBlocklistStates);
+  }
+  for (revenue_share, blocklist_states_info) in
+    revenue_shares.iter().zip(blocklist_states_infos)
+  {
+    let (blocklist_states_key, _) = Pubkey::find_program_address(
+      &[
+        b"wallet_blocklist_states",
+        revenue_share.revenue_receiver.as_ref(),
+      ],
+      &crate::ID,
+    );
+    if blocklist_states_info.key() != blocklist_states_key {
+      return err!(JustiesErrorCode::InvalidWalletBlocklistStates);
+    }
+    if WalletBlocklistStates::is_blocked(blocklist_states_info) {
+      return err!(JustiesErrorCode::WalletBlocked);
+    }
+  }
+  Ok(())
+}
+
+/// Collects the listing fee (if any) from the creator upon the creation of a
+/// listing, and returns the amount held by the listing as the refundable
+/// deposit.
+///
+/// The listing fee in SOL is transferred to the fee treasury, or to the listing
+/// itself when refundable. The listing fee in tokens is transferred between the
+/// associated token accounts of the creator and the fee treasury.
+#[allow(clippy::too_many_arguments)]
+pub fn collect_listing_fee<'info>(
+  global_states: &GlobalStates,
+  creator: AccountInfo<'info>,
+  listing: AccountInfo<'info>,
+  fee_treasury: AccountInfo<'info>,
+  creator_token_account: &AccountInfo<'info>,
+  fee_treasury_token_account: &AccountInfo<'info>,
+  token_program: &Program<'info, Token>,
+  system_program: &Program<'info, System>,
+) -> Result<u64> {
+  let listing_fee = global_states.listing_fee;
+  if listing_fee == 0 {
+    return Ok(0);
+  }
+
+  if global_states.is_listing_fee_in_sol() {
+    let recipient = if global_states.listing_fee_refundable {
+      listing
+    } else {
+      fee_treasury
+    };
+    invoke(
+      &transfer(&creator.key(), &recipient.key(), listing_fee),
+      &[creator, recipient, system_program.to_account_info()],
+    )?;
+    return Ok(if global_states.listing_fee_refundable {
+      listing_fee
+    } else {
+      0
+    });
+  }
+
+  let token_mint = global_states.listing_fee_token_mint;
+  if creator_token_account.key()
+    != get_associated_token_address(&creator.key(), &token_mint)
+    || fee_treasury_token_account.key()
+      != get_associated_token_address(&fee_treasury.key(), &token_mint)
+  {
+    return err!(JustiesErrorCode::InvalidListingFeeTokenAccount);
+  }
+  let from = Account::<TokenAccount>::try_from(creator_token_account)?;
+  let to = Account::<TokenAccount>::try_from(fee_treasury_token_account)?;
+  transfer_token(token_program, &from, &to, creator, listing_fee, None)?;
+  Ok(0)
+}
 
-  match global_states.mock_timestamp {
-    None => real_timestamp,
-    Some(mock_timestamp) => mock_timestamp,
+/// Releases the refundable listing fee held by a listing to the recipient,
+/// i.e.: the creator once the listing settles, or the fee treasury once the
+/// listing is cancelled.
+pub fn release_listing_fee_deposit(
+  listing: &AccountInfo,
+  recipient: &AccountInfo,
+  deposit: u64,
+) -> Result<()> {
+  if deposit == 0 {
+    return Ok(());
   }
+  **listing.try_borrow_mut_lamports()? = listing
+    .lamports()
+    .checked_sub(deposit)
+    .ok_or_else(|| error!(JustiesErrorCode::InvalidListingFeeDeposit))?;
+  **recipient.try_borrow_mut_lamports()? = recipient
+    .lamports()
+    .checked_add(deposit)
+    .ok_or_else(|| error!(JustiesErrorCode::InvalidListingFeeDeposit))?;
+  Ok(())
 }
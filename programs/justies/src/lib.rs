@@ -23,13 +23,6 @@ pub mod justies {
   // Admin Instructions
   //////////////////////////////////////////////////////////////////////////////
 
-  pub fn set_mock_timestamp(
-    ctx: Context<SetMockTimestamp>,
-    mock_timestamp: Option<i64>,
-  ) -> Result<()> {
-    admin::set_mock_timestamp::handler(ctx, mock_timestamp)
-  }
-
   pub fn init_justies_program(
     ctx: Context<InitJustiesProgram>,
     market_fee_rate_bps: u16,
@@ -51,6 +44,127 @@ pub mod justies {
     admin::update_configs::handler(ctx, input)
   }
 
+  pub fn migrate_global_states(
+    ctx: Context<MigrateGlobalStates>,
+  ) -> Result<()> {
+    admin::migrate_global_states::handler(ctx)
+  }
+
+  pub fn migrate_allowlist_states(
+    ctx: Context<MigrateAllowlistStates>,
+    allowlist_type: AllowlistType,
+    token_mint_address: Pubkey,
+  ) -> Result<()> {
+    admin::migrate_allowlist_states::handler(
+      ctx,
+      allowlist_type,
+      token_mint_address,
+    )
+  }
+
+  pub fn set_fee_discounts(
+    ctx: Context<SetFeeDiscounts>,
+    fee_discounts: Vec<FeeDiscountConfig>,
+  ) -> Result<()> {
+    admin::set_fee_discounts::handler(ctx, fee_discounts)
+  }
+
+  pub fn set_fee_tiers(
+    ctx: Context<SetFeeTiers>,
+    fee_tiers: Vec<FeeTierConfig>,
+  ) -> Result<()> {
+    admin::set_fee_tiers::handler(ctx, fee_tiers)
+  }
+
+  pub fn set_feature_enabled(
+    ctx: Context<SetFeatureEnabled>,
+    feature: Feature,
+    enabled: bool,
+  ) -> Result<()> {
+    admin::set_feature_enabled::handler(ctx, feature, enabled)
+  }
+
+  pub fn queue_config_changes(
+    ctx: Context<QueueConfigChanges>,
+    input: TimelockedConfigsInput,
+  ) -> Result<()> {
+    admin::queue_config_changes::handler(ctx, input)
+  }
+
+  pub fn execute_config_changes(
+    ctx: Context<ExecuteConfigChanges>,
+  ) -> Result<()> {
+    admin::execute_config_changes::handler(ctx)
+  }
+
+  pub fn cancel_config_changes(
+    ctx: Context<CancelConfigChanges>,
+  ) -> Result<()> {
+    admin::cancel_config_changes::handler(ctx)
+  }
+
+  pub fn propose_authority(
+    ctx: Context<ProposeAuthority>,
+    new_authority: Pubkey,
+  ) -> Result<()> {
+    admin::propose_authority::handler(ctx, new_authority)
+  }
+
+  pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+    admin::accept_authority::handler(ctx)
+  }
+
+  pub fn cancel_authority_proposal(
+    ctx: Context<CancelAuthorityProposal>,
+  ) -> Result<()> {
+    admin::cancel_authority_proposal::handler(ctx)
+  }
+
+  pub fn grant_admin_role(
+    ctx: Context<GrantAdminRole>,
+    role: AdminRole,
+    grantee: Pubkey,
+  ) -> Result<()> {
+    admin::grant_admin_role::handler(ctx, role, grantee)
+  }
+
+  pub fn revoke_admin_role(
+    ctx: Context<RevokeAdminRole>,
+    role: AdminRole,
+  ) -> Result<()> {
+    admin::revoke_admin_role::handler(ctx, role)
+  }
+
+  pub fn set_multisig(
+    ctx: Context<SetMultisig>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+  ) -> Result<()> {
+    admin::set_multisig::handler(ctx, members, threshold)
+  }
+
+  pub fn create_admin_proposal(
+    ctx: Context<CreateAdminProposal>,
+    accounts: Vec<ProposalAccountMeta>,
+    data: Vec<u8>,
+  ) -> Result<()> {
+    admin::create_admin_proposal::handler(ctx, accounts, data)
+  }
+
+  pub fn approve_admin_proposal(
+    ctx: Context<ApproveAdminProposal>,
+    proposal_id: u64,
+  ) -> Result<()> {
+    admin::approve_admin_proposal::handler(ctx, proposal_id)
+  }
+
+  pub fn execute_admin_proposal<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteAdminProposal<'info>>,
+    proposal_id: u64,
+  ) -> Result<()> {
+    admin::execute_admin_proposal::handler(ctx, proposal_id)
+  }
+
   pub fn add_currency_token_to_allowlist(
     ctx: Context<AddCurrencyTokenToAllowList>,
     token_mint_address: Pubkey,
@@ -68,6 +182,148 @@ pub mod justies {
     )
   }
 
+  pub fn add_nft_creator_to_allowlist(
+    ctx: Context<AddNftCreatorToAllowList>,
+    creator_address: Pubkey,
+  ) -> Result<()> {
+    admin::add_nft_creator_to_allowlist::handler(ctx, creator_address)
+  }
+
+  pub fn add_nft_mint_to_allowlist(
+    ctx: Context<AddNftMintToAllowList>,
+    nft_mint_address: Pubkey,
+  ) -> Result<()> {
+    admin::add_nft_mint_to_allowlist::handler(ctx, nft_mint_address)
+  }
+
+  pub fn batch_add_currency_tokens_to_allowlist<'info>(
+    ctx: Context<'_, '_, '_, 'info, BatchAddCurrencyTokensToAllowList<'info>>,
+    token_mint_addresses: Vec<Pubkey>,
+  ) -> Result<()> {
+    admin::batch_add_currency_tokens_to_allowlist::handler(
+      ctx,
+      token_mint_addresses,
+    )
+  }
+
+  pub fn batch_add_nft_collections_to_allowlist<'info>(
+    ctx: Context<'_, '_, '_, 'info, BatchAddNftCollectionsToAllowList<'info>>,
+    collection_mint_addresses: Vec<Pubkey>,
+  ) -> Result<()> {
+    admin::batch_add_nft_collections_to_allowlist::handler(
+      ctx,
+      collection_mint_addresses,
+    )
+  }
+
+  pub fn set_currency_listing_limits(
+    ctx: Context<SetCurrencyListingLimits>,
+    token_mint_address: Pubkey,
+    listing_limits: CurrencyListingLimits,
+  ) -> Result<()> {
+    admin::set_currency_listing_limits::handler(
+      ctx,
+      token_mint_address,
+      listing_limits,
+    )
+  }
+
+  pub fn set_currency_market_fee_rate(
+    ctx: Context<SetCurrencyMarketFeeRate>,
+    token_mint_address: Pubkey,
+    market_fee_rate_bps: Option<u16>,
+  ) -> Result<()> {
+    admin::set_currency_market_fee_rate::handler(
+      ctx,
+      token_mint_address,
+      market_fee_rate_bps,
+    )
+  }
+
+  pub fn set_nft_collection_listing_rules(
+    ctx: Context<SetNftCollectionListingRules>,
+    collection_mint_address: Pubkey,
+    listing_rules: ListingRuleOverrides,
+  ) -> Result<()> {
+    admin::set_nft_collection_listing_rules::handler(
+      ctx,
+      collection_mint_address,
+      listing_rules,
+    )
+  }
+
+  pub fn suspend_allowlist_entry(
+    ctx: Context<SuspendAllowlistEntry>,
+    allowlist_type: AllowlistType,
+    token_mint_address: Pubkey,
+    reason_code: Option<u16>,
+  ) -> Result<()> {
+    admin::suspend_allowlist_entry::handler(
+      ctx,
+      allowlist_type,
+      token_mint_address,
+      reason_code,
+    )
+  }
+
+  pub fn reinstate_allowlist_entry(
+    ctx: Context<ReinstateAllowlistEntry>,
+    allowlist_type: AllowlistType,
+    token_mint_address: Pubkey,
+  ) -> Result<()> {
+    admin::reinstate_allowlist_entry::handler(
+      ctx,
+      allowlist_type,
+      token_mint_address,
+    )
+  }
+
+  pub fn remove_allowlist_entry(
+    ctx: Context<RemoveAllowlistEntry>,
+    allowlist_type: AllowlistType,
+    token_mint_address: Pubkey,
+    reason_code: Option<u16>,
+    index_page_id: u64,
+  ) -> Result<()> {
+    admin::remove_allowlist_entry::handler(
+      ctx,
+      allowlist_type,
+      token_mint_address,
+      reason_code,
+      index_page_id,
+    )
+  }
+
+  pub fn block_wallet(
+    ctx: Context<BlockWallet>,
+    wallet_address: Pubkey,
+    reason_code: Option<u16>,
+  ) -> Result<()> {
+    admin::block_wallet::handler(ctx, wallet_address, reason_code)
+  }
+
+  pub fn unblock_wallet(
+    ctx: Context<UnblockWallet>,
+    wallet_address: Pubkey,
+    reason_code: Option<u16>,
+  ) -> Result<()> {
+    admin::unblock_wallet::handler(ctx, wallet_address, reason_code)
+  }
+
+  pub fn set_curated_creator(
+    ctx: Context<SetCuratedCreator>,
+    creator_address: Pubkey,
+    curated: bool,
+    max_live_listings: Option<u16>,
+  ) -> Result<()> {
+    admin::set_curated_creator::handler(
+      ctx,
+      creator_address,
+      curated,
+      max_live_listings,
+    )
+  }
+
   //////////////////////////////////////////////////////////////////////////////
   // Auction Instructions
   //////////////////////////////////////////////////////////////////////////////
@@ -97,6 +353,14 @@ pub mod justies {
     auction::cancel_auction::handler(ctx)
   }
 
+  pub fn force_cancel_auction(
+    ctx: Context<ForceCancelAuction>,
+    _auction_id: u64,
+    reason_code: Option<u16>,
+  ) -> Result<()> {
+    auction::force_cancel_auction::handler(ctx, reason_code)
+  }
+
   pub fn make_bid(
     ctx: Context<MakeBid>,
     auction_id: u64,
@@ -127,11 +391,23 @@ pub mod justies {
     auction::cancel_auction_bid::handler(ctx)
   }
 
+  pub fn migrate_auction(
+    ctx: Context<MigrateAuction>,
+    auction_id: u64,
+  ) -> Result<()> {
+    auction::migrate_auction::handler(ctx, auction_id)
+  }
+
   pub fn claim_auction_revenue<'info>(
     ctx: Context<'_, '_, '_, 'info, ClaimAuctionRevenue<'info>>,
     auction_id: u64,
+    eligibility_check_input: Option<EligibilityCheckInput>,
   ) -> Result<()> {
-    auction::claim_auction_revenue::handler(ctx, auction_id)
+    auction::claim_auction_revenue::handler(
+      ctx,
+      auction_id,
+      eligibility_check_input,
+    )
   }
 
   //////////////////////////////////////////////////////////////////////////////
@@ -167,6 +443,21 @@ pub mod justies {
     raffle::cancel_raffle::handler(ctx)
   }
 
+  pub fn force_cancel_raffle(
+    ctx: Context<ForceCancelRaffle>,
+    _raffle_id: u64,
+    reason_code: Option<u16>,
+  ) -> Result<()> {
+    raffle::force_cancel_raffle::handler(ctx, reason_code)
+  }
+
+  pub fn refund_raffle_tickets(
+    ctx: Context<RefundRaffleTickets>,
+    _raffle_id: u64,
+  ) -> Result<()> {
+    raffle::refund_raffle_tickets::handler(ctx)
+  }
+
   pub fn buy_raffle_tickets(
     ctx: Context<BuyRaffleTickets>,
     _raffle_id: u64,
@@ -188,12 +479,11 @@ pub mod justies {
     raffle::make_raffle::handler(ctx, rerun)
   }
 
-  pub fn set_raffle_winners(
-    ctx: Context<SetRaffleWinners>,
-    _raffle_id: u64,
-    winner_ids: Vec<u16>,
+  pub fn migrate_raffle(
+    ctx: Context<MigrateRaffle>,
+    raffle_id: u64,
   ) -> Result<()> {
-    raffle::set_raffle_winners::handler(ctx, winner_ids)
+    raffle::migrate_raffle::handler(ctx, raffle_id)
   }
 
   pub fn claim_raffle_reward(
@@ -206,8 +496,13 @@ pub mod justies {
   pub fn claim_raffle_revenue<'info>(
     ctx: Context<'_, '_, '_, 'info, ClaimRaffleRevenue<'info>>,
     raffle_id: u64,
+    eligibility_check_input: Option<EligibilityCheckInput>,
   ) -> Result<()> {
-    raffle::claim_raffle_revenue::handler(ctx, raffle_id)
+    raffle::claim_raffle_revenue::handler(
+      ctx,
+      raffle_id,
+      eligibility_check_input,
+    )
   }
 
   pub fn claim_remaining_raffle_rewards(
@@ -216,4 +511,18 @@ pub mod justies {
   ) -> Result<()> {
     raffle::claim_remaining_raffle_rewards::handler(ctx)
   }
+
+  //////////////////////////////////////////////////////////////////////////////
+  // Testing-only Instructions
+  //////////////////////////////////////////////////////////////////////////////
+
+  // The testing-only instructions are only compiled into the builds with the
+  // "testing" feature, where they are dispatched via this fallback.
+  pub fn fallback(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+  ) -> Result<()> {
+    common::dispatch_testing_instruction(program_id, accounts, data)
+  }
 }
@@ -1,7 +1,7 @@
-use crate::admin::GlobalStates;
+use crate::admin::{GlobalStates, WalletBlocklistStates};
 use crate::common::{
-  get_current_timestamp, JustiesErrorCode, EligibilityCheckInput,
-  EligibilityCheckStrategy, PubkeyIndexPage, RaffleStrategy,
+  check_listing_allowlisted, get_current_timestamp, EligibilityCheckInput,
+  EligibilityCheckStrategy, JustiesErrorCode, PubkeyIndexPage, RaffleStrategy,
 };
 use crate::program::Justies;
 use crate::raffle::{Raffle, RaffleTicketPosition, TicketPositionStats};
@@ -21,6 +21,12 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 /// eligibility_check_input and corresponding account payloads as
 /// "remaining_accounts". For more details, check the docstring of
 /// EligibilityCheckInput.
+///
+/// No tickets can be bought once the currency token or the NFT allowlist entry
+/// (i.e.: the NFT collection, the NFT mint or the NFT creator) is suspended or
+/// removed from the allowlists, or while ticket sales are paused.
+///
+/// Blocked wallets can't buy tickets.
 #[derive(Accounts)]
 #[instruction(
     raffle_id: u64,
@@ -39,13 +45,42 @@ pub struct BuyRaffleTickets<'info> {
   #[account(
       seeds = [b"global_states"],
       bump = global_states.bump,
+      constraint = global_states.ticket_sales_enabled == true @JustiesErrorCode::TicketSalesDisabled,
   )]
   pub global_states: Box<Account<'info, GlobalStates>>,
+  /// CHECK: the allowlist states may have been removed (i.e.: closed), which is
+  /// checked in the handler.
+  #[account(
+      seeds = [
+        b"token_allowlist_states",
+        raffle.currency_token_mint_address.as_ref(),
+      ],
+      bump,
+  )]
+  pub token_allowlist_states: UncheckedAccount<'info>,
+  /// CHECK: the allowlist states may have been removed (i.e.: closed), which is
+  /// checked in the handler.
+  #[account(
+      seeds = [
+        raffle.nft_allowlist_type.states_seed(),
+        raffle.nft_allowlist_key.as_ref(),
+      ],
+      bump,
+  )]
+  pub nft_allowlist_states: UncheckedAccount<'info>,
   #[account(
       mut,
       constraint = buyer.key() != raffle.creator @JustiesErrorCode::RaffleCreatorCannotBuyTickets,
   )]
   pub buyer: Signer<'info>,
+  /// CHECK: the blocklist states of the buyer, which only exists when the
+  /// buyer is blocked.
+  #[account(
+      seeds = [b"wallet_blocklist_states", buyer.key().as_ref()],
+      bump,
+      constraint = !WalletBlocklistStates::is_blocked(&buyer_blocklist_states) @JustiesErrorCode::WalletBlocked,
+  )]
+  pub buyer_blocklist_states: UncheckedAccount<'info>,
   #[account(
       constraint = currency_token_mint.key() == raffle.currency_token_mint_address @JustiesErrorCode::InvalidCurrencyTokenMint,
   )]
@@ -112,6 +147,11 @@ pub fn handler(
   num_tickets: u16,
   eligibility_check_input: Option<EligibilityCheckInput>,
 ) -> Result<()> {
+  check_listing_allowlisted(
+    &ctx.accounts.token_allowlist_states,
+    &ctx.accounts.nft_allowlist_states,
+  )?;
+
   // Checks the buyer's eligibility first.
   let eligibility_check_strategy = EligibilityCheckStrategy::new(
     &ctx.accounts.raffle.eligible_groups,
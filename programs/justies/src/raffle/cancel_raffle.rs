@@ -1,4 +1,5 @@
-use crate::admin::GlobalStates;
+use crate::admin::{CreatorStates, GlobalStates};
+use crate::common::release_listing_fee_deposit;
 use crate::common::JustiesErrorCode;
 use crate::common::RaffleStrategy;
 use crate::raffle::Raffle;
@@ -15,6 +16,9 @@ use anchor_spl::token::{Token, TokenAccount};
 /// Please note that the only pre-requisite required cancellation is that there
 /// are no raffle ticket sold, no matter of the raffle status.
 ///
+/// The refundable listing fee held by the raffle (if any) goes to the fee
+/// treasury.
+///
 /// The raffle status will be set to "Cancelled" once confirmed.
 #[derive(Accounts)]
 #[instruction(
@@ -32,6 +36,14 @@ pub struct CancelRaffle<'info> {
   pub raffle: Box<Account<'info, Raffle>>,
   #[account(mut)]
   pub creator: Signer<'info>,
+  #[account(
+      init_if_needed,
+      payer = creator,
+      space = 8 + CreatorStates::MAX_DATA_SIZE,
+      seeds = [b"creator_states", raffle.creator.as_ref()],
+      bump,
+  )]
+  pub creator_states: Box<Account<'info, CreatorStates>>,
   /// CHECK: verified in the constraint.
   pub nft_mint: UncheckedAccount<'info>,
   /// CHECK: verified in the constraint.
@@ -64,6 +76,12 @@ pub struct CancelRaffle<'info> {
       bump = global_states.bump,
   )]
   pub global_states: Box<Account<'info, GlobalStates>>,
+  /// CHECK: the address is verified in the constraint.
+  #[account(
+      mut,
+      constraint = global_states.fee_treasury_address == fee_treasury.key() @JustiesErrorCode::InvalidFeeTreasuryAddress,
+  )]
+  pub fee_treasury: UncheckedAccount<'info>,
 
   pub token_program: Program<'info, Token>,
   pub associated_token_program: Program<'info, AssociatedToken>,
@@ -95,5 +113,21 @@ pub fn handler(ctx: Context<CancelRaffle>) -> Result<()> {
     &ctx.accounts.creator.to_account_info(),
   )?;
   raffle_strategy.cancel();
+  release_listing_fee_deposit(
+    &ctx.accounts.raffle.to_account_info(),
+    &ctx.accounts.fee_treasury.to_account_info(),
+    ctx.accounts.raffle.listing_fee_deposit,
+  )?;
+  ctx.accounts.raffle.listing_fee_deposit = 0;
+  let creator_states_bump = *ctx.bumps.get("creator_states").unwrap();
+  let creator_key = ctx.accounts.raffle.creator;
+  ctx
+    .accounts
+    .creator_states
+    .init_if_needed(creator_states_bump, creator_key);
+  ctx
+    .accounts
+    .creator_states
+    .remove_live_raffle(&mut ctx.accounts.raffle)?;
   Ok(())
 }
@@ -1,7 +1,11 @@
-use crate::admin::GlobalStates;
+use crate::admin::{
+  CreatorVolume, CurrencyStats, GlobalStates, TokenAllowlistStates,
+};
 use crate::common::{
-  init_revenue_distribution_accounts, JustiesErrorCode, RaffleStrategy,
-  RevenueDistributionStrategy,
+  fee_discount_bps, init_fee_distribution_accounts,
+  init_revenue_distribution_accounts, release_listing_fee_deposit,
+  split_distribution_accounts, EligibilityCheckInput, JustiesErrorCode,
+  ListingType, RaffleStrategy, RevenueDistributionStrategy,
 };
 use crate::raffle::Raffle;
 use anchor_lang::prelude::*;
@@ -17,6 +21,26 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 /// token accounts if they are not initialized. Once distributed, the raffle
 /// revenue escrow token account will be closed with the rents refunded to the
 /// creator.
+///
+/// When the market fee is split (see global_states.fee_shares), the fee goes to
+/// the token accounts of the fee treasuries instead, which need to be passed as
+/// "remaining_accounts" after the revenue distribution accounts. The share of a
+/// treasury without the token account goes to the fee treasury.
+///
+/// The creator in a fee discount group (see global_states.fee_discounts) can
+/// prove the membership with the eligibility check input, whose payload
+/// accounts need to be passed as "remaining_accounts" after the fee
+/// distribution accounts.
+///
+/// The settlement is recorded in the currency stats (i.e.: the fee ledger) of
+/// the currency token, which the creator pays rent for if not initialized. So
+/// is the creator's volume in the currency token, which determines the
+/// creator's market fee tier (see global_states.fee_tiers).
+///
+/// The refundable listing fee held by the raffle (if any) is refunded to the
+/// creator.
+///
+/// Not callable while claims are paused.
 #[derive(Accounts)]
 #[instruction(
     raffle_id: u64,
@@ -32,8 +56,29 @@ pub struct ClaimRaffleRevenue<'info> {
   #[account(
       seeds = [b"global_states"],
       bump = global_states.bump,
+      constraint = global_states.claims_enabled == true @JustiesErrorCode::ClaimsDisabled,
   )]
   pub global_states: Box<Account<'info, GlobalStates>>,
+  /// CHECK: the allowlist states may have been removed (i.e.: closed), which is
+  /// checked in the handler.
+  #[account(
+      seeds = [
+        b"token_allowlist_states",
+        raffle.currency_token_mint_address.as_ref(),
+      ],
+      bump,
+  )]
+  pub token_allowlist_states: UncheckedAccount<'info>,
+  /// CHECK: the allowlist states may have been removed (i.e.: closed), which is
+  /// checked in the handler.
+  #[account(
+      seeds = [
+        raffle.nft_allowlist_type.states_seed(),
+        raffle.nft_allowlist_key.as_ref(),
+      ],
+      bump,
+  )]
+  pub nft_allowlist_states: UncheckedAccount<'info>,
   #[account(mut)]
   pub creator: Signer<'info>,
 
@@ -55,6 +100,26 @@ pub struct ClaimRaffleRevenue<'info> {
       constraint = token_mint.key() == raffle.currency_token_mint_address @JustiesErrorCode::InvalidCurrencyTokenMint,
   )]
   pub token_mint: Box<Account<'info, Mint>>,
+  #[account(
+      init_if_needed,
+      payer = creator,
+      space = 8 + CurrencyStats::MAX_DATA_SIZE,
+      seeds = [b"currency_stats", token_mint.key().as_ref()],
+      bump,
+  )]
+  pub currency_stats: Box<Account<'info, CurrencyStats>>,
+  #[account(
+      init_if_needed,
+      payer = creator,
+      space = 8 + CreatorVolume::MAX_DATA_SIZE,
+      seeds = [
+        b"creator_volume",
+        creator.key().as_ref(),
+        token_mint.key().as_ref(),
+      ],
+      bump,
+  )]
+  pub creator_volume: Box<Account<'info, CreatorVolume>>,
 
   pub token_program: Program<'info, Token>,
   pub associated_token_program: Program<'info, AssociatedToken>,
@@ -64,8 +129,23 @@ pub struct ClaimRaffleRevenue<'info> {
 pub fn handler<'info>(
   ctx: Context<'_, '_, '_, 'info, ClaimRaffleRevenue<'info>>,
   raffle_id: u64,
+  eligibility_check_input: Option<EligibilityCheckInput>,
 ) -> Result<()> {
-  let revenue_distribution_accounts = ctx.remaining_accounts.to_vec();
+  let (
+    revenue_distribution_accounts,
+    fee_distribution_accounts,
+    account_payloads,
+  ) = split_distribution_accounts(
+    ctx.remaining_accounts,
+    &ctx.accounts.raffle.revenue_shares,
+    &ctx.accounts.global_states.fee_shares,
+  );
+  let fee_discount_bps = fee_discount_bps(
+    &ctx.accounts.global_states.fee_discounts,
+    ctx.accounts.creator.key(),
+    eligibility_check_input,
+    &account_payloads,
+  )?;
   let revenue_distributions = init_revenue_distribution_accounts(
     ctx.accounts.creator.to_account_info(),
     ctx.accounts.token_mint.to_account_info(),
@@ -75,9 +155,37 @@ pub fn handler<'info>(
     &ctx.accounts.token_program,
     &ctx.accounts.system_program,
   )?;
+  let currency_stats_bump = *ctx.bumps.get("currency_stats").unwrap();
+  let token_mint_key = ctx.accounts.token_mint.key();
+  ctx
+    .accounts
+    .currency_stats
+    .init_if_needed(currency_stats_bump, token_mint_key);
+  let creator_volume_bump = *ctx.bumps.get("creator_volume").unwrap();
+  let creator_key = ctx.accounts.creator.key();
+  ctx.accounts.creator_volume.init_if_needed(
+    creator_volume_bump,
+    creator_key,
+    token_mint_key,
+  );
+  let fee_distributions = init_fee_distribution_accounts(
+    ctx.accounts.token_mint.to_account_info(),
+    &ctx.accounts.global_states.fee_shares,
+    &fee_distribution_accounts,
+    &ctx.accounts.fee_treasury_token_account,
+  )?;
   let raffle_id_bytes = raffle_id.to_le_bytes();
   let raffle_bump_bytes = ctx.accounts.raffle.bump.to_le_bytes();
   let global_states = ctx.accounts.global_states.clone();
+  let currency_market_fee_rate_bps =
+    TokenAllowlistStates::market_fee_rate_override(
+      &ctx.accounts.token_allowlist_states,
+    )?;
+  let collection_market_fee_rate_bps =
+    TokenAllowlistStates::listing_rule_overrides(
+      &ctx.accounts.nft_allowlist_states,
+    )?
+    .market_fee_rate_bps;
   let escrow_signer_seed_fn = || {
     vec![
       b"raffle".as_ref(),
@@ -91,10 +199,17 @@ pub fn handler<'info>(
       .revenue_escrow_token_account,
     fee_treasury_token_account: &ctx.accounts.fee_treasury_token_account,
     global_states: &global_states,
+    currency_market_fee_rate_bps,
+    collection_market_fee_rate_bps,
+    fee_discount_bps,
     escrow_authority: ctx.accounts.raffle.to_account_info(),
     escrow_token_account_creator: ctx.accounts.creator.to_account_info(),
     escrow_signer_seed_fn,
     revenue_distributions,
+    fee_distributions,
+    currency_stats: &mut ctx.accounts.currency_stats,
+    creator_volume: &mut ctx.accounts.creator_volume,
+    listing_type: ListingType::Raffle,
   };
   let raffle_strategy = RaffleStrategy::new(
     &mut ctx.accounts.raffle,
@@ -111,5 +226,11 @@ pub fn handler<'info>(
     .distribute_revenue(&ctx.accounts.token_program)?;
   revenue_distribution_strategy
     .close_revenue_escrow_token_account(&ctx.accounts.token_program)?;
+  release_listing_fee_deposit(
+    &ctx.accounts.raffle.to_account_info(),
+    &ctx.accounts.creator.to_account_info(),
+    ctx.accounts.raffle.listing_fee_deposit,
+  )?;
+  ctx.accounts.raffle.listing_fee_deposit = 0;
   Ok(())
 }
@@ -11,6 +11,8 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 /// transferred from the raffle rewards escrow NFT account to the winner's
 /// NFT account. If the rewards escrow's NFT token amount becomes 0 after
 /// claiming, it will be closed with the rents refunded to the creator.
+///
+/// Not callable while claims are paused.
 #[derive(Accounts)]
 #[instruction(
     raffle_id: u64,
@@ -25,6 +27,7 @@ pub struct ClaimRaffleReward<'info> {
   #[account(
       seeds = [b"global_states"],
       bump = global_states.bump,
+      constraint = global_states.claims_enabled == true @JustiesErrorCode::ClaimsDisabled,
   )]
   pub global_states: Box<Account<'info, GlobalStates>>,
   #[account(mut)]
@@ -1,7 +1,10 @@
-use crate::admin::{GlobalStates, TokenAllowlistStates};
+use crate::admin::{
+  CreatorStates, GlobalStates, TokenAllowlistStates, WalletBlocklistStates,
+};
 use crate::common::{
-  validate_share_configs, JustiesErrorCode, GroupConfig, RaffleStrategy,
-  RevenueShareConfig,
+  check_nft_allowlisted, check_revenue_receivers_not_blocked,
+  collect_listing_fee, validate_share_configs, GroupConfig, JustiesErrorCode,
+  RaffleStrategy, RevenueShareConfig,
 };
 use crate::get_current_timestamp;
 use crate::raffle::{Raffle, TicketPositionStats};
@@ -16,8 +19,18 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 /// supply, ticket price, number of raffled nfts, revenue recipients, NFT token,
 /// currency token, eligible groups config, etc). Once created, the NFT tokens
 /// will be transferred from the creator's NFT account to the rewards escrow
-/// NFT account (with the raffle as the token authority). Both of the NFT
-/// collection and currency token must have been allow-listed.
+/// NFT account (with the raffle as the token authority). Both of the NFT and
+/// currency token must have been allow-listed, where the NFT can be
+/// allow-listed via its verified collection, its mint or one of its verified
+/// creators.
+///
+/// Neither the creator nor the revenue recipients can be blocked, where the
+/// wallet_blocklist_states pdas of the revenue recipients need to be passed as
+/// "remaining_accounts" in the same order as revenue_shares.
+///
+/// The listing fee (see global_states.listing_fee) is charged to the creator,
+/// which goes to the fee treasury, or is held by the raffle until the revenue
+/// is claimed when refundable.
 #[derive(Accounts)]
 #[instruction(
     id: u64,
@@ -35,7 +48,6 @@ pub struct CreateRaffle<'info> {
       space = 8 + Raffle::MAX_DATA_SIZE,
       seeds = [b"raffle", id.to_le_bytes().as_ref()],
       bump,
-      constraint = global_states.raffle_creation_enabled == true @JustiesErrorCode::RaffleCreationDisabled,
       constraint = global_states.total_raffles == id @JustiesErrorCode::InvalidRaffleId,
   )]
   pub raffle: Box<Account<'info, Raffle>>,
@@ -65,6 +77,22 @@ pub struct CreateRaffle<'info> {
   pub currency_token_mint: Box<Account<'info, Mint>>,
   #[account(mut)]
   pub creator: Signer<'info>,
+  /// CHECK: the blocklist states of the creator, which only exists when the
+  /// creator is blocked.
+  #[account(
+      seeds = [b"wallet_blocklist_states", creator.key().as_ref()],
+      bump,
+      constraint = !WalletBlocklistStates::is_blocked(&creator_blocklist_states) @JustiesErrorCode::WalletBlocked,
+  )]
+  pub creator_blocklist_states: UncheckedAccount<'info>,
+  #[account(
+      init_if_needed,
+      payer = creator,
+      space = 8 + CreatorStates::MAX_DATA_SIZE,
+      seeds = [b"creator_states", creator.key().as_ref()],
+      bump,
+  )]
+  pub creator_states: Box<Account<'info, CreatorStates>>,
   #[account(
       mut,
       constraint = creator_nft_account.owner == creator.key() @JustiesErrorCode::InvalidRaffleCreatorNftAccount,
@@ -99,20 +127,11 @@ pub struct CreateRaffle<'info> {
       seeds::program = Metadata::id(),
       bump,
       constraint = nft_metadata.mint == nft_mint.key() @JustiesErrorCode::InvalidNftMetadata,
-      constraint = nft_metadata.collection != None @JustiesErrorCode::InvalidNftMetadata,
-      constraint = nft_metadata.collection.as_ref().unwrap().verified == true @JustiesErrorCode::InvalidNftMetadata,
   )]
   pub nft_metadata: Box<Account<'info, MetadataAccount>>,
-  #[account(
-      seeds = [
-        b"nft_allowlist_states",
-        nft_metadata.collection.as_ref().unwrap().key.as_ref(),
-      ],
-      bump = nft_allowlist_states.bump,
-      constraint = nft_allowlist_states.token_mint_address == nft_metadata.collection.as_ref().unwrap().key @JustiesErrorCode::NftCollectionNotInAllowlist,
-      constraint = nft_allowlist_states.allowed == true @JustiesErrorCode::NftCollectionNotInAllowlist,
-  )]
-  pub nft_allowlist_states: Box<Account<'info, TokenAllowlistStates>>,
+  /// CHECK: it can be the allowlist states of the NFT collection, the NFT mint
+  /// or the NFT creator, which is checked in the handler.
+  pub nft_allowlist_states: UncheckedAccount<'info>,
   #[account(
       seeds = [b"token_allowlist_states", currency_token_mint.key().as_ref()],
       bump = token_allowlist_states.bump,
@@ -120,6 +139,22 @@ pub struct CreateRaffle<'info> {
       constraint = token_allowlist_states.allowed == true @JustiesErrorCode::TokenNotInAllowlist,
   )]
   pub token_allowlist_states: Box<Account<'info, TokenAllowlistStates>>,
+  /// CHECK: the address is verified in the constraint.
+  #[account(
+      mut,
+      constraint = global_states.fee_treasury_address == fee_treasury.key() @JustiesErrorCode::InvalidFeeTreasuryAddress,
+  )]
+  pub fee_treasury: UncheckedAccount<'info>,
+  /// CHECK: the creator's associated token account of the listing fee token,
+  /// which is only used (and checked in the handler) for the listing fee in
+  /// tokens.
+  #[account(mut)]
+  pub creator_listing_fee_token_account: UncheckedAccount<'info>,
+  /// CHECK: the fee treasury's associated token account of the listing fee
+  /// token, which is only used (and checked in the handler) for the listing fee
+  /// in tokens.
+  #[account(mut)]
+  pub fee_treasury_listing_fee_token_account: UncheckedAccount<'info>,
 
   pub token_program: Program<'info, Token>,
   pub system_program: Program<'info, System>,
@@ -135,6 +170,20 @@ pub fn handler(
   eligible_groups: Vec<GroupConfig>,
   revenue_shares: Vec<RevenueShareConfig>,
 ) -> Result<()> {
+  let (nft_allowlist_type, nft_allowlist_key) = check_nft_allowlisted(
+    &ctx.accounts.nft_metadata,
+    &ctx.accounts.nft_allowlist_states,
+  )?;
+  let listing_rules = TokenAllowlistStates::listing_rule_overrides(
+    &ctx.accounts.nft_allowlist_states,
+  )?;
+  let creator_states_bump = *ctx.bumps.get("creator_states").unwrap();
+  let creator_key = ctx.accounts.creator.key();
+  ctx
+    .accounts
+    .creator_states
+    .init_if_needed(creator_states_bump, creator_key);
+
   let current_timestamp = get_current_timestamp(&ctx.accounts.global_states);
   let mut raffle_strategy = RaffleStrategy::new(
     &mut ctx.accounts.raffle,
@@ -144,6 +193,7 @@ pub fn handler(
   ctx.accounts.ticket_position_stats.bump =
     *ctx.bumps.get("ticket_position_stats").unwrap();
   validate_share_configs(&revenue_shares)?;
+  check_revenue_receivers_not_blocked(&revenue_shares, ctx.remaining_accounts)?;
   raffle_strategy.init_raffle(
     id,
     ctx.bumps["raffle"],
@@ -156,6 +206,9 @@ pub fn handler(
     ticket_price,
     &eligible_groups,
     &revenue_shares,
+    &ctx.accounts.token_allowlist_states.listing_limits,
+    &listing_rules,
+    &mut ctx.accounts.creator_states,
     current_timestamp,
   )?;
   raffle_strategy.deposit_nft(
@@ -164,5 +217,17 @@ pub fn handler(
     &ctx.accounts.rewards_escrow_nft_account,
     ctx.accounts.creator.to_account_info(),
   )?;
+  ctx.accounts.raffle.nft_allowlist_type = nft_allowlist_type;
+  ctx.accounts.raffle.nft_allowlist_key = nft_allowlist_key;
+  ctx.accounts.raffle.listing_fee_deposit = collect_listing_fee(
+    &ctx.accounts.global_states,
+    ctx.accounts.creator.to_account_info(),
+    ctx.accounts.raffle.to_account_info(),
+    ctx.accounts.fee_treasury.to_account_info(),
+    &ctx.accounts.creator_listing_fee_token_account,
+    &ctx.accounts.fee_treasury_listing_fee_token_account,
+    &ctx.accounts.token_program,
+    &ctx.accounts.system_program,
+  )?;
   Ok(())
 }
@@ -0,0 +1,58 @@
+use crate::admin::GlobalStates;
+use crate::common::{resize_account, JustiesErrorCode};
+use crate::raffle::state::Raffle;
+use anchor_lang::prelude::*;
+
+/// The space of the fields added to the raffles since their layout before the
+/// allowlist entries, i.e.: nft_allowlist_type, nft_allowlist_key,
+/// listing_fee_deposit & counted_as_live.
+const ADDED_FIELDS_SPACE: usize = 1 + 32 + 8 + 1;
+
+/// Rewrites a raffle in the layout before the allowlist entries (testing-only),
+/// so that the migration from it can be tested.
+///
+/// The fields added since then are zeroed, and the account is shrunk to the
+/// space of that layout (with the rents refunded to the authority).
+///
+/// Only callable by the authority when global_states.is_test_environment is
+/// true and the program is built with the "testing" feature. The instruction is
+/// only compiled into the builds with the "testing" feature, and is dispatched
+/// via the fallback of the program (see common/testing_instructions.rs).
+#[derive(Accounts)]
+#[instruction(raffle_id: u64)]
+pub struct DowngradeRaffle<'info> {
+  /// CHECK: The account is rewritten in an older layout, so it is deserialized
+  /// in the handler.
+  #[account(
+      mut,
+      seeds = [b"raffle", raffle_id.to_le_bytes().as_ref()],
+      bump,
+      owner = crate::ID,
+  )]
+  pub raffle: UncheckedAccount<'info>,
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.is_test_mode() @JustiesErrorCode::NotTestEnvironment,
+      constraint = global_states.authority == authority.key() @JustiesErrorCode::NotTheAuthority,
+  )]
+  pub global_states: Box<Account<'info, GlobalStates>>,
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DowngradeRaffle>) -> Result<()> {
+  let raffle_info = ctx.accounts.raffle.to_account_info();
+  let raffle = Raffle::try_deserialize(&mut &raffle_info.data.borrow()[..])?;
+  // The added fields are serialized right after the fields of the older
+  // layout, which are followed by zeros in the older layout.
+  let data_len = 8 + raffle.try_to_vec()?.len() - ADDED_FIELDS_SPACE;
+  raffle_info.data.borrow_mut()[data_len..].fill(0);
+  resize_account(
+    raffle_info,
+    8 + Raffle::MAX_DATA_SIZE - ADDED_FIELDS_SPACE,
+    ctx.accounts.authority.to_account_info(),
+    ctx.accounts.system_program.to_account_info(),
+  )
+}
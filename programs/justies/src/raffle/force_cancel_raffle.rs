@@ -0,0 +1,156 @@
+use crate::admin::{
+  AuditField, AuditLog, ConfigChange, CreatorStates, GlobalStates,
+};
+use crate::common::{
+  get_current_timestamp, release_listing_fee_deposit, JustiesErrorCode,
+  ListingStatus, RaffleStrategy,
+};
+use crate::raffle::Raffle;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount};
+
+/// Force-cancels a raffle (e.g.: a fraudulent listing of a stolen NFT).
+///
+/// Only callable by the authority, at any stage before any reward or the
+/// revenue is claimed. All the remaining rewards will be transferred from the
+/// escrow to either the creator or the quarantine wallet, and the rewards
+/// escrow NFT account will be closed with the rents refunded to the creator.
+///
+/// The revenue escrow token account is kept open so that every ticket buyer
+/// can get refunded via refund_raffle_tickets.
+///
+/// The refundable listing fee held by the raffle (if any) goes to the fee
+/// treasury.
+///
+/// The raffle status will be marked as "ForceCancelled". The creator_states
+/// of the raffle creator needs to be passed as the first remaining account
+/// while the raffle is still counted as live.
+#[derive(Accounts)]
+#[instruction(
+    raffle_id: u64,
+)]
+pub struct ForceCancelRaffle<'info> {
+  #[account(
+      mut,
+      seeds = [b"raffle", raffle_id.to_le_bytes().as_ref()],
+      bump = raffle.bump,
+      constraint = raffle.status != ListingStatus::Cancelled && raffle.status != ListingStatus::ForceCancelled @JustiesErrorCode::RaffleCancelled,
+      constraint = raffle.claim_mask == 0 @JustiesErrorCode::RaffleNotForceCancelable,
+      constraint = raffle.nft_mint_address == nft_mint.key() @JustiesErrorCode::NftMintAddressMismatch,
+  )]
+  pub raffle: Box<Account<'info, Raffle>>,
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  /// CHECK: the address is verified in the constraint.
+  #[account(
+      mut,
+      constraint = creator.key() == raffle.creator @JustiesErrorCode::NotRaffleCreator,
+  )]
+  pub creator: UncheckedAccount<'info>,
+  /// CHECK: the address is verified in the constraint.
+  #[account(
+      constraint = nft_recipient.key() == raffle.creator || nft_recipient.key() == global_states.quarantine_wallet_address @JustiesErrorCode::InvalidNftRecipient,
+  )]
+  pub nft_recipient: UncheckedAccount<'info>,
+  /// CHECK: verified in the constraint.
+  pub nft_mint: UncheckedAccount<'info>,
+  /// CHECK: verified in the constraint.
+  pub currency_token_mint: UncheckedAccount<'info>,
+  #[account(
+      mut,
+      seeds = [b"raffle", raffle.key().as_ref(), b"rewards_escrow"],
+      bump,
+      token::mint = nft_mint,
+      token::authority = raffle,
+  )]
+  pub rewards_escrow_nft_account: Box<Account<'info, TokenAccount>>,
+  // The revenue must not have been claimed, as the tickets are refunded from
+  // the revenue escrow.
+  #[account(
+      seeds = [b"raffle", raffle.key().as_ref(), b"revenue_escrow"],
+      bump,
+      token::mint = currency_token_mint,
+      token::authority = raffle,
+  )]
+  pub revenue_escrow_token_account: Box<Account<'info, TokenAccount>>,
+  #[account(
+      init_if_needed,
+      payer = authority,
+      associated_token::mint = nft_mint,
+      associated_token::authority = nft_recipient,
+  )]
+  pub nft_recipient_nft_account: Box<Account<'info, TokenAccount>>,
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+      constraint = global_states.authority == authority.key() @JustiesErrorCode::NotTheAuthority,
+  )]
+  pub global_states: Box<Account<'info, GlobalStates>>,
+  /// CHECK: the address is verified in the constraint.
+  #[account(
+      mut,
+      constraint = global_states.fee_treasury_address == fee_treasury.key() @JustiesErrorCode::InvalidFeeTreasuryAddress,
+  )]
+  pub fee_treasury: UncheckedAccount<'info>,
+  #[account(
+      mut,
+      seeds = [b"audit_log"],
+      bump = audit_log.bump,
+  )]
+  pub audit_log: Box<Account<'info, AuditLog>>,
+
+  pub token_program: Program<'info, Token>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<ForceCancelRaffle>,
+  reason_code: Option<u16>,
+) -> Result<()> {
+  let raffle_key = ctx.accounts.raffle.key();
+  let current_timestamp = get_current_timestamp(&ctx.accounts.global_states);
+  let rewards_escrow_nft_account =
+    ctx.accounts.rewards_escrow_nft_account.clone();
+  let mut raffle_strategy = RaffleStrategy::new(
+    &mut ctx.accounts.raffle,
+    &mut ctx.accounts.global_states,
+    None,
+  );
+  raffle_strategy.transfer_rewards(
+    &ctx.accounts.token_program,
+    &rewards_escrow_nft_account,
+    &ctx.accounts.nft_recipient_nft_account,
+    rewards_escrow_nft_account.amount,
+  )?;
+  raffle_strategy.try_close_rewards_escrow_nft_account(
+    &ctx.accounts.token_program,
+    &mut ctx.accounts.rewards_escrow_nft_account,
+    &ctx.accounts.creator.to_account_info(),
+  )?;
+  raffle_strategy.force_cancel();
+  release_listing_fee_deposit(
+    &ctx.accounts.raffle.to_account_info(),
+    &ctx.accounts.fee_treasury.to_account_info(),
+    ctx.accounts.raffle.listing_fee_deposit,
+  )?;
+  ctx.accounts.raffle.listing_fee_deposit = 0;
+
+  ctx.accounts.audit_log.record(
+    ConfigChange::new(
+      AuditField::RaffleForceCancelled,
+      ctx.accounts.creator.key(),
+      ctx.accounts.nft_recipient.key(),
+    )
+    .with_target(raffle_key)
+    .with_reason_code(reason_code),
+    ctx.accounts.authority.key(),
+    current_timestamp,
+  );
+  CreatorStates::release_live_raffle(
+    &mut ctx.accounts.raffle,
+    ctx.remaining_accounts,
+  )?;
+  Ok(())
+}
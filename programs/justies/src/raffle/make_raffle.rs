@@ -1,4 +1,4 @@
-use crate::admin::GlobalStates;
+use crate::admin::{AdminRole, CreatorStates, GlobalStates};
 use crate::common::{get_current_timestamp, JustiesErrorCode, RaffleStrategy};
 use crate::raffle::{Raffle, TicketPositionStats};
 use anchor_lang::prelude::*;
@@ -6,8 +6,13 @@ use anchor_lang::prelude::*;
 /// Makes raffle to pick winners.
 ///
 /// The "rerun" flag is for testing purpose. It can only be true when
-/// global_states.is_test_environment == true. When being true, the raffle
-/// algorithm will be rerun. This is useful for testing the raffle algorithm.
+/// global_states.is_test_environment == true and the program is built with the
+/// "testing" feature. When being true, the raffle algorithm will be rerun. This
+/// is useful for testing the raffle algorithm.
+///
+/// The made raffle is removed from the creator's live listings, whose
+/// creator_states needs to be passed as the first remaining account while the
+/// raffle is still counted as live. Only callable by the raffle operator.
 #[derive(Accounts)]
 #[instruction(
     raffle_id: u64,
@@ -33,7 +38,7 @@ pub struct MakeRaffle<'info> {
   #[account(
       seeds = [b"global_states"],
       bump = global_states.bump,
-      constraint = global_states.authority == authority.key() @JustiesErrorCode::NotTheAuthority,
+      constraint = global_states.has_role(AdminRole::RaffleOperator, authority.key()) @JustiesErrorCode::MissingAdminRole,
   )]
   pub global_states: Box<Account<'info, GlobalStates>>,
   pub authority: Signer<'info>,
@@ -50,5 +55,9 @@ pub fn handler(ctx: Context<MakeRaffle>, rerun: bool) -> Result<()> {
   raffle_strategy.validate_make_raffle(current_timestamp, rerun)?;
   raffle_strategy
     .make_raffle(&ctx.accounts.ticket_position_stats.ticket_positions);
+  CreatorStates::release_live_raffle(
+    &mut ctx.accounts.raffle,
+    ctx.remaining_accounts,
+  )?;
   Ok(())
 }
@@ -0,0 +1,59 @@
+use crate::common::{grow_account, legacy_nft_allowlist_entry};
+use crate::raffle::state::Raffle;
+use anchor_lang::prelude::*;
+
+/// Migrates a raffle created before the program upgrade that added the
+/// raffle's allowlist entry and listing fee deposit, by growing it to the
+/// current size and filling the defaults of the new fields.
+///
+/// The raffled NFTs of such a raffle were allowlisted via their verified
+/// collection, which is read from the NFT metadata. The listing fee deposit is
+/// 0. Until it's migrated, the raffle's tickets can't be bought (and the raffle
+/// can't be loaded at all if its vectors fill the account), while the migration
+/// of a raffle of the current layout is a no-op.
+///
+/// Callable by anyone, where the signer pays the rent of the added space.
+#[derive(Accounts)]
+#[instruction(raffle_id: u64)]
+pub struct MigrateRaffle<'info> {
+  /// CHECK: The account of an older layout may be too small to deserialize, so
+  /// it is deserialized in the handler after the reallocation.
+  #[account(
+      mut,
+      seeds = [b"raffle", raffle_id.to_le_bytes().as_ref()],
+      bump,
+      owner = crate::ID,
+  )]
+  pub raffle: UncheckedAccount<'info>,
+  /// CHECK: the metadata of the raffled NFT, which is checked in the handler.
+  pub nft_metadata: UncheckedAccount<'info>,
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateRaffle>, _raffle_id: u64) -> Result<()> {
+  let raffle_info = ctx.accounts.raffle.to_account_info();
+  grow_account(
+    raffle_info.clone(),
+    8 + Raffle::MAX_DATA_SIZE,
+    ctx.accounts.payer.to_account_info(),
+    ctx.accounts.system_program.to_account_info(),
+  )?;
+
+  let mut raffle =
+    Raffle::try_deserialize(&mut &raffle_info.data.borrow()[..])?;
+  // The raffles created after the upgrade always have the allowlist entry.
+  if raffle.nft_allowlist_key != Pubkey::default() {
+    return Ok(());
+  }
+  let (nft_allowlist_type, nft_allowlist_key) = legacy_nft_allowlist_entry(
+    &ctx.accounts.nft_metadata,
+    raffle.nft_mint_address,
+  )?;
+  raffle.nft_allowlist_type = nft_allowlist_type;
+  raffle.nft_allowlist_key = nft_allowlist_key;
+  raffle.listing_fee_deposit = 0;
+  raffle.try_serialize(&mut &mut raffle_info.data.borrow_mut()[..])?;
+  Ok(())
+}
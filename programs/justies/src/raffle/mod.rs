@@ -10,9 +10,21 @@ pub mod create_raffle;
 pub use create_raffle::*;
 pub mod make_raffle;
 pub use make_raffle::*;
+#[cfg(feature = "testing")]
+pub mod downgrade_raffle;
+#[cfg(feature = "testing")]
+pub use downgrade_raffle::*;
+pub mod migrate_raffle;
+pub use migrate_raffle::*;
 pub mod cancel_raffle;
 pub use cancel_raffle::*;
+#[cfg(feature = "testing")]
 pub mod set_raffle_winners;
+#[cfg(feature = "testing")]
 pub use set_raffle_winners::*;
+pub mod force_cancel_raffle;
+pub use force_cancel_raffle::*;
+pub mod refund_raffle_tickets;
+pub use refund_raffle_tickets::*;
 pub mod state;
 pub use state::*;
@@ -0,0 +1,99 @@
+use crate::admin::GlobalStates;
+use crate::common::{JustiesErrorCode, ListingStatus, RaffleStrategy};
+use crate::raffle::{Raffle, RaffleTicketPosition};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+/// Refunds the raffle tickets of a force-cancelled raffle.
+///
+/// Only callable by the ticket buyers once the raffle is force-cancelled. The
+/// full price of the buyer's tickets will be transferred from the revenue
+/// escrow token account to the buyer's token account. If the revenue escrow's
+/// token amount becomes 0 after refunding, it will be closed with the rents
+/// refunded to the creator.
+///
+/// Never paused, so that buyers can always get their funds back.
+#[derive(Accounts)]
+#[instruction(
+    raffle_id: u64,
+)]
+pub struct RefundRaffleTickets<'info> {
+  #[account(
+      seeds = [b"raffle", raffle_id.to_le_bytes().as_ref()],
+      bump = raffle.bump,
+      constraint = raffle.status == ListingStatus::ForceCancelled @JustiesErrorCode::RaffleNotForceCancelled,
+  )]
+  pub raffle: Box<Account<'info, Raffle>>,
+  #[account(
+      seeds = [b"global_states"],
+      bump = global_states.bump,
+  )]
+  pub global_states: Box<Account<'info, GlobalStates>>,
+  #[account(mut)]
+  pub buyer: Signer<'info>,
+  #[account(
+      mut,
+      seeds = [
+        b"raffle",
+        raffle.key().as_ref(),
+        b"ticket_position",
+        buyer.key().as_ref(),
+      ],
+      bump = ticket_position.bump,
+      constraint = ticket_position.buyer == buyer.key() @JustiesErrorCode::InconsistentRaffleTicketPosition,
+      constraint = ticket_position.total_num_tickets > 0 @JustiesErrorCode::NoRaffleTicketsToRefund,
+  )]
+  pub ticket_position: Box<Account<'info, RaffleTicketPosition>>,
+  #[account(
+      mut,
+      seeds = [b"raffle", raffle.key().as_ref(), b"revenue_escrow"],
+      bump,
+      token::mint = currency_token_mint,
+      token::authority = raffle,
+  )]
+  pub revenue_escrow_token_account: Box<Account<'info, TokenAccount>>,
+  #[account(
+      init_if_needed,
+      payer = buyer,
+      associated_token::mint = currency_token_mint,
+      associated_token::authority = buyer,
+  )]
+  pub buyer_token_account: Box<Account<'info, TokenAccount>>,
+  #[account(
+      constraint = currency_token_mint.key() == raffle.currency_token_mint_address @JustiesErrorCode::InvalidCurrencyTokenMint,
+  )]
+  pub currency_token_mint: Box<Account<'info, Mint>>,
+  /// CHECK: non-risky as it is verified in the constraint.
+  #[account(
+      mut,
+      constraint = creator.key() == raffle.creator @JustiesErrorCode::NotRaffleCreator,
+  )]
+  pub creator: UncheckedAccount<'info>,
+
+  pub token_program: Program<'info, Token>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RefundRaffleTickets>) -> Result<()> {
+  let revenue_escrow_token_account =
+    ctx.accounts.revenue_escrow_token_account.clone();
+  let mut raffle_strategy = RaffleStrategy::new(
+    &mut ctx.accounts.raffle,
+    &mut ctx.accounts.global_states,
+    None,
+  );
+  raffle_strategy.refund_tickets(
+    &ctx.accounts.token_program,
+    &mut ctx.accounts.ticket_position,
+    &revenue_escrow_token_account,
+    &ctx.accounts.buyer_token_account,
+  )?;
+  raffle_strategy.try_close_revenue_escrow_token_account(
+    &ctx.accounts.token_program,
+    &mut ctx.accounts.revenue_escrow_token_account,
+    &ctx.accounts.creator.to_account_info(),
+  )?;
+  Ok(())
+}
@@ -1,12 +1,18 @@
-use crate::admin::GlobalStates;
+use crate::admin::{AdminRole, CreatorStates, GlobalStates};
 use crate::common::{get_current_timestamp, JustiesErrorCode, RaffleStrategy};
 use crate::raffle::Raffle;
 use anchor_lang::prelude::*;
 
 /// Sets raffle winners (testing-only).
 ///
-/// Only callable by the authority when global_states.is_test_environment is
-/// true. This is for setting up deterministic winners for testing purpose.
+/// Only callable by the raffle operator when global_states.is_test_environment
+/// is true and the program is built with the "testing" feature. This is for
+/// setting up deterministic winners for testing purpose. The instruction is
+/// only compiled into the builds with the "testing" feature, and is dispatched
+/// via the fallback of the program (see common/testing_instructions.rs).
+///
+/// The creator_states of the raffle creator needs to be passed as the first
+/// remaining account while the raffle is still counted as live.
 #[derive(Accounts)]
 #[instruction(
     raffle_id: u64,
@@ -22,8 +28,8 @@ pub struct SetRaffleWinners<'info> {
   #[account(
       seeds = [b"global_states"],
       bump = global_states.bump,
-      constraint = global_states.is_test_environment == true @JustiesErrorCode::NotTestEnvironment,
-      constraint = global_states.authority == authority.key() @JustiesErrorCode::NotTheAuthority,
+      constraint = global_states.is_test_mode() @JustiesErrorCode::NotTestEnvironment,
+      constraint = global_states.has_role(AdminRole::RaffleOperator, authority.key()) @JustiesErrorCode::MissingAdminRole,
   )]
   pub global_states: Box<Account<'info, GlobalStates>>,
   pub authority: Signer<'info>,
@@ -42,5 +48,9 @@ pub fn handler(
   );
   raffle_strategy.validate_set_winners(current_timestamp)?;
   raffle_strategy.set_winners(&winner_ids);
+  CreatorStates::release_live_raffle(
+    &mut ctx.accounts.raffle,
+    ctx.remaining_accounts,
+  )?;
   Ok(())
 }
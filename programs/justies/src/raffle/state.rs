@@ -1,3 +1,4 @@
+use crate::admin::AllowlistType;
 use crate::common::types::{GroupConfig, ListingStatus, RevenueShareConfig};
 use anchor_lang::prelude::*;
 
@@ -24,6 +25,19 @@ pub struct Raffle {
   // The winner ids (by default each winner can only win 1 nft).
   pub winner_ids: Vec<u16>,
   pub claim_mask: u64,
+  // The allowlist entry that the raffled NFTs were listed under (i.e.: the NFT
+  // collection, the NFT mint or the NFT creator), whose allowlist states are
+  // checked when buying tickets.
+  pub nft_allowlist_type: AllowlistType,
+  pub nft_allowlist_key: Pubkey,
+  // The refundable listing fee (in lamports) held by the raffle, which is
+  // refunded to the creator once the revenue is claimed, or goes to the fee
+  // treasury if the raffle is cancelled.
+  pub listing_fee_deposit: u64,
+  // Whether the raffle is counted in the live listings of the creator, which
+  // is true from the creation until the raffle is finalized (i.e.: cancelled
+  // or made).
+  pub counted_as_live: bool,
 }
 
 impl Raffle {
@@ -50,7 +64,11 @@ impl Raffle {
         * RevenueShareConfig::MAX_DATA_SIZE)
     + 4
     + (4 + Self::MAX_RAFFLED_NFTS as usize * 2)
-    + 8);
+    + 8
+    + 1
+    + 32
+    + 8
+    + 1);
 }
 
 #[account]
@@ -6,10 +6,14 @@ import {describe} from "mocha";
 import {toLamport} from "../libraries/ProgramUtils";
 import {BN, IdlTypes} from "@project-serum/anchor";
 import {
+  GLOBAL_STATES_MAJOR_VERSION,
   JustiesProgramClient,
-  RevenueShareConfig
+  FeeShareConfig,
+  RevenueShareConfig,
+  TimelockedConfigsInput,
+  UpdateConfigsInput
 } from "../libraries/JustiesProgramClient";
-import {PublicKey} from "@solana/web3.js";
+import {AccountMeta, PublicKey, SystemProgram} from "@solana/web3.js";
 import {generatorToList} from "../libraries/Utils";
 
 use(chaiAsPromised);
@@ -101,13 +105,70 @@ async function restoreConfigs(
   input.maxRaffledNfts = originalGlobalStates.maxRaffledNfts;
   input.minRaffleDuration = originalGlobalStates.minRaffleDuration;
   input.maxRaffleDuration = originalGlobalStates.maxRaffleDuration;
-  input.auctionCreationEnabled =
-    originalGlobalStates.auctionCreationEnabled;
-  input.raffleCreationEnabled = originalGlobalStates.raffleCreationEnabled;
+  input.biddingEnabled = originalGlobalStates.biddingEnabled;
+  input.ticketSalesEnabled = originalGlobalStates.ticketSalesEnabled;
+  input.claimsEnabled = originalGlobalStates.claimsEnabled;
+  input.quarantineWalletAddress = originalGlobalStates.quarantineWalletAddress;
+  input.curatedCreatorsOnly = originalGlobalStates.curatedCreatorsOnly;
+  input.maxLiveListingsPerCreator =
+    originalGlobalStates.maxLiveListingsPerCreator;
+  input.listingFee = originalGlobalStates.listingFee;
+  input.listingFeeTokenMint = originalGlobalStates.listingFeeTokenMint;
+  input.listingFeeRefundable = originalGlobalStates.listingFeeRefundable;
   input.numKeysPerIndexPage = originalGlobalStates.numKeysPerIndexPage;
   await client.updateConfigs(input);
 }
 
+// Queues & executes the timelocked config changes, by mocking the timestamp to
+// the activation and restoring it afterwards.
+async function applyTimelockedConfigs(
+  client: JustiesProgramClient,
+  input: TimelockedConfigsInput
+) {
+  const {mockTimestamp} = await client.fetchGlobalStates();
+  await client.queueConfigChanges(input);
+  const pendingConfigs = await client.fetchPendingConfigs();
+  await client.setMockTimestamp(pendingConfigs.activationTimestamp);
+  await client.executeConfigChanges();
+  await client.setMockTimestamp(mockTimestamp);
+}
+
+// Reads the creation toggles the way the clients of the 1.6 layout do, i.e.:
+// the auction_creation_enabled & raffle_creation_enabled bools that follow the
+// baseline configs at the offset 116.
+function readLegacyCreationToggles(data: Buffer): [boolean, boolean] {
+  const offset = 8 + 1 + 2 + 32 + 32 + 2 + 1 + 1 + 8 + 8 + 2 + 2 + 1 + 8 + 8;
+  return [data[offset] === 1, data[offset + 1] === 1];
+}
+
+async function fetchLatestAuditEntries(
+  client: JustiesProgramClient,
+  count: number
+) {
+  const auditLog = await client.fetchAuditLog();
+  const capacity = auditLog.entries.length;
+  const entries = [];
+  for (let i = count; i > 0; --i) {
+    entries.push(auditLog.entries[(auditLog.nextIndex + capacity - i) % capacity]);
+  }
+  return entries;
+}
+
+// Reads the fee ledger of a currency, which is all zeros before the first
+// settlement in the currency.
+async function fetchCurrencyStatsCounters(
+  client: JustiesProgramClient,
+  tokenMintAddress: PublicKey
+) {
+  const stats = await client.fetchCurrencyStats(tokenMintAddress);
+  return {
+    totalFees: stats?.totalFees.toNumber() ?? 0,
+    totalVolume: stats?.totalVolume.toNumber() ?? 0,
+    numSettledAuctions: stats?.numSettledAuctions.toNumber() ?? 0,
+    numSettledRaffles: stats?.numSettledRaffles.toNumber() ?? 0,
+  };
+}
+
 describe("Justies Test", () => {
   const devEnv = new DevEnvironment("Justies Test");
   let feeTreasurySigner = devEnv.generateKeypair();
@@ -137,10 +198,10 @@ describe("Justies Test", () => {
 
     it("Update regular configs", async () => {
       let originalGlobalStates = await justiesClient.fetchGlobalStates();
-      const newFeeTreasuryAddress = devEnv.generateKeypair().publicKey;
       let input = JustiesProgramClient.getDefaultUpdateConfigsInput();
-      input.marketFeeRateBps = 250;
-      input.feeTreasuryAddress = newFeeTreasuryAddress;
+      // The timelocked configs can be set to their current values.
+      input.marketFeeRateBps = originalGlobalStates.marketFeeRateBps;
+      input.feeTreasuryAddress = originalGlobalStates.feeTreasuryAddress;
       input.minOutbidRateBps = 510;
       input.lastMinutesForAuctionExtend = 8;
       input.auctionExtendMinutes = 9;
@@ -151,15 +212,15 @@ describe("Justies Test", () => {
       input.maxRaffledNfts = 19;
       input.minRaffleDuration = new BN(4 * 3600);
       input.maxRaffleDuration = new BN(9 * 24 * 3600);
-      input.auctionCreationEnabled = false;
-      input.raffleCreationEnabled = false;
+      input.biddingEnabled = false;
+      input.ticketSalesEnabled = false;
       input.numKeysPerIndexPage = 150;
 
       await justiesClient.updateConfigs(input);
       const globalStates = await justiesClient.fetchGlobalStates();
       expect(globalStates).to.containSubset({
-        marketFeeRateBps: 250,
-        feeTreasuryAddress: newFeeTreasuryAddress,
+        marketFeeRateBps: originalGlobalStates.marketFeeRateBps,
+        feeTreasuryAddress: originalGlobalStates.feeTreasuryAddress,
         minOutbidRateBps: 510,
         lastMinutesForAuctionExtend: 8,
         auctionExtendMinutes: 9,
@@ -170,23 +231,257 @@ describe("Justies Test", () => {
         maxRaffledNfts: 19,
         minRaffleDuration: new BN(4 * 3600),
         maxRaffleDuration: new BN(9 * 24 * 3600),
-        auctionCreationEnabled: false,
-        raffleCreationEnabled: false,
+        biddingEnabled: false,
+        ticketSalesEnabled: false,
         numKeysPerIndexPage: 150,
       });
       await restoreConfigs(justiesClient, originalGlobalStates);
     });
 
-    it("Error - invalid market fee rate bps", async () => {
+    it("Records config changes in the audit log", async () => {
+      const originalGlobalStates = await justiesClient.fetchGlobalStates();
+      const totalEntries = (await justiesClient.fetchAuditLog()).totalEntries;
       let input = JustiesProgramClient.getDefaultUpdateConfigsInput();
-      input.marketFeeRateBps = 0;
-      expect(justiesClient.updateConfigs(input)).to.eventually.be
+      input.minOutbidRateBps = originalGlobalStates.minOutbidRateBps + 1;
+      // Unchanged values are not recorded.
+      input.maxRaffledNfts = originalGlobalStates.maxRaffledNfts;
+      input.biddingEnabled = !originalGlobalStates.biddingEnabled;
+      await justiesClient.updateConfigs(input);
+
+      const auditLog = await justiesClient.fetchAuditLog();
+      expect(auditLog.totalEntries.toNumber()).to
+        .eq(totalEntries.toNumber() + 2);
+      const entries = await fetchLatestAuditEntries(justiesClient, 2);
+      expect(entries[0]).to.containSubset({
+        change: {
+          field: {minOutbidRateBps: {}},
+          target: null,
+          oldValue: {u16: {"0": originalGlobalStates.minOutbidRateBps}},
+          newValue: {u16: {"0": originalGlobalStates.minOutbidRateBps + 1}},
+        },
+        signer: justiesClient.providerAddress,
+      });
+      expect(entries[1]).to.containSubset({
+        change: {
+          field: {biddingEnabled: {}},
+          oldValue: {bool: {"0": originalGlobalStates.biddingEnabled}},
+          newValue: {bool: {"0": !originalGlobalStates.biddingEnabled}},
+        },
+        signer: justiesClient.providerAddress,
+      });
+      await restoreConfigs(justiesClient, originalGlobalStates);
+    });
+
+    it("Initializes the global states of the current version", async () => {
+      const globalStates = await justiesClient.fetchGlobalStates();
+      expect(globalStates).to.containSubset({
+        majorVersion: GLOBAL_STATES_MAJOR_VERSION,
+        minorVersion: 7,
+      });
+      // All the features are enabled by default.
+      expect(globalStates.features.toNumber()).to.eq(0b11111);
+    });
+
+    it("Migrates the global states of the current version", async () => {
+      const originalGlobalStates = await justiesClient.fetchGlobalStates();
+      const totalEntries = (await justiesClient.fetchAuditLog()).totalEntries;
+      await justiesClient.migrateGlobalStates();
+      // Nothing changes for the current version.
+      const globalStates = await justiesClient.fetchGlobalStates();
+      expect(globalStates).to.deep.equal(originalGlobalStates);
+      const auditLog = await justiesClient.fetchAuditLog();
+      expect(auditLog.totalEntries.toNumber()).to.eq(totalEntries.toNumber());
+    });
+
+    it("Migrates the global states of version 0.0", async () => {
+      // The disabled creation is carried over by the creation bools of 0.0.
+      await justiesClient.setFeatureEnabled({raffleCreation: {}}, false);
+      const originalGlobalStates = await justiesClient.fetchGlobalStates();
+      await justiesClient.downgradeGlobalStates();
+      const accountInfo = await devEnv.connection.getAccountInfo(
+        justiesClient.findPdaGlobalStates());
+      expect(accountInfo.data.length).to.be.lessThan(200);
+
+      await justiesClient.migrateGlobalStates();
+      // The new fields get the same defaults as the program initialization.
+      const globalStates = await justiesClient.fetchGlobalStates();
+      expect(globalStates).to.containSubset({
+        majorVersion: GLOBAL_STATES_MAJOR_VERSION,
+        minorVersion: 7,
+        configAdmin: justiesClient.providerAddress,
+        treasuryAdmin: justiesClient.providerAddress,
+        quarantineWalletAddress: justiesClient.providerAddress,
+        multisigMembers: [],
+        multisigThreshold: 0,
+        biddingEnabled: true,
+        ticketSalesEnabled: true,
+        claimsEnabled: true,
+        auctionCreationEnabled: true,
+        raffleCreationEnabled: false,
+      });
+      expect(globalStates.features.toNumber()).to.eq(0b01111);
+      expect(globalStates).to.deep.equal(originalGlobalStates);
+      // The readers of the 1.6 layout still see the creation toggles.
+      const migratedAccountInfo = await devEnv.connection.getAccountInfo(
+        justiesClient.findPdaGlobalStates());
+      expect(readLegacyCreationToggles(migratedAccountInfo.data)).to
+        .deep.eq([true, false]);
+      const [majorVersionEntry, minorVersionEntry] =
+        await fetchLatestAuditEntries(justiesClient, 2);
+      expect(majorVersionEntry.change).to.containSubset({
+        field: {globalStatesMajorVersion: {}},
+        newValue: {u8: {"0": GLOBAL_STATES_MAJOR_VERSION}},
+      });
+      expect(minorVersionEntry.change).to.containSubset({
+        field: {globalStatesMinorVersion: {}},
+        newValue: {u8: {"0": 7}},
+      });
+      await justiesClient.setFeatureEnabled({raffleCreation: {}}, true);
+    });
+
+    it("Error - non-authority migrates the global states", async () => {
+      return expect(devEnv.justiesClient("bidder1").migrateGlobalStates()).to
+        .eventually.be.rejectedWith("Error Code: NotTheAuthority");
+    });
+
+    it("Error - changes timelocked configs directly", async () => {
+      let input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+      input.marketFeeRateBps = 250;
+      await expect(justiesClient.updateConfigs(input)).to.eventually.be
+        .rejectedWith("Error Code: TimelockRequired");
+      input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+      input.feeTreasuryAddress = devEnv.generateKeypair().publicKey;
+      return expect(justiesClient.updateConfigs(input)).to.eventually.be
+        .rejectedWith("Error Code: TimelockRequired");
+    });
+
+    it("Error - invalid market fee rate bps", async () => {
+      let input = JustiesProgramClient.getDefaultTimelockedConfigsInput();
+      input.marketFeeRateBps = 10000;
+      await expect(justiesClient.queueConfigChanges(input)).to.eventually.be
         .rejectedWith("Error Code: InvalidMarketFeeRate");
       input.marketFeeRateBps = 10001;
-      return expect(justiesClient.updateConfigs(input)).to.eventually.be
+      return expect(justiesClient.queueConfigChanges(input)).to.eventually.be
         .rejectedWith("Error Code: InvalidMarketFeeRate");
     });
 
+    it("Error - invalid config timelock delay", async () => {
+      let input = JustiesProgramClient.getDefaultTimelockedConfigsInput();
+      input.configTimelockDelay = new BN(31 * 24 * 3600);
+      return expect(justiesClient.queueConfigChanges(input)).to.eventually.be
+        .rejectedWith("Error Code: InvalidConfigTimelockDelay");
+    });
+
+    describe("Timelocked config changes", () => {
+      let executorClient: JustiesProgramClient;
+
+      before(async () => {
+        executorClient = devEnv.createJustiesClient("configExecutor");
+        await devEnv.airdrop("configExecutor", 10);
+      });
+
+      after(async () => {
+        await justiesClient.clearMockTimestamp();
+      });
+
+      it("Executes the config changes after the delay", async () => {
+        const originalGlobalStates = await justiesClient.fetchGlobalStates();
+        const newFeeTreasuryAddress = devEnv.generateKeypair().publicKey;
+        let input = JustiesProgramClient.getDefaultTimelockedConfigsInput();
+        input.marketFeeRateBps = 250;
+        input.feeTreasuryAddress = newFeeTreasuryAddress;
+        await expect(executorClient.queueConfigChanges(input)).to.eventually.be
+          .rejectedWith("Error Code: MissingAdminRole");
+        await justiesClient.queueConfigChanges(input);
+
+        let pendingConfigs = await justiesClient.fetchPendingConfigs();
+        expect(pendingConfigs.input).to.deep.eq(input);
+        expect(pendingConfigs.proposer).to
+          .deep.eq(justiesClient.providerAddress);
+        // Only one set of changes can be pending at a time.
+        await expect(justiesClient.queueConfigChanges(input)).to.eventually.be
+          .rejected;
+
+        await justiesClient.setMockTimestamp(
+          pendingConfigs.activationTimestamp.subn(1));
+        await expect(executorClient.executeConfigChanges()).to.eventually.be
+          .rejectedWith("Error Code: PendingConfigsNotActivated");
+
+        await justiesClient.setMockTimestamp(
+          pendingConfigs.activationTimestamp);
+        await executorClient.executeConfigChanges();
+        let globalStates = await justiesClient.fetchGlobalStates();
+        expect(globalStates.marketFeeRateBps).to.eq(250);
+        expect(globalStates.feeTreasuryAddress).to
+          .deep.eq(newFeeTreasuryAddress);
+        expect(await justiesClient.fetchPendingConfigs()).to.be.null;
+
+        // Restores the timelocked configs.
+        input.marketFeeRateBps = originalGlobalStates.marketFeeRateBps;
+        input.feeTreasuryAddress = originalGlobalStates.feeTreasuryAddress;
+        await justiesClient.queueConfigChanges(input);
+        pendingConfigs = await justiesClient.fetchPendingConfigs();
+        await justiesClient.setMockTimestamp(
+          pendingConfigs.activationTimestamp);
+        await executorClient.executeConfigChanges();
+        globalStates = await justiesClient.fetchGlobalStates();
+        expect(globalStates.marketFeeRateBps).to
+          .eq(originalGlobalStates.marketFeeRateBps);
+        expect(globalStates.feeTreasuryAddress).to
+          .deep.eq(originalGlobalStates.feeTreasuryAddress);
+      });
+
+      it("Cancels the config changes", async () => {
+        let input = JustiesProgramClient.getDefaultTimelockedConfigsInput();
+        input.marketFeeRateBps = 300;
+        await justiesClient.queueConfigChanges(input);
+        let entries = await fetchLatestAuditEntries(justiesClient, 1);
+        expect(entries[0]).to.containSubset({
+          change: {
+            field: {pendingMarketFeeRateBps: {}},
+            oldValue: {none: {}},
+            newValue: {u16: {"0": 300}},
+          },
+          signer: justiesClient.providerAddress,
+        });
+        await expect(executorClient.cancelConfigChanges()).to.eventually.be
+          .rejectedWith("Error Code: MissingAdminRole");
+        await justiesClient.cancelConfigChanges();
+        expect(await justiesClient.fetchPendingConfigs()).to.be.null;
+        // The cancellation clears the queued values in the audit log.
+        entries = await fetchLatestAuditEntries(justiesClient, 1);
+        expect(entries[0]).to.containSubset({
+          change: {
+            field: {pendingMarketFeeRateBps: {}},
+            oldValue: {u16: {"0": 300}},
+            newValue: {none: {}},
+          },
+          signer: justiesClient.providerAddress,
+        });
+        return expect(executorClient.executeConfigChanges()).to.eventually.be
+          .rejected;
+      });
+
+      it("Error - cancels the config changes once activated", async () => {
+        let input = JustiesProgramClient.getDefaultTimelockedConfigsInput();
+        input.marketFeeRateBps = 300;
+        await justiesClient.queueConfigChanges(input);
+        const pendingConfigs = await justiesClient.fetchPendingConfigs();
+        // The activated changes can only be executed.
+        await justiesClient.setMockTimestamp(
+          pendingConfigs.activationTimestamp);
+        await expect(justiesClient.cancelConfigChanges()).to.eventually.be
+          .rejectedWith("Error Code: PendingConfigsActivated");
+        expect(await justiesClient.fetchPendingConfigs()).to.deep.eq(
+          pendingConfigs);
+
+        await justiesClient.setMockTimestamp(
+          pendingConfigs.activationTimestamp.subn(1));
+        await justiesClient.cancelConfigChanges();
+        expect(await justiesClient.fetchPendingConfigs()).to.be.null;
+      });
+    });
+
     it("Error - invalid min outbid rate bps", async () => {
       let input = JustiesProgramClient.getDefaultUpdateConfigsInput();
       input.minOutbidRateBps = 0;
@@ -262,6 +557,339 @@ describe("Justies Test", () => {
       return expect(justiesClient.updateConfigs(input)).to.eventually.be
         .rejectedWith("Error Code: InvalidRaffleDurationRangeSettings");
     });
+
+    describe("Authority transfer", () => {
+      let newAuthorityClient: JustiesProgramClient;
+
+      before(async () => {
+        newAuthorityClient = devEnv.createJustiesClient("newAuthority");
+        await devEnv.airdrop("newAuthority", 10);
+      });
+
+      it("Error - non-authority proposes a new authority", async () => {
+        return expect(newAuthorityClient.proposeAuthority(
+          newAuthorityClient.providerAddress
+        )).to.eventually.be.rejectedWith("Error Code: NotTheAuthority");
+      });
+
+      it("Error - non-pending authority accepts the authority", async () => {
+        await justiesClient.proposeAuthority(
+          newAuthorityClient.providerAddress);
+        return expect(justiesClient.acceptAuthority()).to.eventually.be
+          .rejectedWith("Error Code: NotThePendingAuthority");
+      });
+
+      it("Cancels the authority proposal", async () => {
+        await justiesClient.cancelAuthorityProposal();
+        const globalStates = await justiesClient.fetchGlobalStates();
+        expect(globalStates.pendingAuthority).to.be.null;
+        await expect(newAuthorityClient.acceptAuthority()).to.eventually.be
+          .rejectedWith("Error Code: NotThePendingAuthority");
+        return expect(justiesClient.cancelAuthorityProposal()).to.eventually
+          .be.rejectedWith("Error Code: NoPendingAuthority");
+      });
+
+      it("Transfers the authority", async () => {
+        await justiesClient.proposeAuthority(
+          newAuthorityClient.providerAddress);
+        let globalStates = await justiesClient.fetchGlobalStates();
+        expect(globalStates.pendingAuthority).to
+          .deep.eq(newAuthorityClient.providerAddress);
+        // The proposal doesn't take effect before being accepted.
+        expect(globalStates.authority).to
+          .deep.eq(justiesClient.providerAddress);
+
+        await newAuthorityClient.acceptAuthority();
+        globalStates = await justiesClient.fetchGlobalStates();
+        expect(globalStates.authority).to
+          .deep.eq(newAuthorityClient.providerAddress);
+        expect(globalStates.pendingAuthority).to.be.null;
+
+        // The previous authority loses the access immediately.
+        await expect(justiesClient.grantAdminRole(
+          {configAdmin: {}}, justiesClient.providerAddress
+        )).to.eventually.be.rejectedWith("Error Code: NotTheAuthority");
+
+        // Transfers the authority back for the rest of the tests.
+        await newAuthorityClient.proposeAuthority(justiesClient.providerAddress);
+        await justiesClient.acceptAuthority();
+        globalStates = await justiesClient.fetchGlobalStates();
+        expect(globalStates.authority).to
+          .deep.eq(justiesClient.providerAddress);
+      });
+    });
+
+    describe("Admin roles", () => {
+      let keeperClient: JustiesProgramClient;
+
+      before(async () => {
+        keeperClient = devEnv.createJustiesClient("keeper");
+        await devEnv.airdrop("keeper", 10);
+      });
+
+      it("All roles are held by the authority by default", async () => {
+        const globalStates = await justiesClient.fetchGlobalStates();
+        expect(globalStates.configAdmin).to
+          .deep.eq(justiesClient.providerAddress);
+        expect(globalStates.allowlistCurator).to
+          .deep.eq(justiesClient.providerAddress);
+        expect(globalStates.raffleOperator).to
+          .deep.eq(justiesClient.providerAddress);
+        expect(globalStates.treasuryAdmin).to
+          .deep.eq(justiesClient.providerAddress);
+      });
+
+      it("Error - non-authority grants or revokes roles", async () => {
+        await expect(keeperClient.grantAdminRole(
+          {raffleOperator: {}}, keeperClient.providerAddress
+        )).to.eventually.be.rejectedWith("Error Code: NotTheAuthority");
+        return expect(keeperClient.revokeAdminRole({raffleOperator: {}})).to
+          .eventually.be.rejectedWith("Error Code: NotTheAuthority");
+      });
+
+      it("Raffle operator can't update configs", async () => {
+        await justiesClient.grantAdminRole(
+          {raffleOperator: {}}, keeperClient.providerAddress);
+        const globalStates = await justiesClient.fetchGlobalStates();
+        expect(globalStates.raffleOperator).to
+          .deep.eq(keeperClient.providerAddress);
+
+        let input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+        input.marketFeeRateBps = globalStates.marketFeeRateBps;
+        await expect(keeperClient.updateConfigs(input)).to.eventually.be
+          .rejectedWith("Error Code: MissingAdminRole");
+        input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+        input.feeTreasuryAddress = keeperClient.providerAddress;
+        await expect(keeperClient.updateConfigs(input)).to.eventually.be
+          .rejectedWith("Error Code: MissingAdminRole");
+        input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+        input.minOutbidRateBps = globalStates.minOutbidRateBps;
+        await expect(keeperClient.updateConfigs(input)).to.eventually.be
+          .rejectedWith("Error Code: MissingAdminRole");
+
+        await justiesClient.grantAdminRole(
+          {raffleOperator: {}}, justiesClient.providerAddress);
+      });
+
+      it("Treasury admin only updates treasury configs", async () => {
+        await justiesClient.grantAdminRole(
+          {treasuryAdmin: {}}, keeperClient.providerAddress);
+        const globalStates = await justiesClient.fetchGlobalStates();
+
+        let input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+        input.marketFeeRateBps = globalStates.marketFeeRateBps;
+        await keeperClient.updateConfigs(input);
+        // The config admin no longer holds the treasury admin role.
+        await expect(justiesClient.updateConfigs(input)).to.eventually.be
+          .rejectedWith("Error Code: MissingAdminRole");
+
+        // Updating both treasury and regular configs requires both roles.
+        input.minOutbidRateBps = globalStates.minOutbidRateBps;
+        await expect(keeperClient.updateConfigs(input)).to.eventually.be
+          .rejectedWith("Error Code: MissingAdminRole");
+
+        await justiesClient.grantAdminRole(
+          {treasuryAdmin: {}}, justiesClient.providerAddress);
+        await justiesClient.updateConfigs(input);
+      });
+
+      it("Revokes a role", async () => {
+        await justiesClient.revokeAdminRole({configAdmin: {}});
+        const globalStates = await justiesClient.fetchGlobalStates();
+        expect(globalStates.configAdmin).to.be.null;
+
+        const input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+        input.minOutbidRateBps = globalStates.minOutbidRateBps;
+        await expect(justiesClient.updateConfigs(input)).to.eventually.be
+          .rejectedWith("Error Code: MissingAdminRole");
+
+        await justiesClient.grantAdminRole(
+          {configAdmin: {}}, justiesClient.providerAddress);
+        await justiesClient.updateConfigs(input);
+      });
+    });
+
+    describe("Multisig", () => {
+      let member1Client: JustiesProgramClient;
+      let member2Client: JustiesProgramClient;
+      let nonMemberClient: JustiesProgramClient;
+
+      async function buildUpdateConfigsInstruction(input: UpdateConfigsInput) {
+        return await justiesClient.justiesProgram.methods.updateConfigs(input)
+          .accounts({
+            globalStates: justiesClient.findPdaGlobalStates(),
+            auditLog: justiesClient.findPdaAuditLog(),
+            authority: justiesClient.findPdaMultisigAuthority(),
+            systemProgram: SystemProgram.programId,
+          }).instruction();
+      }
+
+      before(async () => {
+        member1Client = devEnv.createJustiesClient("multisigMember1");
+        await devEnv.airdrop("multisigMember1", 10);
+        member2Client = devEnv.createJustiesClient("multisigMember2");
+        await devEnv.airdrop("multisigMember2", 10);
+        nonMemberClient = devEnv.createJustiesClient("multisigNonMember");
+        await devEnv.airdrop("multisigNonMember", 10);
+      });
+
+      it("Error - invalid multisig settings", async () => {
+        const members = [
+          member1Client.providerAddress,
+          member2Client.providerAddress,
+        ];
+        await expect(member1Client.setMultisig(members, 2)).to.eventually.be
+          .rejectedWith("Error Code: NotTheAuthority");
+        await expect(justiesClient.setMultisig(members, 3)).to.eventually.be
+          .rejectedWith("Error Code: InvalidMultisigSettings");
+        await expect(justiesClient.setMultisig(members, 0)).to.eventually.be
+          .rejectedWith("Error Code: InvalidMultisigSettings");
+        return expect(justiesClient.setMultisig(
+          [member1Client.providerAddress, member1Client.providerAddress], 1
+        )).to.eventually.be.rejectedWith("Error Code: InvalidMultisigSettings");
+      });
+
+      it("Executes an admin proposal approved by the multisig", async () => {
+        await justiesClient.setMultisig([
+          member1Client.providerAddress,
+          member2Client.providerAddress,
+          nonMemberClient.providerAddress,
+        ], 2);
+        // Removes a member by resetting the members.
+        await justiesClient.setMultisig([
+          member1Client.providerAddress,
+          member2Client.providerAddress,
+        ], 2);
+        let globalStates = await justiesClient.fetchGlobalStates();
+        expect(globalStates.multisigThreshold).to.eq(2);
+        expect(globalStates.multisigMembers).to.have.lengthOf(2);
+
+        await justiesClient.grantAdminRole(
+          {configAdmin: {}}, justiesClient.findPdaMultisigAuthority());
+        const originalMinOutbidRateBps = globalStates.minOutbidRateBps;
+        const input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+        input.minOutbidRateBps = originalMinOutbidRateBps + 1;
+        // The single key no longer holds the config admin role.
+        await expect(justiesClient.updateConfigs(input)).to.eventually.be
+          .rejectedWith("Error Code: MissingAdminRole");
+
+        const instruction = await buildUpdateConfigsInstruction(input);
+        await expect(nonMemberClient.createAdminProposal(instruction)).to
+          .eventually.be.rejectedWith("Error Code: NotAMultisigMember");
+        const proposalId = await member1Client.createAdminProposal(
+          instruction);
+        let proposal = await justiesClient.fetchAdminProposal(proposalId);
+        expect(proposal.approvers).to
+          .deep.eq([member1Client.providerAddress]);
+        expect(proposal.executed).to.be.false;
+
+        await expect(member1Client.approveAdminProposal(proposalId)).to
+          .eventually.be.rejectedWith("Error Code: AdminProposalAlreadyApproved");
+        await expect(nonMemberClient.approveAdminProposal(proposalId)).to
+          .eventually.be.rejectedWith("Error Code: NotAMultisigMember");
+        await expect(nonMemberClient.executeAdminProposal(proposalId)).to
+          .eventually.be
+          .rejectedWith("Error Code: NotEnoughAdminProposalApprovals");
+
+        await member2Client.approveAdminProposal(proposalId);
+        // Anyone can execute the proposal once it's approved.
+        await nonMemberClient.executeAdminProposal(proposalId);
+        proposal = await justiesClient.fetchAdminProposal(proposalId);
+        expect(proposal.executed).to.be.true;
+        globalStates = await justiesClient.fetchGlobalStates();
+        expect(globalStates.minOutbidRateBps).to
+          .eq(originalMinOutbidRateBps + 1);
+
+        await expect(member1Client.executeAdminProposal(proposalId)).to
+          .eventually.be.rejectedWith("Error Code: AdminProposalAlreadyExecuted");
+
+        // Restores the settings for the rest of the tests.
+        await justiesClient.grantAdminRole(
+          {configAdmin: {}}, justiesClient.providerAddress);
+        input.minOutbidRateBps = originalMinOutbidRateBps;
+        await justiesClient.updateConfigs(input);
+        await justiesClient.setMultisig([], 0);
+      });
+
+      it("Error - execute admin proposal targeting the admin proposals",
+        async () => {
+          await justiesClient.setMultisig([member1Client.providerAddress], 1);
+          const multisigAuthority = justiesClient.findPdaMultisigAuthority();
+          const totalAdminProposals =
+            (await justiesClient.fetchGlobalStates()).totalAdminProposals;
+          const approveInstruction = await justiesClient.justiesProgram.methods
+            .approveAdminProposal(totalAdminProposals).accounts({
+              adminProposal:
+                justiesClient.findPdaAdminProposal(totalAdminProposals),
+              globalStates: justiesClient.findPdaGlobalStates(),
+              approver: multisigAuthority,
+              systemProgram: SystemProgram.programId,
+            }).instruction();
+          let proposalId = await member1Client.createAdminProposal(
+            approveInstruction);
+          await expect(member1Client.executeAdminProposal(proposalId)).to
+            .eventually.be
+            .rejectedWith("Error Code: InvalidAdminProposalInstruction");
+
+          // The proposed instruction can't take the proposal account either.
+          const updateConfigsInstruction = await buildUpdateConfigsInstruction(
+            JustiesProgramClient.getDefaultUpdateConfigsInput());
+          updateConfigsInstruction.keys.push({
+            pubkey: justiesClient.findPdaAdminProposal(proposalId.addn(1)),
+            isSigner: false,
+            isWritable: true,
+          });
+          proposalId = await member1Client.createAdminProposal(
+            updateConfigsInstruction);
+          await expect(member1Client.executeAdminProposal(proposalId)).to
+            .eventually.be
+            .rejectedWith("Error Code: InvalidAdminProposalInstruction");
+          const proposal = await justiesClient.fetchAdminProposal(proposalId);
+          expect(proposal.executed).to.be.false;
+
+          await justiesClient.setMultisig([], 0);
+        });
+
+      it("Clears the approvals once the multisig members change", async () => {
+        const members = [
+          member1Client.providerAddress,
+          member2Client.providerAddress,
+        ];
+        await justiesClient.setMultisig(members, 2);
+        await justiesClient.grantAdminRole(
+          {configAdmin: {}}, justiesClient.findPdaMultisigAuthority());
+        const originalMinOutbidRateBps =
+          (await justiesClient.fetchGlobalStates()).minOutbidRateBps;
+        const input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+        input.minOutbidRateBps = originalMinOutbidRateBps + 1;
+        const proposalId = await member1Client.createAdminProposal(
+          await buildUpdateConfigsInstruction(input));
+
+        // Removes & re-adds member1, whose earlier approval no longer counts.
+        await justiesClient.setMultisig([member2Client.providerAddress], 1);
+        await justiesClient.setMultisig(members, 2);
+        await member2Client.approveAdminProposal(proposalId);
+        let proposal = await justiesClient.fetchAdminProposal(proposalId);
+        expect(proposal.approvers).to
+          .deep.eq([member2Client.providerAddress]);
+        await expect(nonMemberClient.executeAdminProposal(proposalId)).to
+          .eventually.be
+          .rejectedWith("Error Code: NotEnoughAdminProposalApprovals");
+
+        await member1Client.approveAdminProposal(proposalId);
+        await nonMemberClient.executeAdminProposal(proposalId);
+        const globalStates = await justiesClient.fetchGlobalStates();
+        expect(globalStates.minOutbidRateBps).to
+          .eq(originalMinOutbidRateBps + 1);
+
+        // Restores the settings for the rest of the tests.
+        await justiesClient.grantAdminRole(
+          {configAdmin: {}}, justiesClient.providerAddress);
+        input.minOutbidRateBps = originalMinOutbidRateBps;
+        await justiesClient.updateConfigs(input);
+        await justiesClient.setMultisig([], 0);
+      });
+    });
   });
 
   describe("Allowlisting", () => {
@@ -294,6 +922,17 @@ describe("Justies Test", () => {
           allowed: true,
         },
       ]);
+
+      const [auditEntry] = await fetchLatestAuditEntries(justiesClient, 1);
+      expect(auditEntry).to.containSubset({
+        change: {
+          field: {currencyTokenAllowlist: {}},
+          target: devEnv.getTokenMintAddress("USDT"),
+          oldValue: {bool: {"0": false}},
+          newValue: {bool: {"0": true}},
+        },
+        signer: justiesClient.providerAddress,
+      });
     });
 
     it(
@@ -360,6 +999,155 @@ describe("Justies Test", () => {
           .rejectedWith("nft_allowlist_index. Error Code: ConstraintSeeds");
       }
     );
+
+    it("Error - add to allowlist without the allowlist curator role",
+      async () => {
+        const bidderClient = devEnv.justiesClient("bidder1");
+        await expect(bidderClient.addCurrencyTokenToAllowlist(
+          devEnv.getTokenMintAddress("FOO"))
+        ).to.eventually.be.rejectedWith("Error Code: MissingAdminRole");
+        return expect(bidderClient.addNftCollectionToAllowlist(
+          devEnv.getNftCollection("Bar").address)
+        ).to.eventually.be.rejectedWith("Error Code: MissingAdminRole");
+      }
+    );
+
+    it("Suspends & reinstates allowlist entries", async () => {
+      const justiesClient = devEnv.justiesClient("authority");
+      const usdcMintAddress = devEnv.getTokenMintAddress("USDC");
+      await expect(devEnv.justiesClient("bidder1").suspendAllowlistEntry(
+        {currencyToken: {}}, usdcMintAddress, null
+      )).to.eventually.be.rejectedWith("Error Code: MissingAdminRole");
+
+      await justiesClient.suspendAllowlistEntry(
+        {currencyToken: {}}, usdcMintAddress, 3);
+      expect(await justiesClient.fetchTokenAllowlistStates(usdcMintAddress)).to
+        .containSubset({allowed: false, reasonCode: 3});
+      const [auditEntry] = await fetchLatestAuditEntries(justiesClient, 1);
+      expect(auditEntry.change).to.containSubset({
+        field: {currencyTokenAllowlist: {}},
+        target: usdcMintAddress,
+        newValue: {bool: {"0": false}},
+        reasonCode: 3,
+      });
+      await expect(justiesClient.suspendAllowlistEntry(
+        {currencyToken: {}}, usdcMintAddress, null
+      )).to.eventually.be
+        .rejectedWith("Error Code: AllowlistEntryAlreadySuspended");
+
+      await justiesClient.reinstateAllowlistEntry(
+        {currencyToken: {}}, usdcMintAddress);
+      expect(await justiesClient.fetchTokenAllowlistStates(usdcMintAddress)).to
+        .containSubset({allowed: true, reasonCode: null});
+      return expect(justiesClient.reinstateAllowlistEntry(
+        {currencyToken: {}}, usdcMintAddress
+      )).to.eventually.be.rejectedWith("Error Code: AllowlistEntryNotSuspended");
+    });
+
+    it("Removes & adds back allowlist entries", async () => {
+      const justiesClient = devEnv.justiesClient("authority");
+      const collectionAddress = devEnv.getNftCollection("justs").address;
+      const totalAllowed =
+        (await justiesClient.fetchGlobalStates()).totalAllowedNftCollections;
+      await justiesClient.removeAllowlistEntry(
+        {nftCollection: {}}, collectionAddress, 5);
+      expect((await justiesClient.fetchGlobalStates())
+        .totalAllowedNftCollections.toNumber()).to.equal(
+        totalAllowed.toNumber() - 1);
+      let allowlistStates =
+        await generatorToList(justiesClient.getNftAllowlistStates());
+      expect(allowlistStates.map(states => states.tokenMintAddress)).to.not
+        .deep.include(collectionAddress);
+
+      await justiesClient.addNftCollectionToAllowlist(collectionAddress);
+      allowlistStates =
+        await generatorToList(justiesClient.getNftAllowlistStates());
+      expect(allowlistStates.filter(
+        states => states.tokenMintAddress.equals(collectionAddress)
+      )).to.have.lengthOf(1);
+      expect((await justiesClient.fetchGlobalStates())
+        .totalAllowedNftCollections.toNumber()).to.equal(
+        totalAllowed.toNumber());
+    });
+
+    it("Migrates allowlist entries of the current layout as a no-op",
+      async () => {
+        const justiesClient = devEnv.justiesClient("bidder1");
+        const usdcMintAddress = devEnv.getTokenMintAddress("USDC");
+        const allowlistStates =
+          await justiesClient.fetchTokenAllowlistStates(usdcMintAddress);
+        await justiesClient.migrateAllowlistStates(
+          {currencyToken: {}}, usdcMintAddress);
+        expect(await justiesClient.fetchTokenAllowlistStates(usdcMintAddress))
+          .to.deep.equal(allowlistStates);
+      });
+
+    it("Batch adds currency tokens to allowlist", async () => {
+      const justiesClient = devEnv.justiesClient("authority");
+      const tokenNames = ["Batch #1", "Batch #2", "Batch #3"];
+      for (const tokenName of tokenNames) {
+        await devEnv.createToken(tokenName);
+      }
+      const tokenMintAddresses =
+        tokenNames.map(tokenName => devEnv.getTokenMintAddress(tokenName));
+
+      await expect(devEnv.justiesClient("bidder1")
+        .batchAddCurrencyTokensToAllowlist(tokenMintAddresses)
+      ).to.eventually.be.rejectedWith("Error Code: MissingAdminRole");
+      await expect(justiesClient.batchAddCurrencyTokensToAllowlist([
+        tokenMintAddresses[0],
+        devEnv.getTokenMintAddress("USDC"),
+      ])).to.eventually.be.rejectedWith(
+        "entries[1].allowlist_states. Error Code: TokenAlreadyInAllowlist");
+
+      // Funding an allowlist states address beforehand doesn't block the add.
+      const signature = await devEnv.connection.requestAirdrop(
+        justiesClient.findPdaTokenAllowlistStates(tokenMintAddresses[0]), 1000);
+      await devEnv.connection.confirmTransaction(signature);
+      await justiesClient.batchAddCurrencyTokensToAllowlist(tokenMintAddresses);
+      expect(await generatorToList(
+        justiesClient.getTokenAllowlistStates())).to.containSubset(
+        tokenMintAddresses.map(tokenMintAddress => ({
+          tokenMintAddress,
+          allowed: true,
+        }))
+      );
+      const auditEntries = await fetchLatestAuditEntries(justiesClient, 3);
+      expect(auditEntries.map(entry => entry.change)).to.containSubset(
+        tokenMintAddresses.map(tokenMintAddress => ({
+          field: {currencyTokenAllowlist: {}},
+          target: tokenMintAddress,
+          newValue: {bool: {"0": true}},
+        }))
+      );
+    });
+
+    it("Batch adds nft collections to allowlist", async () => {
+      const justiesClient = devEnv.justiesClient("authority");
+      const collectionNames = ["Batch #1", "Batch #2", "Batch #3"];
+      for (const collectionName of collectionNames) {
+        await devEnv.createNftCollection(collectionName);
+      }
+      const collectionMintAddresses = collectionNames.map(
+        collectionName => devEnv.getNftCollection(collectionName).address);
+
+      // "Gods #1" is an NFT within a collection rather than a collection.
+      await expect(justiesClient.batchAddNftCollectionsToAllowlist([
+        collectionMintAddresses[0],
+        devEnv.getNft("Gods #1").mint.address,
+      ])).to.eventually.be.rejectedWith(
+        "entries[1].collection_metadata. Error Code: InvalidNftCollectionMetadata");
+
+      await justiesClient.batchAddNftCollectionsToAllowlist(
+        collectionMintAddresses);
+      expect(await generatorToList(
+        justiesClient.getNftAllowlistStates())).to.containSubset(
+        collectionMintAddresses.map(tokenMintAddress => ({
+          tokenMintAddress,
+          allowed: true,
+        }))
+      );
+    });
   });
 
   describe("Auction", () => {
@@ -559,19 +1347,117 @@ describe("Justies Test", () => {
             .rejectedWith("Error Code: InvalidRevenueShareConfig");
         }
       );
+      it(
+        "Error - create auction below the currency listing limits",
+        async () => {
+          const authorityClient = devEnv.justiesClient("authority");
+          const usdtMintAddress = devEnv.getTokenMintAddress("USDT");
+          const listingLimits =
+            JustiesProgramClient.getDefaultCurrencyListingLimits();
+          listingLimits.minTicketPrice = new BN(toLamport(10));
+          listingLimits.maxTicketPrice = new BN(toLamport(5));
+          await expect(authorityClient.setCurrencyListingLimits(
+            usdtMintAddress, listingLimits
+          )).to.eventually.be
+            .rejectedWith("Error Code: InvalidCurrencyListingLimits");
+
+          listingLimits.minStartBid = new BN(toLamport(100));
+          listingLimits.minTicketPrice = new BN(0);
+          listingLimits.maxTicketPrice = null;
+          await expect(devEnv.justiesClient("auctionCreator")
+            .setCurrencyListingLimits(usdtMintAddress, listingLimits)
+          ).to.eventually.be.rejectedWith("Error Code: MissingAdminRole");
+          await authorityClient.setCurrencyListingLimits(
+            usdtMintAddress, listingLimits);
+          const [auditEntry] = await fetchLatestAuditEntries(authorityClient, 1);
+          expect(auditEntry.change).to.containSubset({
+            field: {minStartBid: {}},
+            target: usdtMintAddress,
+          });
+
+          await expect(devEnv.justiesClient("auctionCreator").createAuction(
+            devEnv.getNft("Gods #1").mint.address,
+            usdtMintAddress,
+            24 * 3600,
+            toLamport(50),
+            [],
+            [
+              {
+                revenueReceiver: revenueShareWalletAddress,
+                shareBps: 10000,
+              },
+            ],
+          )).to.eventually.be.rejectedWith("Error Code: InvalidStartBid");
+
+          await authorityClient.setCurrencyListingLimits(
+            usdtMintAddress,
+            JustiesProgramClient.getDefaultCurrencyListingLimits(),
+          );
+        }
+      );
+      it(
+        "Error - create auction out of the collection's duration range",
+        async () => {
+          const authorityClient = devEnv.justiesClient("authority");
+          const godsCollectionAddress =
+            devEnv.getNftCollection("Gods").address;
+          const globalStates = await authorityClient.fetchGlobalStates();
+          const listingRules =
+            JustiesProgramClient.getDefaultListingRuleOverrides();
+          listingRules.marketFeeRateBps = globalStates.marketFeeRateBps + 1;
+          await expect(authorityClient.setNftCollectionListingRules(
+            godsCollectionAddress, listingRules
+          )).to.eventually.be
+            .rejectedWith("Error Code: InvalidCollectionMarketFeeRate");
+          listingRules.marketFeeRateBps = null;
+          listingRules.maxAuctionDuration = globalStates.minAuctionDuration;
+          await expect(authorityClient.setNftCollectionListingRules(
+            godsCollectionAddress, listingRules
+          )).to.eventually.be
+            .rejectedWith("Error Code: InvalidAuctionDurationRangeSettings");
+
+          listingRules.maxAuctionDuration = new BN(12 * 3600);
+          await expect(devEnv.justiesClient("auctionCreator")
+            .setNftCollectionListingRules(godsCollectionAddress, listingRules)
+          ).to.eventually.be.rejectedWith("Error Code: MissingAdminRole");
+          await authorityClient.setNftCollectionListingRules(
+            godsCollectionAddress, listingRules);
+          const [auditEntry] = await fetchLatestAuditEntries(authorityClient, 1);
+          expect(auditEntry.change).to.containSubset({
+            field: {maxAuctionDuration: {}},
+            oldValue: {none: {}},
+            target: godsCollectionAddress,
+          });
+
+          await expect(devEnv.justiesClient("auctionCreator").createAuction(
+            devEnv.getNft("Gods #1").mint.address,
+            devEnv.getTokenMintAddress("USDT"),
+            24 * 3600,
+            50e9,
+            [],
+            [
+              {
+                revenueReceiver: revenueShareWalletAddress,
+                shareBps: 10000,
+              },
+            ],
+          )).to.eventually.be.rejectedWith("Error Code: InvalidAuctionDuration");
+
+          await authorityClient.setNftCollectionListingRules(
+            godsCollectionAddress,
+            JustiesProgramClient.getDefaultListingRuleOverrides(),
+          );
+        }
+      );
       it(
         "Error - create auction when auction creation is disabled.",
         async () => {
           const justiesClient = devEnv.justiesClient("auctionCreator");
-          const originalGlobalStates = await justiesClient.fetchGlobalStates();
-
-          const updateConfigsInput = JustiesProgramClient.getDefaultUpdateConfigsInput();
-          updateConfigsInput.auctionCreationEnabled = false;
-          await devEnv.justiesClient("authority")
-            .updateConfigs(updateConfigsInput);
+          const authorityClient = devEnv.justiesClient("authority");
+          await authorityClient.setFeatureEnabled({auctionCreation: {}}, false);
 
           await expect(justiesClient.createAuction(
-            devEnv.getNft("Gods #1").address,
+            devEnv.getNft("Gods #1").mint.address,
             devEnv.getTokenMintAddress("USDT"),
             24 * 3600,
             toLamport(50),
@@ -587,14 +1473,40 @@ describe("Justies Test", () => {
               },
             ],
           )).to.eventually.be
-            .rejectedWith("Error Code: AuctionCreationDisabled");
+            .rejectedWith(
+              "Error Message: the feature is disabled: AuctionCreation");
 
-          await restoreConfigs(
-            devEnv.justiesClient("authority"),
-            originalGlobalStates
-          );
+          await authorityClient.setFeatureEnabled({auctionCreation: {}}, true);
         }
       );
+      it("Error - create auction of a disabled feature", async () => {
+        const justiesClient = devEnv.justiesClient("auctionCreator");
+        const authorityClient = devEnv.justiesClient("authority");
+        await authorityClient.setFeatureEnabled({exclusiveAuctions: {}}, false);
+
+        // The error names the disabled feature.
+        await expect(justiesClient.createAuction(
+          devEnv.getNft("Gods #1").mint.address,
+          devEnv.getTokenMintAddress("USDT"),
+          24 * 3600,
+          toLamport(50),
+          [
+            {
+              groupType: {tokenHolderGroup: {}},
+              key: devEnv.getTokenMintAddress("USDT"),
+            },
+          ],
+          [
+            {
+              revenueReceiver: justiesClient.providerAddress,
+              shareBps: 10000,
+            },
+          ],
+        )).to.eventually.be.rejectedWith(
+          "Error Message: the feature is disabled: ExclusiveAuctions");
+
+        await authorityClient.setFeatureEnabled({exclusiveAuctions: {}}, true);
+      });
     });
     describe("Non-exclusive auction use cases", () => {
       before(async () => {
@@ -687,6 +1599,22 @@ describe("Justies Test", () => {
             auction.currencyTokenMintAddress,
           )).to.eventually.be.rejectedWith("Error Code: InvalidBidAmount");
         });
+        it("Error - bids when the currency token is suspended.", async () => {
+          const justiesClient = devEnv.justiesClient("bidder1");
+          const authorityClient = devEnv.justiesClient("authority");
+          const auction = await justiesClient.fetchLatestAuction();
+          await authorityClient.suspendAllowlistEntry(
+            {currencyToken: {}}, auction.currencyTokenMintAddress, 1);
+          await expect(justiesClient.makeBid(
+            auction.id,
+            toLamport(50),
+            toLamport(50),
+            null,
+            auction.currencyTokenMintAddress,
+          )).to.eventually.be.rejectedWith("Error Code: TokenNotInAllowlist");
+          await authorityClient.reinstateAllowlistEntry(
+            {currencyToken: {}}, auction.currencyTokenMintAddress);
+        });
         it("Error - less than start bid.", async () => {
           const justiesClient = devEnv.justiesClient("bidder1");
           const auction = await justiesClient.fetchLatestAuction();
@@ -1043,9 +1971,37 @@ describe("Justies Test", () => {
             );
         });
 
+        it("Error - bids while bidding is paused", async () => {
+          const authorityClient = devEnv.justiesClient("authority");
+          const justiesClient = devEnv.justiesClient("bidder3");
+          const auction = await justiesClient.fetchLatestAuction();
+          const updateConfigsInput =
+            JustiesProgramClient.getDefaultUpdateConfigsInput();
+          updateConfigsInput.biddingEnabled = false;
+          await authorityClient.updateConfigs(updateConfigsInput);
+
+          await expect(justiesClient.makeBid(
+            auction.id,
+            toLamport(500),
+            toLamport(500),
+            null,
+            auction.currencyTokenMintAddress,
+          )).to.eventually.be.rejectedWith("Error Code: BiddingDisabled");
+
+          updateConfigsInput.biddingEnabled = true;
+          await authorityClient.updateConfigs(updateConfigsInput);
+        });
+
         it("bidder4 cancels bid", async () => {
           const justiesClient = devEnv.justiesClient("bidder4");
           const auctionId = await justiesClient.latestAuctionId();
+          // The refunds stay open while bidding & claims are paused.
+          const authorityClient = devEnv.justiesClient("authority");
+          const updateConfigsInput =
+            JustiesProgramClient.getDefaultUpdateConfigsInput();
+          updateConfigsInput.biddingEnabled = false;
+          updateConfigsInput.claimsEnabled = false;
+          await authorityClient.updateConfigs(updateConfigsInput);
           const auctionAddress = justiesClient.findPdaAuction(auctionId);
           const bidEscrowAccountAddress = justiesClient.findPdaBidEscrow(
             auctionAddress,
@@ -1061,6 +2017,9 @@ describe("Justies Test", () => {
           )).amount);
 
           await justiesClient.cancelAuctionBid(auctionId);
+          updateConfigsInput.biddingEnabled = true;
+          updateConfigsInput.claimsEnabled = true;
+          await authorityClient.updateConfigs(updateConfigsInput);
           bid = await justiesClient.fetchAuctionBid(
             auctionAddress,
             justiesClient.providerAddress
@@ -1134,6 +2093,22 @@ describe("Justies Test", () => {
             );
         });
 
+        it("Error - claiming lot while claims are paused", async () => {
+          const authorityClient = devEnv.justiesClient("authority");
+          const justiesClient = devEnv.justiesClient("bidder1");
+          const auctionId = await justiesClient.latestAuctionId();
+          const updateConfigsInput =
+            JustiesProgramClient.getDefaultUpdateConfigsInput();
+          updateConfigsInput.claimsEnabled = false;
+          await authorityClient.updateConfigs(updateConfigsInput);
+
+          await expect(justiesClient.claimLotNft(auctionId)).to.eventually.be
+            .rejectedWith("Error Code: ClaimsDisabled");
+
+          updateConfigsInput.claimsEnabled = true;
+          await authorityClient.updateConfigs(updateConfigsInput);
+        });
+
         it("Claiming lot", async () => {
           const justiesClient = devEnv.justiesClient("bidder1");
           let auction = await justiesClient.fetchLatestAuction();
@@ -1216,6 +2191,22 @@ describe("Justies Test", () => {
               "bid_escrow_token_account. Error Code: AccountNotInitialized");
         });
 
+        it("Error - claiming revenue while claims are paused", async () => {
+          const authorityClient = devEnv.justiesClient("authority");
+          const justiesClient = devEnv.justiesClient("auctionCreator");
+          const auctionId = await justiesClient.latestAuctionId();
+          const updateConfigsInput =
+            JustiesProgramClient.getDefaultUpdateConfigsInput();
+          updateConfigsInput.claimsEnabled = false;
+          await authorityClient.updateConfigs(updateConfigsInput);
+
+          await expect(justiesClient.claimAuctionRevenue(auctionId)).to
+            .eventually.be.rejectedWith("Error Code: ClaimsDisabled");
+
+          updateConfigsInput.claimsEnabled = true;
+          await authorityClient.updateConfigs(updateConfigsInput);
+        });
+
         it("Claiming revenue", async () => {
           const justiesClient = devEnv.justiesClient("auctionCreator");
           const auction = await justiesClient.fetchLatestAuction();
@@ -1251,6 +2242,8 @@ describe("Justies Test", () => {
             "auctionCreator",
             "USDT"
           );
+          const previousStats = await fetchCurrencyStatsCounters(
+            justiesClient, auction.currencyTokenMintAddress);
 
           await justiesClient.claimAuctionRevenue(auction.id);
 
@@ -1278,6 +2271,15 @@ describe("Justies Test", () => {
             .eq(previousRevenueShareAmount + expectedSharedRevenue);
           expect(currentTokenAmount).to
             .eq(previousTokenAmount + expectedCreatorRevenue);
+          // Expects that the settlement is recorded in the fee ledger of the
+          // currency.
+          expect(await fetchCurrencyStatsCounters(
+            justiesClient, auction.currencyTokenMintAddress)).to.deep.eq({
+            totalFees: previousStats.totalFees + expectedFee,
+            totalVolume: previousStats.totalVolume + totalRevenue,
+            numSettledAuctions: previousStats.numSettledAuctions + 1,
+            numSettledRaffles: previousStats.numSettledRaffles,
+          });
         });
 
         it("Error - claiming revenue multiple times", async () => {
@@ -1667,6 +2669,33 @@ describe("Justies Test", () => {
         expect(await devEnv.connection.getAccountInfo(
           lotEscrowAddress)).to.be.null;
       });
+
+      it("Migrates an auction of the older layout", async () => {
+        const auctionAddress = justiesClient.findPdaAuction(auctionId);
+        const auction = await justiesClient.fetchAuction(auctionId);
+        const dataLength = (await devEnv.connection.getAccountInfo(
+          auctionAddress)).data.length;
+        // Rewrites the auction in the layout before the allowlist entries,
+        // without the allowlist type & key, the minimum outbid increment, the
+        // listing fee deposit and the live listing flag.
+        await devEnv.justiesClient("authority").downgradeAuction(auctionId);
+        expect((await devEnv.connection.getAccountInfo(
+          auctionAddress)).data.length).to.eq(dataLength - 50);
+
+        await devEnv.justiesClient("bidder2").migrateAuction(
+          auctionId, devEnv.getNft("Gods #3").address);
+        const migratedAuction = await justiesClient.fetchAuction(auctionId);
+        expect(migratedAuction).to.deep.equal(auction);
+        // Expects the defaults of the new fields.
+        expect(migratedAuction.nftAllowlistType).to.eql({nftCollection: {}});
+        expect(migratedAuction.nftAllowlistKey).to.eql(
+          devEnv.getNftCollection("Gods").address);
+        expect(migratedAuction.minOutbidIncrement.toNumber()).to.eq(0);
+        expect(migratedAuction.listingFeeDeposit.toNumber()).to.eq(0);
+        expect(migratedAuction.countedAsLive).to.be.false;
+        expect((await devEnv.connection.getAccountInfo(
+          auctionAddress)).data.length).to.eq(dataLength);
+      });
       it("Error - makes bid on a cancelled auction", async () => {
         return expect(devEnv.justiesClient("bidder2").makeBid(
           auctionId,
@@ -1703,7 +2732,233 @@ describe("Justies Test", () => {
         return expect(justiesClient.cancelAuction(auctionId))
           .to.eventually.be.rejectedWith("Error Code: AuctionNotCancelable");
       });
-    });
+      it("Error - non-authority force-cancels the auction", async () => {
+        return expect(justiesClient.forceCancelAuction(
+          auctionId,
+          justiesClient.providerAddress,
+          null
+        )).to.eventually.be.rejectedWith("Error Code: NotTheAuthority");
+      });
+      it("Error - force-cancels to an invalid NFT recipient", async () => {
+        const authorityClient = devEnv.justiesClient("authority");
+        return expect(authorityClient.forceCancelAuction(
+          auctionId,
+          devEnv.justiesClient("bidder3").providerAddress,
+          null
+        )).to.eventually.be.rejectedWith("Error Code: InvalidNftRecipient");
+      });
+      it("Force-cancels to the quarantine wallet with bids", async () => {
+        const authorityClient = devEnv.justiesClient("authority");
+        const globalStates = await authorityClient.fetchGlobalStates();
+        const quarantineWalletAddress = globalStates.quarantineWalletAddress;
+        await authorityClient.forceCancelAuction(
+          auctionId,
+          quarantineWalletAddress,
+          7
+        );
+        // Expects the auction status is force-cancelled.
+        const auction = await authorityClient.fetchAuction(auctionId);
+        const auctionAddress = authorityClient.findPdaAuction(auctionId);
+        expect(auction.status).to.eql({forceCancelled: {}});
+        // Expects the NFT has been moved to the quarantine wallet.
+        // (the quarantine wallet defaults to the authority).
+        const quarantineNftAccount = await devEnv.getSplTokenAccount(
+          devEnv.getNftAta("authority", "Gods #3"));
+        expect(Number(quarantineNftAccount.amount)).to.eq(1);
+        // Expects the lot escrow nft account has been closed.
+        expect(await devEnv.connection.getAccountInfo(
+          authorityClient.findPdaLotEscrow(auctionAddress))).to.be.null;
+        const [auditEntry] = await fetchLatestAuditEntries(authorityClient, 1);
+        expect(auditEntry).to.containSubset({
+          change: {
+            field: {auctionForceCancelled: {}},
+            target: auctionAddress,
+            oldValue: {pubkey: {"0": auction.creator}},
+            newValue: {pubkey: {"0": quarantineWalletAddress}},
+            reasonCode: 7,
+          },
+          signer: authorityClient.providerAddress,
+        });
+      });
+      it("Top bidder withdraws the bid after force-cancellation", async () => {
+        const bidderClient = devEnv.justiesClient("bidder2");
+        const previousTokenAmount = Number((await devEnv.getSplTokenAccount(
+          devEnv.getTokenAta("bidder2", "USDT")
+        )).amount);
+        await bidderClient.cancelAuctionBid(auctionId);
+        const currentTokenAmount = Number((await devEnv.getSplTokenAccount(
+          devEnv.getTokenAta("bidder2", "USDT")
+        )).amount);
+        expect(currentTokenAmount).to.eq(previousTokenAmount + toLamport(50));
+      });
+    });
+
+    describe("Auction of an allowlisted NFT mint", () => {
+      let justiesClient: JustiesProgramClient;
+      let nftMintAddress: PublicKey;
+
+      before(async () => {
+        justiesClient = devEnv.justiesClient("auctionCreator");
+        nftMintAddress = devEnv.getNft("Artwork #1").mint.address;
+        await devEnv.transferNft("auctionCreator", "Artwork #1");
+      });
+
+      async function createArtworkAuction() {
+        await justiesClient.createAuction(
+          nftMintAddress,
+          devEnv.getTokenMintAddress("USDT"),
+          24 * 3600,
+          toLamport(50),
+          [],
+          [
+            {
+              revenueReceiver: justiesClient.providerAddress,
+              shareBps: 10000,
+            },
+          ],
+        );
+      }
+
+      it("Error - create auction with an NFT not in allowlist", async () => {
+        return expect(createArtworkAuction()).to.eventually.be.rejectedWith(
+          "caused by account: nft_allowlist_states. Error Code: AccountNotInitialized");
+      });
+
+      it("Error - allowlists an NFT mint with verified collection", async () => {
+        return expect(devEnv.justiesClient("authority").addNftMintToAllowlist(
+          devEnv.getNft("Gods #4").mint.address
+        )).to.eventually.be.rejectedWith("Error Code: InvalidNftMetadata");
+      });
+
+      it("Creates auction with an allowlisted NFT mint", async () => {
+        const authorityClient = devEnv.justiesClient("authority");
+        await authorityClient.addNftMintToAllowlist(nftMintAddress);
+        expect(await generatorToList(
+          authorityClient.getNftMintAllowlistStates())).to.containSubset([
+          {tokenMintAddress: nftMintAddress, allowed: true},
+        ]);
+
+        await createArtworkAuction();
+        const auction = await justiesClient.fetchLatestAuction();
+        expect(auction.nftAllowlistType).to.eql({nftMint: {}});
+        expect(auction.nftAllowlistKey).to.eql(nftMintAddress);
+      });
+
+      it("Migrates an auction of the current layout as a no-op", async () => {
+        const auctionId = await justiesClient.latestAuctionId();
+        const auction = await justiesClient.fetchAuction(auctionId);
+        await devEnv.justiesClient("bidder1").migrateAuction(
+          auctionId, nftMintAddress);
+        expect(await justiesClient.fetchAuction(auctionId)).to.deep.equal(
+          auction);
+      });
+
+      it("Error - makes bid when the NFT mint is suspended", async () => {
+        const authorityClient = devEnv.justiesClient("authority");
+        const auctionId = await justiesClient.latestAuctionId();
+        await authorityClient.suspendAllowlistEntry(
+          {nftMint: {}}, nftMintAddress, null);
+        await expect(devEnv.justiesClient("bidder2").makeBid(
+          auctionId,
+          toLamport(50),
+          toLamport(50),
+          null,
+          devEnv.getTokenMintAddress("USDT")
+        )).to.eventually.be
+          .rejectedWith("Error Code: NftCollectionNotInAllowlist");
+
+        await authorityClient.reinstateAllowlistEntry(
+          {nftMint: {}}, nftMintAddress);
+        await devEnv.justiesClient("bidder2").makeBid(
+          auctionId,
+          toLamport(50),
+          toLamport(50),
+          null,
+          devEnv.getTokenMintAddress("USDT")
+        );
+        const auction = await justiesClient.fetchAuction(auctionId);
+        expect(auction.topBidder).to.eql(
+          devEnv.justiesClient("bidder2").providerAddress);
+      });
+    });
+
+    describe("Auction of an NFT allowlisted by creator", () => {
+      let justiesClient: JustiesProgramClient;
+      let nftMintAddress: PublicKey;
+
+      before(async () => {
+        justiesClient = devEnv.justiesClient("auctionCreator");
+        nftMintAddress = devEnv.getNft("Legacy #1").mint.address;
+        await devEnv.transferNft("auctionCreator", "Legacy #1");
+      });
+
+      async function createLegacyAuction() {
+        await justiesClient.createAuction(
+          nftMintAddress,
+          devEnv.getTokenMintAddress("USDT"),
+          24 * 3600,
+          toLamport(50),
+          [],
+          [
+            {
+              revenueReceiver: justiesClient.providerAddress,
+              shareBps: 10000,
+            },
+          ],
+        );
+      }
+
+      it("Error - create auction before the creator is allowed", async () => {
+        return expect(createLegacyAuction()).to.eventually.be.rejectedWith(
+          "caused by account: nft_allowlist_states. Error Code: AccountNotInitialized");
+      });
+
+      it("Creates auction with an NFT of an allowlisted creator", async () => {
+        const authorityClient = devEnv.justiesClient("authority");
+        const creatorAddress = devEnv.getNftCreator("Legacy").publicKey;
+        await expect(devEnv.justiesClient("bidder1").addNftCreatorToAllowlist(
+          creatorAddress
+        )).to.eventually.be.rejectedWith("Error Code: MissingAdminRole");
+        await authorityClient.addNftCreatorToAllowlist(creatorAddress);
+        expect(await generatorToList(
+          authorityClient.getNftCreatorAllowlistStates())).to.containSubset([
+          {tokenMintAddress: creatorAddress, allowed: true},
+        ]);
+
+        // The minimum outbid increment is captured by the auction, so that
+        // resetting the listing limits doesn't affect it.
+        const usdtMintAddress = devEnv.getTokenMintAddress("USDT");
+        const listingLimits =
+          JustiesProgramClient.getDefaultCurrencyListingLimits();
+        listingLimits.minOutbidIncrement = new BN(toLamport(10));
+        await authorityClient.setCurrencyListingLimits(
+          usdtMintAddress, listingLimits);
+        await createLegacyAuction();
+        await authorityClient.setCurrencyListingLimits(
+          usdtMintAddress,
+          JustiesProgramClient.getDefaultCurrencyListingLimits(),
+        );
+        const auction = await justiesClient.fetchLatestAuction();
+        expect(auction.nftAllowlistType).to.eql({nftCreator: {}});
+        expect(auction.nftAllowlistKey).to.eql(creatorAddress);
+      });
+
+      it("Error - outbids below the minimum outbid increment", async () => {
+        const auctionId = await justiesClient.latestAuctionId();
+        const usdtMintAddress = devEnv.getTokenMintAddress("USDT");
+        await devEnv.justiesClient("bidder1").makeBid(
+          auctionId, toLamport(50), toLamport(50), null, usdtMintAddress);
+        // 55 meets the minimum outbid rate (5%) but not the minimum outbid
+        // increment (10).
+        await expect(devEnv.justiesClient("bidder2").makeBid(
+          auctionId, toLamport(55), toLamport(55), null, usdtMintAddress
+        )).to.eventually.be.rejectedWith("Error Code: NotMetMinOutbidIncrement");
+        await devEnv.justiesClient("bidder2").makeBid(
+          auctionId, toLamport(55), toLamport(60), null, usdtMintAddress);
+        const auction = await justiesClient.fetchAuction(auctionId);
+        expect(auction.topBid.toNumber()).to.eq(toLamport(60));
+      });
+    });
   });
 
   describe("Raffle", () => {
@@ -1817,6 +3072,33 @@ describe("Justies Test", () => {
           .rejectedWith("Error Code: InvalidRaffleTicketSupply");
       });
 
+      it("Error - create raffle of a disabled feature", async () => {
+        const authorityClient = devEnv.justiesClient("authority");
+        await expect(devEnv.justiesClient("raffleCreator").setFeatureEnabled(
+          {multiNftRaffles: {}}, false)).to.eventually.be
+          .rejectedWith("Error Code: MissingAdminRole");
+
+        await authorityClient.setFeatureEnabled({multiNftRaffles: {}}, false);
+        const entries = await fetchLatestAuditEntries(authorityClient, 1);
+        expect(entries[0]).to.containSubset({
+          change: {
+            field: {features: {}},
+            oldValue: {u64: {"0": new BN(0b11111)}},
+            newValue: {u64: {"0": new BN(0b11011)}},
+          },
+        });
+        // The error names the disabled feature.
+        await expect(createTestRaffle(
+          "Gift Card #2",
+          undefined,
+          undefined,
+          undefined,
+          2
+        )).to.eventually.be.rejectedWith(
+          "Error Message: the feature is disabled: MultiNftRaffles");
+        return authorityClient.setFeatureEnabled({multiNftRaffles: {}}, true);
+      });
+
       it("Error - create raffle with invalid num raffled nfts", async () => {
         // Error case: num raffled nfts is 0;
         expect(createTestRaffle(
@@ -1836,6 +3118,65 @@ describe("Justies Test", () => {
         )).to.eventually.be.rejectedWith("Error Code: InvalidNumRaffledNfts");
 
       });
+      it("Error - create raffle out of the currency listing limits", async () => {
+        const authorityClient = devEnv.justiesClient("authority");
+        const usdtMintAddress = devEnv.getTokenMintAddress("USDT");
+        const listingLimits =
+          JustiesProgramClient.getDefaultCurrencyListingLimits();
+        listingLimits.minTicketPrice = new BN(toLamport(1));
+        listingLimits.maxTicketPrice = new BN(toLamport(2));
+        await authorityClient.setCurrencyListingLimits(
+          usdtMintAddress, listingLimits);
+
+        // The default ticket price (5 USDT) exceeds the maximum.
+        await expect(createTestRaffle()).to.eventually.be
+          .rejectedWith("Error Code: InvalidTicketPrice");
+        await expect(createTestRaffle(
+          undefined,
+          undefined,
+          undefined,
+          undefined,
+          undefined,
+          undefined,
+          undefined,
+          toLamport(0.5),
+        )).to.eventually.be.rejectedWith("Error Code: InvalidTicketPrice");
+
+        await authorityClient.setCurrencyListingLimits(
+          usdtMintAddress,
+          JustiesProgramClient.getDefaultCurrencyListingLimits(),
+        );
+      });
+      it("Error - create raffle out of the collection's listing rules", async () => {
+        const authorityClient = devEnv.justiesClient("authority");
+        const giftCardCollectionAddress =
+          devEnv.getNftCollection("Gift Card").address;
+        const listingRules =
+          JustiesProgramClient.getDefaultListingRuleOverrides();
+        listingRules.maxRaffledNfts = 0;
+        await expect(authorityClient.setNftCollectionListingRules(
+          giftCardCollectionAddress, listingRules
+        )).to.eventually.be
+          .rejectedWith("Error Code: InvalidCollectionListingRules");
+
+        // The default raffle (2 NFTs for 24 hours) exceeds the overrides.
+        listingRules.maxRaffledNfts = 1;
+        await authorityClient.setNftCollectionListingRules(
+          giftCardCollectionAddress, listingRules);
+        await expect(createTestRaffle()).to.eventually.be
+          .rejectedWith("Error Code: InvalidNumRaffledNfts");
+        listingRules.maxRaffledNfts = null;
+        listingRules.minRaffleDuration = new BN(48 * 3600);
+        await authorityClient.setNftCollectionListingRules(
+          giftCardCollectionAddress, listingRules);
+        await expect(createTestRaffle()).to.eventually.be
+          .rejectedWith("Error Code: InvalidRaffleDuration");
+
+        await authorityClient.setNftCollectionListingRules(
+          giftCardCollectionAddress,
+          JustiesProgramClient.getDefaultListingRuleOverrides(),
+        );
+      });
       it("Error - create raffle with unsupported currency token", async () => {
         // Error case: creates raffle with a currency token that is not in the
         // allowlist.
@@ -1941,17 +3282,15 @@ describe("Justies Test", () => {
       );
       it("Error - create raffle when raffle creation is disabled", async () => {
         const authority = devEnv.justiesClient("authority");
-        const originalGlobalStates = await authority.fetchGlobalStates();
-
-        // Disables raffle creation.
-        const updateConfigsInput = JustiesProgramClient.getDefaultUpdateConfigsInput();
-        updateConfigsInput.raffleCreationEnabled = false;
-        await authority.updateConfigs(updateConfigsInput);
+        await authority.setFeatureEnabled({raffleCreation: {}}, false);
+        // The legacy bool is kept in sync for the older clients.
+        expect((await authority.fetchGlobalStates()).raffleCreationEnabled).to
+          .be.false;
 
         await expect(createTestRaffle()).to.eventually.be.rejectedWith(
-          "Error Code: RaffleCreationDisabled",
+          "Error Message: the feature is disabled: RaffleCreation",
         );
-        await restoreConfigs(authority, originalGlobalStates);
+        await authority.setFeatureEnabled({raffleCreation: {}}, true);
       });
     });
     describe("Non-exclusive raffle use cases", () => {
@@ -2070,6 +3409,20 @@ describe("Justies Test", () => {
           raffleAddress = ticketBuyer1Client.findPdaRaffle(raffleId);
         });
 
+        it("Error - buys tickets while ticket sales are paused", async () => {
+          const authorityClient = devEnv.justiesClient("authority");
+          const updateConfigsInput =
+            JustiesProgramClient.getDefaultUpdateConfigsInput();
+          updateConfigsInput.ticketSalesEnabled = false;
+          await authorityClient.updateConfigs(updateConfigsInput);
+
+          await expect(ticketBuyer1Client.buyRaffleTickets(raffleId, 1, null))
+            .to.eventually.be.rejectedWith("Error Code: TicketSalesDisabled");
+
+          updateConfigsInput.ticketSalesEnabled = true;
+          await authorityClient.updateConfigs(updateConfigsInput);
+        });
+
         it("Error - buys 0 tickets", async () => {
           return expect(ticketBuyer1Client.buyRaffleTickets(raffleId, 0, null))
             .to.eventually
@@ -2085,6 +3438,21 @@ describe("Justies Test", () => {
             .rejectedWith("Error Code: InvalidRaffleTicketNumber");
         });
 
+        it(
+          "Error - buys tickets when the nft collection is suspended",
+          async () => {
+            const authorityClient = devEnv.justiesClient("authority");
+            const raffle = await ticketBuyer1Client.fetchRaffle(raffleId);
+            await authorityClient.suspendAllowlistEntry(
+              {nftCollection: {}}, raffle.nftAllowlistKey, null);
+            await expect(ticketBuyer1Client.buyRaffleTickets(raffleId, 1, null))
+              .to.eventually.be
+              .rejectedWith("Error Code: NftCollectionNotInAllowlist");
+            await authorityClient.reinstateAllowlistEntry(
+              {nftCollection: {}}, raffle.nftAllowlistKey);
+          }
+        );
+
         it("Error - buys tickets with insufficient token balance", async () => {
           const ticketBuyer3Client = devEnv.justiesClient("ticketBuyer3");
           return expect(ticketBuyer3Client.buyRaffleTickets(
@@ -2267,10 +3635,10 @@ describe("Justies Test", () => {
             .to.eventually.be.rejectedWith("Error Code: RaffleEnded");
         });
 
-        it("Error - non-authority user sets winners.", async () => {
+        it("Error - non-raffle-operator user sets winners.", async () => {
           return expect(ticketBuyer1Client.setRaffleWinners(raffleId, [
             0, 1,
-          ])).to.eventually.be.rejectedWith("Error Code: NotTheAuthority");
+          ])).to.eventually.be.rejectedWith("Error Code: MissingAdminRole");
         });
 
         it("Sets winners", async () => {
@@ -2295,6 +3663,20 @@ describe("Justies Test", () => {
             .eventually.be.rejectedWith("Error Code: NotRaffleCreator");
         });
 
+        it("Error - claims rewards while claims are paused", async () => {
+          const authorityClient = devEnv.justiesClient("authority");
+          const updateConfigsInput =
+            JustiesProgramClient.getDefaultUpdateConfigsInput();
+          updateConfigsInput.claimsEnabled = false;
+          await authorityClient.updateConfigs(updateConfigsInput);
+
+          await expect(ticketBuyer1Client.claimRaffleReward(raffleId)).to
+            .eventually.be.rejectedWith("Error Code: ClaimsDisabled");
+
+          updateConfigsInput.claimsEnabled = true;
+          await authorityClient.updateConfigs(updateConfigsInput);
+        });
+
         it("ticketBuyer1 claims raffle rewards", async () => {
           let raffle = await raffleCreatorClient.fetchRaffle(raffleId);
           const raffleNftEscrowAddress = raffleCreatorClient.findPdaRaffleRewardsEscrow(
@@ -2363,8 +3745,53 @@ describe("Justies Test", () => {
           }
         );
 
+        it("Error - claims revenue while claims are paused", async () => {
+          const authorityClient = devEnv.justiesClient("authority");
+          const updateConfigsInput =
+            JustiesProgramClient.getDefaultUpdateConfigsInput();
+          updateConfigsInput.claimsEnabled = false;
+          await authorityClient.updateConfigs(updateConfigsInput);
+
+          await expect(raffleCreatorClient.claimRaffleRevenue(raffleId)).to
+            .eventually.be.rejectedWith("Error Code: ClaimsDisabled");
+
+          updateConfigsInput.claimsEnabled = true;
+          await authorityClient.updateConfigs(updateConfigsInput);
+        });
+
         it("Claim raffle revenue", async () => {
+          const authorityClient = devEnv.justiesClient("authority");
+          const usdtMintAddress = devEnv.getTokenMintAddress("USDT");
           const globalStates = await raffleCreatorClient.fetchGlobalStates();
+          // The currency's market fee rate overrides the global one, but
+          // can't exceed it.
+          await expect(raffleCreatorClient.setCurrencyMarketFeeRate(
+            usdtMintAddress, 100
+          )).to.eventually.be.rejectedWith("Error Code: MissingAdminRole");
+          await expect(authorityClient.setCurrencyMarketFeeRate(
+            usdtMintAddress, globalStates.marketFeeRateBps + 1
+          )).to.eventually.be
+            .rejectedWith("Error Code: InvalidCurrencyMarketFeeRate");
+          const currencyMarketFeeRateBps =
+            Math.trunc(globalStates.marketFeeRateBps / 2);
+          await authorityClient.setCurrencyMarketFeeRate(
+            usdtMintAddress, currencyMarketFeeRateBps);
+          const [auditEntry] = await fetchLatestAuditEntries(authorityClient, 1);
+          expect(auditEntry.change).to.containSubset({
+            field: {currencyMarketFeeRateBps: {}},
+            newValue: {u16: {"0": currencyMarketFeeRateBps}},
+            target: usdtMintAddress,
+          });
+          // The collection's market fee rate takes precedence over the
+          // currency's one.
+          const giftCardCollectionAddress =
+            devEnv.getNftCollection("Gift Card").address;
+          const listingRules =
+            JustiesProgramClient.getDefaultListingRuleOverrides();
+          listingRules.marketFeeRateBps =
+            Math.trunc(globalStates.marketFeeRateBps / 4);
+          await authorityClient.setNftCollectionListingRules(
+            giftCardCollectionAddress, listingRules);
           const raffleRevenueEscrowAddress =
             raffleCreatorClient.findPdaRaffleRevenueEscrow(
               raffleAddress);
@@ -2383,13 +3810,20 @@ describe("Justies Test", () => {
           const totalRevenue = Number((await devEnv.getSplTokenAccount(
             raffleRevenueEscrowAddress)).amount);
           const expectedFee = Math.trunc(totalRevenue *
-            globalStates.marketFeeRateBps / 10000);
+            listingRules.marketFeeRateBps / 10000);
           const expectedSharedRevenue = Math.trunc((totalRevenue -
             expectedFee) / 10);
           const expectedCreatorRevenue = Math.trunc((totalRevenue -
             expectedFee) * 9 / 10);
+          const previousStats = await fetchCurrencyStatsCounters(
+            raffleCreatorClient, usdtMintAddress);
 
           await raffleCreatorClient.claimRaffleRevenue(raffleId);
+          await authorityClient.setCurrencyMarketFeeRate(usdtMintAddress, null);
+          await authorityClient.setNftCollectionListingRules(
+            giftCardCollectionAddress,
+            JustiesProgramClient.getDefaultListingRuleOverrides(),
+          );
 
           const currentFee = await devEnv.ataTokenAmount(
             feeTreasuryAddress,
@@ -2409,6 +3843,15 @@ describe("Justies Test", () => {
             .eq(expectedSharedRevenue);
           expect(currentCreatorTokenBalance - previousCreatorTokenBalance).to
             .eq(expectedCreatorRevenue);
+          // Expects that the settlement is recorded in the fee ledger of the
+          // currency.
+          expect(await fetchCurrencyStatsCounters(
+            raffleCreatorClient, usdtMintAddress)).to.deep.eq({
+            totalFees: previousStats.totalFees + expectedFee,
+            totalVolume: previousStats.totalVolume + totalRevenue,
+            numSettledAuctions: previousStats.numSettledAuctions,
+            numSettledRaffles: previousStats.numSettledRaffles + 1,
+          });
           // Expects the revenue escrow account to be closed.
           expect(await devEnv.connection.getAccountInfo(
             raffleRevenueEscrowAddress)).to.be.null;
@@ -2763,6 +4206,30 @@ describe("Justies Test", () => {
         expect(await devEnv.connection.getAccountInfo(
           revenueEscrowAddress)).to.be.null;
       });
+      it("Migrates a raffle of the older layout", async () => {
+        const raffle = await raffleCreatorClient.fetchRaffle(raffleId);
+        const dataLength = (await devEnv.connection.getAccountInfo(
+          raffleAddress)).data.length;
+        // Rewrites the raffle in the layout before the allowlist entries,
+        // without the allowlist type & key, the listing fee deposit and the
+        // live listing flag.
+        await devEnv.justiesClient("authority").downgradeRaffle(raffleId);
+        expect((await devEnv.connection.getAccountInfo(
+          raffleAddress)).data.length).to.eq(dataLength - 42);
+
+        await ticketBuyer1Client.migrateRaffle(
+          raffleId, devEnv.getNft("Gift Card #2").address);
+        const migratedRaffle = await raffleCreatorClient.fetchRaffle(raffleId);
+        expect(migratedRaffle).to.deep.equal(raffle);
+        // Expects the defaults of the new fields.
+        expect(migratedRaffle.nftAllowlistType).to.eql({nftCollection: {}});
+        expect(migratedRaffle.nftAllowlistKey).to.eql(
+          devEnv.getNftCollection("Gift Card").address);
+        expect(migratedRaffle.listingFeeDeposit.toNumber()).to.eq(0);
+        expect(migratedRaffle.countedAsLive).to.be.false;
+        expect((await devEnv.connection.getAccountInfo(
+          raffleAddress)).data.length).to.eq(dataLength);
+      });
       it("Error - buys ticket from a cancelled raffle", async () => {
         return expect(ticketBuyer1Client.buyRaffleTickets(raffleId, 1, null)).to
           .eventually.be.rejectedWith(
@@ -2781,6 +4248,704 @@ describe("Justies Test", () => {
         return expect(raffleCreatorClient.cancelRaffle(raffleId)).to.eventually
           .be.rejectedWith("Error Code: RaffleNotCancelable");
       });
+      it("Error - refunds tickets before force-cancellation", async () => {
+        return expect(ticketBuyer1Client.refundRaffleTickets(raffleId)).to
+          .eventually.be.rejectedWith("Error Code: RaffleNotForceCancelled");
+      });
+      it("Error - non-authority force-cancels the raffle", async () => {
+        return expect(raffleCreatorClient.forceCancelRaffle(
+          raffleId,
+          raffleCreatorClient.providerAddress,
+          null
+        )).to.eventually.be.rejectedWith("Error Code: NotTheAuthority");
+      });
+      it("Force-cancels the raffle back to the creator", async () => {
+        const authorityClient = devEnv.justiesClient("authority");
+        await authorityClient.forceCancelRaffle(
+          raffleId,
+          raffleCreatorClient.providerAddress,
+          null
+        );
+        const raffle = await authorityClient.fetchRaffle(raffleId);
+        expect(raffle.status).to.eql({forceCancelled: {}});
+        // Expects that the raffled NFTs has been returned.
+        const creatorNftToken = await devEnv.getSplTokenAccount(
+          devEnv.getNftAta("raffleCreator", "Gift Card #2"));
+        expect(Number(creatorNftToken.amount)).to.eq(30);
+        // Expects that the rewards escrow has been closed while the revenue
+        // escrow is kept for the refunds.
+        raffleAddress = authorityClient.findPdaRaffle(raffleId);
+        expect(await devEnv.connection.getAccountInfo(
+          authorityClient.findPdaRaffleRewardsEscrow(raffleAddress)))
+          .to.be.null;
+        expect(await devEnv.connection.getAccountInfo(
+          authorityClient.findPdaRaffleRevenueEscrow(raffleAddress)))
+          .not.to.be.null;
+      });
+      it("Refunds raffle tickets", async () => {
+        const raffle = await ticketBuyer1Client.fetchRaffle(raffleId);
+        const previousTokenAmount = Number((await devEnv.getSplTokenAccount(
+          devEnv.getTokenAta("ticketBuyer1", "USDT")
+        )).amount);
+        // The refunds stay open while ticket sales & claims are paused.
+        const authorityClient = devEnv.justiesClient("authority");
+        const updateConfigsInput =
+          JustiesProgramClient.getDefaultUpdateConfigsInput();
+        updateConfigsInput.ticketSalesEnabled = false;
+        updateConfigsInput.claimsEnabled = false;
+        await authorityClient.updateConfigs(updateConfigsInput);
+        await ticketBuyer1Client.refundRaffleTickets(raffleId);
+        updateConfigsInput.ticketSalesEnabled = true;
+        updateConfigsInput.claimsEnabled = true;
+        await authorityClient.updateConfigs(updateConfigsInput);
+        const currentTokenAmount = Number((await devEnv.getSplTokenAccount(
+          devEnv.getTokenAta("ticketBuyer1", "USDT")
+        )).amount);
+        expect(currentTokenAmount).to.eq(
+          previousTokenAmount + raffle.ticketPrice.toNumber());
+        // Expects that the revenue escrow has been closed after the last
+        // refund.
+        expect(await devEnv.connection.getAccountInfo(
+          ticketBuyer1Client.findPdaRaffleRevenueEscrow(raffleAddress)))
+          .to.be.null;
+      });
+      it("Error - refunds raffle tickets multiple times", async () => {
+        return expect(ticketBuyer1Client.refundRaffleTickets(raffleId)).to
+          .eventually.be.rejectedWith("Error Code: NoRaffleTicketsToRefund");
+      });
+    });
+    describe("Wallet blocklist", () => {
+      let blockedClient: JustiesProgramClient;
+      let raffleId: BN;
+      before(async () => {
+        blockedClient = devEnv.justiesClient("ticketBuyer2");
+        await createTestRaffle(
+          "Gift Card #2",
+          undefined,
+          undefined,
+          undefined,
+          3,
+        );
+        raffleId = await raffleCreatorClient.latestRaffleId();
+      });
+      it("Error - non-authority blocks a wallet", async () => {
+        return expect(raffleCreatorClient.blockWallet(
+          blockedClient.providerAddress,
+          null
+        )).to.eventually.be.rejectedWith("Error Code: NotTheAuthority");
+      });
+      it("Blocks a wallet", async () => {
+        const authorityClient = devEnv.justiesClient("authority");
+        await authorityClient.blockWallet(blockedClient.providerAddress, 5);
+        expect(await devEnv.connection.getAccountInfo(
+          authorityClient.findPdaWalletBlocklistStates(
+            blockedClient.providerAddress))).not.to.be.null;
+        const [auditEntry] = await fetchLatestAuditEntries(authorityClient, 1);
+        expect(auditEntry.change).to.containSubset({
+          field: {walletBlocklist: {}},
+          target: blockedClient.providerAddress,
+          newValue: {bool: {"0": true}},
+          reasonCode: 5,
+        });
+      });
+      it("Error - blocked wallet buys tickets", async () => {
+        return expect(blockedClient.buyRaffleTickets(raffleId, 1, null)).to
+          .eventually.be.rejectedWith("Error Code: WalletBlocked");
+      });
+      it("Error - blocked wallet creates raffle", async () => {
+        return expect(blockedClient.createRaffle(
+          devEnv.getNft("Gift Card #2").address,
+          devEnv.getTokenMintAddress("USDT"),
+          24 * 3600,
+          1000,
+          toLamport(5),
+          1,
+          [],
+          [
+            {
+              revenueReceiver: blockedClient.providerAddress,
+              shareBps: 10000,
+            },
+          ],
+        )).to.eventually.be.rejectedWith("Error Code: WalletBlocked");
+      });
+      it("Error - blocked wallet as a revenue recipient", async () => {
+        return expect(createTestRaffle(
+          "Gift Card #2",
+          undefined,
+          undefined,
+          undefined,
+          1,
+          [
+            {
+              revenueReceiver: blockedClient.providerAddress,
+              shareBps: 1000,
+            },
+            {
+              revenueReceiver: raffleCreatorClient.providerAddress,
+              shareBps: 9000,
+            },
+          ],
+        )).to.eventually.be.rejectedWith("Error Code: WalletBlocked");
+      });
+      it("Unblocks a wallet", async () => {
+        const authorityClient = devEnv.justiesClient("authority");
+        await authorityClient.unblockWallet(blockedClient.providerAddress, null);
+        expect(await devEnv.connection.getAccountInfo(
+          authorityClient.findPdaWalletBlocklistStates(
+            blockedClient.providerAddress))).to.be.null;
+        await blockedClient.buyRaffleTickets(raffleId, 1, null);
+        const ticketPosition = await blockedClient.fetchRaffleTicketPosition(
+          raffleCreatorClient.findPdaRaffle(raffleId),
+          blockedClient.providerAddress
+        );
+        expect(ticketPosition.totalNumTickets).to.eq(1);
+      });
+      it("Ignores a funded blocklist states address", async () => {
+        const signature = await devEnv.connection.requestAirdrop(
+          blockedClient.findPdaWalletBlocklistStates(
+            blockedClient.providerAddress),
+          toLamport(1),
+        );
+        await devEnv.connection.confirmTransaction(signature);
+        await blockedClient.buyRaffleTickets(raffleId, 1, null);
+        const ticketPosition = await blockedClient.fetchRaffleTicketPosition(
+          raffleCreatorClient.findPdaRaffle(raffleId),
+          blockedClient.providerAddress
+        );
+        expect(ticketPosition.totalNumTickets).to.eq(2);
+      });
+    });
+    describe("Curated creators", () => {
+      let authorityClient: JustiesProgramClient;
+      before(async () => {
+        authorityClient = devEnv.justiesClient("authority");
+        const input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+        input.curatedCreatorsOnly = true;
+        await authorityClient.updateConfigs(input);
+      });
+      after(async () => {
+        await authorityClient.setCuratedCreator(
+          raffleCreatorClient.providerAddress,
+          false,
+          null
+        );
+        const input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+        input.curatedCreatorsOnly = false;
+        await authorityClient.updateConfigs(input);
+      });
+      it("Error - non-authority curates a creator", async () => {
+        return expect(raffleCreatorClient.setCuratedCreator(
+          raffleCreatorClient.providerAddress,
+          true,
+          null
+        )).to.eventually.be.rejectedWith("Error Code: NotTheAuthority");
+      });
+      it("Error - uncurated creator creates raffle", async () => {
+        return expect(createTestRaffle("Gift Card #2", undefined, undefined,
+          undefined, 1)).to.eventually.be.rejectedWith(
+          "Error Code: CreatorNotCurated");
+      });
+      it("Curated creator creates raffle", async () => {
+        await authorityClient.setCuratedCreator(
+          raffleCreatorClient.providerAddress,
+          true,
+          null
+        );
+        const [auditEntry] = await fetchLatestAuditEntries(authorityClient, 1);
+        expect(auditEntry.change).to.containSubset({
+          field: {curatedCreator: {}},
+          target: raffleCreatorClient.providerAddress,
+          newValue: {bool: {"0": true}},
+        });
+        const creatorStates = await raffleCreatorClient.fetchCreatorStates(
+          raffleCreatorClient.providerAddress);
+        await createTestRaffle("Gift Card #2", undefined, undefined, undefined,
+          1);
+        const newCreatorStates = await raffleCreatorClient.fetchCreatorStates(
+          raffleCreatorClient.providerAddress);
+        expect(newCreatorStates.numLiveRaffles).to.eq(
+          creatorStates.numLiveRaffles + 1);
+      });
+      it("Error - creates raffle over the cap on live listings", async () => {
+        const creatorStates = await raffleCreatorClient.fetchCreatorStates(
+          raffleCreatorClient.providerAddress);
+        await authorityClient.setCuratedCreator(
+          raffleCreatorClient.providerAddress,
+          true,
+          creatorStates.numLiveAuctions + creatorStates.numLiveRaffles
+        );
+        await expect(createTestRaffle("Gift Card #2", undefined, undefined,
+          undefined, 1)).to.eventually.be.rejectedWith(
+          "Error Code: TooManyLiveListings");
+
+        // Cancelling a live raffle makes room for a new one.
+        await raffleCreatorClient.cancelRaffle(
+          await raffleCreatorClient.latestRaffleId());
+        const newCreatorStates = await raffleCreatorClient.fetchCreatorStates(
+          raffleCreatorClient.providerAddress);
+        expect(newCreatorStates.numLiveRaffles).to.eq(
+          creatorStates.numLiveRaffles - 1);
+        await createTestRaffle("Gift Card #2", undefined, undefined, undefined,
+          1);
+      });
+      it("Error - creates raffle over the global cap on live listings",
+        async () => {
+          const creatorStates = await raffleCreatorClient.fetchCreatorStates(
+            raffleCreatorClient.providerAddress);
+          const numLiveListings =
+            creatorStates.numLiveAuctions + creatorStates.numLiveRaffles;
+          await authorityClient.setCuratedCreator(
+            raffleCreatorClient.providerAddress,
+            true,
+            null
+          );
+          const input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+          input.maxLiveListingsPerCreator = numLiveListings;
+          await authorityClient.updateConfigs(input);
+          await expect(createTestRaffle("Gift Card #2", undefined, undefined,
+            undefined, 1)).to.eventually.be.rejectedWith(
+            "Error Code: TooManyLiveListings");
+
+          // The creator's own cap overrides the global one.
+          await authorityClient.setCuratedCreator(
+            raffleCreatorClient.providerAddress,
+            true,
+            numLiveListings + 1
+          );
+          await createTestRaffle("Gift Card #2", undefined, undefined,
+            undefined, 1);
+          input.maxLiveListingsPerCreator = 0;
+          await authorityClient.updateConfigs(input);
+        });
+    });
+    describe("Fee split", () => {
+      const treasury1Address = devEnv.generateKeypair().publicKey;
+      const treasury2Address = devEnv.generateKeypair().publicKey;
+      let authorityClient: JustiesProgramClient;
+
+      function feeSplitInput(feeShares: FeeShareConfig[]) {
+        const input = JustiesProgramClient.getDefaultTimelockedConfigsInput();
+        input.feeShares = feeShares;
+        return input;
+      }
+
+      before(async () => {
+        authorityClient = devEnv.justiesClient("authority");
+        await devEnv.createTokenAta(treasury1Address, "USDT");
+        await devEnv.createTokenAta(treasury2Address, "USDT");
+      });
+
+      after(async () => {
+        await applyTimelockedConfigs(authorityClient, feeSplitInput([]));
+      });
+
+      it("Error - set invalid fee split", async () => {
+        // The shares don't add up to 10000 bps.
+        await expect(authorityClient.queueConfigChanges(feeSplitInput([
+          {treasury: treasury1Address, shareBps: 5000},
+          {treasury: treasury2Address, shareBps: 4000},
+        ]))).to.eventually.be.rejectedWith("Error Code: InvalidFeeSplit");
+        // Duplicated treasuries.
+        await expect(authorityClient.queueConfigChanges(feeSplitInput([
+          {treasury: treasury1Address, shareBps: 5000},
+          {treasury: treasury1Address, shareBps: 5000},
+        ]))).to.eventually.be.rejectedWith("Error Code: InvalidFeeSplit");
+        return expect(raffleCreatorClient.queueConfigChanges(feeSplitInput([
+          {treasury: treasury1Address, shareBps: 10000},
+        ]))).to.eventually.be.rejectedWith("Error Code: MissingAdminRole");
+      });
+
+      it("Splits the market fee across the treasuries", async () => {
+        const input = feeSplitInput([
+          {treasury: treasury1Address, shareBps: 7000},
+          {treasury: treasury2Address, shareBps: 3000},
+        ]);
+        // The split only applies once the queued changes are executed.
+        await authorityClient.queueConfigChanges(input);
+        let entries = await fetchLatestAuditEntries(authorityClient, 2);
+        expect(entries[0].change).to.containSubset({
+          field: {pendingFeeShareBps: {}},
+          target: treasury1Address,
+          oldValue: {none: {}},
+          newValue: {u16: {"0": 7000}},
+        });
+        expect(entries[1].change).to.containSubset({
+          field: {pendingFeeShareBps: {}},
+          target: treasury2Address,
+          oldValue: {none: {}},
+          newValue: {u16: {"0": 3000}},
+        });
+        expect((await authorityClient.fetchGlobalStates()).feeShares).to
+          .be.empty;
+        await authorityClient.cancelConfigChanges();
+
+        await applyTimelockedConfigs(authorityClient, input);
+        entries = await fetchLatestAuditEntries(authorityClient, 4);
+        expect(entries[0].change).to.containSubset({
+          field: {feeShareBps: {}},
+          target: treasury1Address,
+          oldValue: {u16: {"0": 0}},
+          newValue: {u16: {"0": 7000}},
+        });
+        expect(entries[1].change).to.containSubset({
+          field: {feeShareBps: {}},
+          target: treasury2Address,
+          oldValue: {u16: {"0": 0}},
+          newValue: {u16: {"0": 3000}},
+        });
+
+        await createTestRaffle("Gift Card #2", undefined, undefined,
+          undefined, 1);
+        const raffle = await raffleCreatorClient.fetchLatestRaffle();
+        await ticketBuyer1Client.buyRaffleTickets(raffle.id, 10, null);
+        await authorityClient.setMockTimestamp(raffle.expiredTimestamp.addn(10));
+        await authorityClient.setRaffleWinners(raffle.id, [0]);
+        const globalStates = await authorityClient.fetchGlobalStates();
+        const totalRevenue = raffle.ticketPrice.toNumber() * 10;
+        const expectedFee = Math.trunc(totalRevenue *
+          globalStates.marketFeeRateBps / 10000);
+        const expectedTreasury1Fee = Math.trunc(expectedFee * 7000 / 10000);
+        const previousFee = await devEnv.ataTokenAmount(
+          globalStates.feeTreasuryAddress, "USDT");
+        const previousTreasury1Fee = await devEnv.ataTokenAmount(
+          treasury1Address, "USDT");
+        const previousTreasury2Fee = await devEnv.ataTokenAmount(
+          treasury2Address, "USDT");
+        const previousStats = await authorityClient.fetchCurrencyStats(
+          raffle.currencyTokenMintAddress);
+
+        await raffleCreatorClient.claimRaffleRevenue(raffle.id);
+
+        // The whole fee goes to the treasuries of the split, where the last one
+        // gets the remainder.
+        expect(await devEnv.ataTokenAmount(
+          globalStates.feeTreasuryAddress, "USDT")).to.eq(previousFee);
+        expect(await devEnv.ataTokenAmount(treasury1Address, "USDT")).to
+          .eq(previousTreasury1Fee + expectedTreasury1Fee);
+        expect(await devEnv.ataTokenAmount(treasury2Address, "USDT")).to
+          .eq(previousTreasury2Fee + expectedFee - expectedTreasury1Fee);
+        // The settlement is recorded in the fee ledger of the currency.
+        const currentStats = await authorityClient.fetchCurrencyStats(
+          raffle.currencyTokenMintAddress);
+        expect(currentStats.totalFees.toNumber()).to
+          .eq(previousStats.totalFees.toNumber() + expectedFee);
+        expect(currentStats.totalVolume.toNumber()).to
+          .eq(previousStats.totalVolume.toNumber() + totalRevenue);
+        expect(currentStats.numSettledRaffles.toNumber()).to
+          .eq(previousStats.numSettledRaffles.toNumber() + 1);
+        expect(currentStats.numSettledAuctions.toNumber()).to
+          .eq(previousStats.numSettledAuctions.toNumber());
+      });
+
+      it("Sends the share of a treasury without the token account to the fee " +
+        "treasury", async () => {
+        // The treasury has no associated token account of the currency.
+        const treasury3Address = devEnv.generateKeypair().publicKey;
+        await applyTimelockedConfigs(authorityClient, feeSplitInput([
+          {treasury: treasury1Address, shareBps: 5000},
+          {treasury: treasury3Address, shareBps: 5000},
+        ]));
+
+        await createTestRaffle("Gift Card #2", undefined, undefined,
+          undefined, 1);
+        const raffle = await raffleCreatorClient.fetchLatestRaffle();
+        await ticketBuyer1Client.buyRaffleTickets(raffle.id, 10, null);
+        await authorityClient.setMockTimestamp(raffle.expiredTimestamp.addn(10));
+        await authorityClient.setRaffleWinners(raffle.id, [0]);
+        const globalStates = await authorityClient.fetchGlobalStates();
+        const totalRevenue = raffle.ticketPrice.toNumber() * 10;
+        const expectedFee = Math.trunc(totalRevenue *
+          globalStates.marketFeeRateBps / 10000);
+        const expectedTreasury1Fee = Math.trunc(expectedFee * 5000 / 10000);
+        const previousFee = await devEnv.ataTokenAmount(
+          globalStates.feeTreasuryAddress, "USDT");
+        const previousTreasury1Fee = await devEnv.ataTokenAmount(
+          treasury1Address, "USDT");
+
+        await raffleCreatorClient.claimRaffleRevenue(raffle.id);
+
+        expect(await devEnv.ataTokenAmount(treasury1Address, "USDT")).to
+          .eq(previousTreasury1Fee + expectedTreasury1Fee);
+        expect(await devEnv.ataTokenAmount(
+          globalStates.feeTreasuryAddress, "USDT")).to
+          .eq(previousFee + expectedFee - expectedTreasury1Fee);
+      });
+    });
+    describe("Listing fee", () => {
+      const listingFee = toLamport(0.01);
+      let authorityClient: JustiesProgramClient;
+
+      before(async () => {
+        authorityClient = devEnv.justiesClient("authority");
+      });
+
+      after(async () => {
+        const timelockedInput =
+          JustiesProgramClient.getDefaultTimelockedConfigsInput();
+        timelockedInput.listingFee = new BN(0);
+        await applyTimelockedConfigs(authorityClient, timelockedInput);
+        const input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+        input.listingFeeRefundable = false;
+        await authorityClient.updateConfigs(input);
+      });
+
+      it("Error - changes the listing fee directly", async () => {
+        let input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+        input.listingFee = new BN(listingFee);
+        await expect(authorityClient.updateConfigs(input)).to.eventually.be
+          .rejectedWith("Error Code: TimelockRequired");
+        input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+        input.listingFeeTokenMint = devEnv.getTokenMintAddress("USDT");
+        return expect(authorityClient.updateConfigs(input)).to.eventually.be
+          .rejectedWith("Error Code: TimelockRequired");
+      });
+
+      it("Error - refundable listing fee in tokens", async () => {
+        const input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+        input.listingFeeRefundable = true;
+        await authorityClient.updateConfigs(input);
+        const timelockedInput =
+          JustiesProgramClient.getDefaultTimelockedConfigsInput();
+        timelockedInput.listingFee = new BN(100);
+        timelockedInput.listingFeeTokenMint =
+          devEnv.getTokenMintAddress("USDT");
+        await expect(authorityClient.queueConfigChanges(timelockedInput)).to
+          .eventually.be.rejectedWith("Error Code: InvalidListingFeeConfigs");
+        input.listingFeeRefundable = false;
+        await authorityClient.updateConfigs(input);
+      });
+
+      it("Charges the listing fee to the fee treasury", async () => {
+        const input = JustiesProgramClient.getDefaultTimelockedConfigsInput();
+        input.listingFee = new BN(listingFee);
+        await applyTimelockedConfigs(authorityClient, input);
+        // The change is followed by the clearing of the queued value.
+        const [auditEntry] = await fetchLatestAuditEntries(authorityClient, 2);
+        expect(auditEntry.change).to.containSubset({
+          field: {listingFee: {}},
+          newValue: {u64: {"0": new BN(listingFee)}},
+        });
+
+        const globalStates = await authorityClient.fetchGlobalStates();
+        const previousFeeBalance = await devEnv.connection.getBalance(
+          globalStates.feeTreasuryAddress);
+        await createTestRaffle("Gift Card #2", undefined, undefined,
+          undefined, 1);
+        const raffle = await raffleCreatorClient.fetchLatestRaffle();
+        expect(raffle.listingFeeDeposit.toNumber()).to.eq(0);
+        expect(await devEnv.connection.getBalance(
+          globalStates.feeTreasuryAddress)).to
+          .eq(previousFeeBalance + listingFee);
+      });
+
+      it("Keeps the refundable listing fee upon cancellation", async () => {
+        const input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+        input.listingFeeRefundable = true;
+        await authorityClient.updateConfigs(input);
+
+        await createTestRaffle("Gift Card #2", undefined, undefined,
+          undefined, 1);
+        const raffle = await raffleCreatorClient.fetchLatestRaffle();
+        expect(raffle.listingFeeDeposit.toNumber()).to.eq(listingFee);
+        const globalStates = await authorityClient.fetchGlobalStates();
+        const previousFeeBalance = await devEnv.connection.getBalance(
+          globalStates.feeTreasuryAddress);
+
+        await raffleCreatorClient.cancelRaffle(raffle.id);
+
+        // The deposit goes to the fee treasury instead of the creator.
+        const currentRaffle = await raffleCreatorClient.fetchRaffle(raffle.id);
+        expect(currentRaffle.listingFeeDeposit.toNumber()).to.eq(0);
+        expect(await devEnv.connection.getBalance(
+          globalStates.feeTreasuryAddress)).to
+          .eq(previousFeeBalance + listingFee);
+      });
+
+      it("Refunds the refundable listing fee upon settlement", async () => {
+        const input = JustiesProgramClient.getDefaultUpdateConfigsInput();
+        input.listingFeeRefundable = true;
+        await authorityClient.updateConfigs(input);
+
+        await createTestRaffle("Gift Card #2", undefined, undefined,
+          undefined, 1);
+        const raffle = await raffleCreatorClient.fetchLatestRaffle();
+        const raffleAddress = raffleCreatorClient.findPdaRaffle(raffle.id);
+        expect(raffle.listingFeeDeposit.toNumber()).to.eq(listingFee);
+        const previousRaffleBalance = await devEnv.connection.getBalance(
+          raffleAddress);
+
+        await ticketBuyer1Client.buyRaffleTickets(raffle.id, 10, null);
+        await authorityClient.setMockTimestamp(raffle.expiredTimestamp.addn(10));
+        await authorityClient.setRaffleWinners(raffle.id, [0]);
+        const globalStates = await authorityClient.fetchGlobalStates();
+        const previousFeeBalance = await devEnv.connection.getBalance(
+          globalStates.feeTreasuryAddress);
+        await raffleCreatorClient.claimRaffleRevenue(raffle.id);
+
+        // The deposit is moved from the raffle back to the creator, rather
+        // than to the fee treasury.
+        const currentRaffle = await raffleCreatorClient.fetchRaffle(raffle.id);
+        expect(currentRaffle.listingFeeDeposit.toNumber()).to.eq(0);
+        expect(await devEnv.connection.getBalance(raffleAddress)).to
+          .eq(previousRaffleBalance - listingFee);
+        expect(await devEnv.connection.getBalance(
+          globalStates.feeTreasuryAddress)).to.eq(previousFeeBalance);
+      });
+    });
+    describe("Fee tiers", () => {
+      let authorityClient: JustiesProgramClient;
+
+      before(async () => {
+        authorityClient = devEnv.justiesClient("authority");
+      });
+
+      after(async () => {
+        await authorityClient.setFeeTiers([]);
+      });
+
+      it("Error - set invalid fee tiers", async () => {
+        // The volume thresholds aren't ascending.
+        await expect(authorityClient.setFeeTiers([
+          {minVolume: new BN(1000), marketFeeRateBps: 150},
+          {minVolume: new BN(1000), marketFeeRateBps: 100},
+        ])).to.eventually.be.rejectedWith("Error Code: InvalidFeeTiers");
+        // The rate exceeds the global market fee rate.
+        const globalStates = await authorityClient.fetchGlobalStates();
+        await expect(authorityClient.setFeeTiers([
+          {
+            minVolume: new BN(0),
+            marketFeeRateBps: globalStates.marketFeeRateBps + 1,
+          },
+        ])).to.eventually.be.rejectedWith("Error Code: InvalidFeeTiers");
+        return expect(raffleCreatorClient.setFeeTiers([
+          {minVolume: new BN(0), marketFeeRateBps: 100},
+        ])).to.eventually.be.rejectedWith("Error Code: MissingAdminRole");
+      });
+
+      it("Charges the rate of the creator's fee tier", async () => {
+        await createTestRaffle("Gift Card #2", undefined, undefined,
+          undefined, 1);
+        const raffle = await raffleCreatorClient.fetchLatestRaffle();
+        const previousVolume = (await raffleCreatorClient.fetchCreatorVolume(
+          raffleCreatorClient.providerAddress,
+          raffle.currencyTokenMintAddress
+        ))?.totalVolume.toNumber() ?? 0;
+        // Only the 1st tier is reached by the creator's volume.
+        await authorityClient.setFeeTiers([
+          {minVolume: new BN(previousVolume), marketFeeRateBps: 100},
+          {minVolume: new BN(previousVolume + 1), marketFeeRateBps: 50},
+        ]);
+        const entries = await fetchLatestAuditEntries(authorityClient, 4);
+        expect(entries[0].change).to.containSubset({
+          field: {feeTierMinVolume: {}},
+          oldValue: {none: {}},
+          newValue: {u64: {"0": new BN(previousVolume)}},
+        });
+        expect(entries[1].change).to.containSubset({
+          field: {feeTierRateBps: {}},
+          oldValue: {none: {}},
+          newValue: {u16: {"0": 100}},
+        });
+
+        await ticketBuyer1Client.buyRaffleTickets(raffle.id, 10, null);
+        await authorityClient.setMockTimestamp(raffle.expiredTimestamp.addn(10));
+        await authorityClient.setRaffleWinners(raffle.id, [0]);
+        const globalStates = await authorityClient.fetchGlobalStates();
+        const totalRevenue = raffle.ticketPrice.toNumber() * 10;
+        const previousFee = await devEnv.ataTokenAmount(
+          globalStates.feeTreasuryAddress, "USDT");
+
+        await raffleCreatorClient.claimRaffleRevenue(raffle.id);
+
+        expect(await devEnv.ataTokenAmount(
+          globalStates.feeTreasuryAddress, "USDT")).to
+          .eq(previousFee + Math.trunc(totalRevenue * 100 / 10000));
+        // The settlement is recorded in the creator's volume.
+        const creatorVolume = await raffleCreatorClient.fetchCreatorVolume(
+          raffleCreatorClient.providerAddress,
+          raffle.currencyTokenMintAddress
+        );
+        expect(creatorVolume.totalVolume.toNumber()).to
+          .eq(previousVolume + totalRevenue);
+      });
+    });
+    describe("Fee discounts", () => {
+      const usdtHolderGroup: GroupConfig = {
+        groupType: {tokenHolderGroup: {}},
+        key: devEnv.getTokenMintAddress("USDT"),
+      };
+      let authorityClient: JustiesProgramClient;
+
+      before(async () => {
+        authorityClient = devEnv.justiesClient("authority");
+      });
+
+      after(async () => {
+        await authorityClient.setFeeDiscounts([]);
+      });
+
+      it("Error - set invalid fee discounts", async () => {
+        await expect(authorityClient.setFeeDiscounts([
+          {group: usdtHolderGroup, discountBps: 0},
+        ])).to.eventually.be.rejectedWith("Error Code: InvalidFeeDiscounts");
+        // Duplicated groups.
+        await expect(authorityClient.setFeeDiscounts([
+          {group: usdtHolderGroup, discountBps: 5000},
+          {group: usdtHolderGroup, discountBps: 2000},
+        ])).to.eventually.be.rejectedWith("Error Code: InvalidFeeDiscounts");
+        return expect(raffleCreatorClient.setFeeDiscounts([
+          {group: usdtHolderGroup, discountBps: 5000},
+        ])).to.eventually.be.rejectedWith("Error Code: MissingAdminRole");
+      });
+
+      it("Applies the discount of the creator's group", async () => {
+        await authorityClient.setFeeDiscounts([
+          {group: usdtHolderGroup, discountBps: 5000},
+        ]);
+        const [auditEntry] = await fetchLatestAuditEntries(authorityClient, 1);
+        expect(auditEntry.change).to.containSubset({
+          field: {feeDiscountBps: {}},
+          target: usdtHolderGroup.key,
+          oldValue: {none: {}},
+          newValue: {u16: {"0": 5000}},
+        });
+
+        await createTestRaffle("Gift Card #2", undefined, undefined,
+          undefined, 1);
+        const raffle = await raffleCreatorClient.fetchLatestRaffle();
+        await ticketBuyer1Client.buyRaffleTickets(raffle.id, 10, null);
+        await authorityClient.setMockTimestamp(raffle.expiredTimestamp.addn(10));
+        await authorityClient.setRaffleWinners(raffle.id, [0]);
+        const globalStates = await authorityClient.fetchGlobalStates();
+        const totalRevenue = raffle.ticketPrice.toNumber() * 10;
+        const discountedRateBps =
+          Math.trunc(globalStates.marketFeeRateBps * 5000 / 10000);
+        const previousFee = await devEnv.ataTokenAmount(
+          globalStates.feeTreasuryAddress, "USDT");
+
+        // The creator proves holding USDT (i.e.: the revenues from the
+        // previous raffles).
+        await raffleCreatorClient.claimRaffleRevenue(
+          raffle.id,
+          {
+            groupType: {tokenHolderGroup: {}},
+            message: null,
+            ed25519Signature: null,
+          },
+          [
+            {
+              pubkey: devEnv.getTokenAta("raffleCreator", "USDT"),
+              isWritable: false,
+              isSigner: false,
+            },
+          ],
+        );
+
+        expect(await devEnv.ataTokenAmount(
+          globalStates.feeTreasuryAddress, "USDT")).to
+          .eq(previousFee + Math.trunc(totalRevenue * discountedRateBps / 10000));
+      });
     });
     describe("Claim remaining rewards", () => {
       // A helper function for creating a new raffle, buy some tickets and pick
@@ -2951,7 +5116,35 @@ describe("Justies Test", () => {
         await authorityJustiesClient.clearMockTimestamp();
       });
 
+      it("Error - make raffle without the creator states", async () => {
+        const makeRaffle = (remainingAccounts: AccountMeta[]) =>
+          authorityJustiesClient.justiesProgram.methods.makeRaffle(
+            raffleId,
+            false
+          ).accounts({
+            raffle: raffleAddress,
+            ticketPositionStats:
+              authorityJustiesClient.findPdaRaffleTicketPositionStats(
+                raffleAddress),
+            globalStates: authorityJustiesClient.findPdaGlobalStates(),
+            authority: authorityJustiesClient.providerAddress,
+            systemProgram: SystemProgram.programId,
+          }).remainingAccounts(remainingAccounts).rpc();
+        await expect(makeRaffle([])).to.eventually.be.rejectedWith(
+          "Error Code: InvalidCreatorStatesAccount");
+        // The creator states of another creator is rejected as well.
+        await expect(makeRaffle([{
+          pubkey: authorityJustiesClient.findPdaCreatorStates(
+            authorityJustiesClient.providerAddress),
+          isWritable: true,
+          isSigner: false,
+        }])).to.eventually.be.rejectedWith(
+          "Error Code: InvalidCreatorStatesAccount");
+      });
+
       it("Check status after making raffle", async () => {
+        const creatorStates = await raffleCreatorClient.fetchCreatorStates(
+          raffleCreatorClient.providerAddress);
         await authorityJustiesClient.makeRaffle(raffleId, false);
         const raffle = await authorityJustiesClient.fetchLatestRaffle();
         // Expects 2 winners are generated.
@@ -2959,6 +5152,12 @@ describe("Justies Test", () => {
         // Expects that there are 2 unique winners.
         expect(new Set(raffle.winnerIds).size).to.eq(2);
         expect(raffle.status).to.eql({finished: {}});
+        // The made raffle is no longer a live listing of the creator.
+        expect(raffle.countedAsLive).to.be.false;
+        const newCreatorStates = await raffleCreatorClient.fetchCreatorStates(
+          raffleCreatorClient.providerAddress);
+        expect(newCreatorStates.numLiveRaffles).to.eq(
+          creatorStates.numLiveRaffles - 1);
       });
 
       it(